@@ -1,5 +1,8 @@
-import { CggmpExecutor } from '../index';
+import { CggmpExecutor, generateIdentityKey, identityPublicKey, generatePrimes, readEncryptedKeyInfo, verifySignature, simulateKeygen, simulateSigning, dealKeyShares, reconstructSecretKey } from '../index';
 import { Envelope, Round, Curve } from '../src/proto/cggmp';
+import { generateKeyPairSync } from 'crypto';
+import { readFileSync } from 'fs';
+import { join } from 'path';
 
 // 테스트 실행 명령어 
 // pnpm --filter @cggmp/node-binding test -- --runTestsByPath __tests__/executor.test.ts
@@ -10,6 +13,9 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
   const PARTY_INDEX = 0;
   const THRESHOLD = 2;
   const PARTIES_COUNT = 3;
+  // 소수 생성은 오래 걸리므로 미리 생성한 소수로 딜러가 2-of-3 키쉐어를 만듦
+  const primes = [0, 1, 2].map((i) => readFileSync(join(__dirname, `../src/testdata/primes${i}.bin`)));
+  const dealtShares = () => dealKeyShares(Buffer.alloc(32, 0x11), 3, 2, { primes });
 
   let executor: CggmpExecutor;
 
@@ -83,7 +89,7 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
      // We don't expose signers in snapshot currently, but at least it shouldn't throw
   });
 
-  test.skip('유효하지 않은 Envelope(라운드 불일치)를 적절히 처리해야 한다', () => {
+  test('유효하지 않은 Envelope(라운드 불일치)를 적절히 처리해야 한다', () => {
     executor.startKeygen();
     
     // Create an envelope for SIGNING round but state is KEYGEN
@@ -94,6 +100,7 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
       round: Round.KEYGEN, // Mismatch! Payload is signing
       fromParty: 1,
       toParties: [],
+      signature: new Uint8Array(),
       meta: {
         curve: Curve.CURVE_SECP256K1,
         threshold: THRESHOLD,
@@ -118,11 +125,13 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
     const encoded = Buffer.from(Envelope.encode(badEnv).finish());
     const input = [encoded];
 
-    // The Rust binding returns Err for round mismatch in validate_envelope
-    expect(() => executor.step(input)).toThrow(/round mismatch/);
+    const report = executor.stepWithReport(input);
+    expect(report.rejected).toHaveLength(1);
+    expect(report.rejected[0].reason).toBe('ROUND_MISMATCH');
+    expect(report.rejected[0].message).toMatch(/round mismatch/);
   });
 
-  test.skip('세션 ID가 일치하지 않는 Envelope을 거부해야 한다', () => {
+  test('세션 ID가 일치하지 않는 Envelope을 거부해야 한다', () => {
     executor.startKeygen();
     
     const badEnv: Envelope = {
@@ -132,6 +141,7 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
       round: Round.KEYGEN,
       fromParty: 1,
       toParties: [],
+      signature: new Uint8Array(),
       meta: {
         curve: Curve.CURVE_SECP256K1,
         threshold: THRESHOLD,
@@ -148,7 +158,11 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
     const encoded = Buffer.from(Envelope.encode(badEnv).finish());
     const input = [encoded];
 
-    expect(() => executor.step(input)).toThrow(/id mismatch/);
+    const report = executor.stepWithReport(input);
+    expect(report.rejected[0].reason).toBe('SESSION_MISMATCH');
+    expect(report.rejected[0].sender).toBe(1);
+    expect(report.rejected[0].message).toMatch(/id mismatch/);
+    expect(JSON.parse(executor.snapshot()).errors[0].reason).toBe('SESSION_MISMATCH');
   });
 
   test('임계값(threshold)이 일치하지 않는 Envelope을 거부해야 한다', () => {
@@ -161,6 +175,7 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
       round: Round.KEYGEN,
       fromParty: 1,
       toParties: [],
+      signature: new Uint8Array(),
       meta: {
         curve: Curve.CURVE_SECP256K1,
         threshold: 1, // Mismatch (Executor has 2), but valid (<= partiesCount)
@@ -180,7 +195,7 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
     expect(() => executor.step(input)).not.toThrow(); // Current implementation might not check threshold in step validation
   });
 
-  test.skip('잘못된 파티의 AuxInfo를 거부해야 한다 (스푸핑 체크)', () => {
+  test('잘못된 파티의 AuxInfo를 거부해야 한다 (스푸핑 체크)', () => {
     const auxEnv: Envelope = {
       version: 1,
       sessionId: SESSION_ID,
@@ -188,6 +203,7 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
       round: Round.AUX_INFO,
       fromParty: 1, // Another party sending AuxInfo
       toParties: [PARTY_INDEX],
+      signature: new Uint8Array(),
       meta: {
         curve: Curve.CURVE_SECP256K1,
         threshold: THRESHOLD,
@@ -208,4 +224,353 @@ describe('CggmpExecutor 통합 (CggmpExecutor Integration)', () => {
     const snap = JSON.parse(executor.snapshot());
     expect(snap.status).not.toBe('keyshare_ready');
   });
+
+  test('키쉐어 없이 프리서명을 시작하면 에러가 발생해야 한다', () => {
+    expect(() => executor.startPresigning()).toThrow(/keyshare missing/);
+    expect(() => executor.signWithPresignature('00')).toThrow(/keyshare missing/);
+  });
+
+  test('프리서명이 없으면 export가 실패해야 한다', () => {
+    const snap = JSON.parse(executor.snapshot());
+    expect(snap.hasPresignature).toBe(false);
+    expect(() => executor.exportPresignature()).toThrow(/presignature not ready/);
+    expect(() => executor.importPresignature(Buffer.from('{}'))).toThrow(/parse json/);
+  });
+
+  test('키쉐어 없이 키 갱신을 시작하면 에러가 발생해야 한다', () => {
+    expect(() => executor.startKeyRefresh()).toThrow(/keyshare missing/);
+    expect(() => executor.commitKeyRefresh()).toThrow(/no refreshed key share to commit/);
+    const snap = JSON.parse(executor.snapshot());
+    expect(snap.phase).toBe('INIT');
+    expect(snap.hasPendingKeyshare).toBe(false);
+  });
+
+  test('secp256r1 곡선을 선택할 수 있어야 한다', () => {
+    const p256 = new CggmpExecutor(SESSION_ID, EXECUTION_ID, PARTY_INDEX, THRESHOLD, PARTIES_COUNT, 'secp256r1');
+    expect(JSON.parse(p256.snapshot()).curve).toBe('secp256r1');
+    expect(JSON.parse(executor.snapshot()).curve).toBe('secp256k1');
+
+    p256.startKeygen();
+    const env = Envelope.decode(p256.step([])[0]);
+    expect(env.meta?.curve).toBe(Curve.CURVE_SECP256R1);
+
+    expect(() => new CggmpExecutor(SESSION_ID, EXECUTION_ID, PARTY_INDEX, THRESHOLD, PARTIES_COUNT, 'ed25519')).toThrow(/unsupported curve/);
+  });
+
+  test('stark 곡선을 선택할 수 있어야 한다', () => {
+    const stark = new CggmpExecutor(SESSION_ID, EXECUTION_ID, PARTY_INDEX, THRESHOLD, PARTIES_COUNT, 'stark');
+    expect(JSON.parse(stark.snapshot()).curve).toBe('stark');
+
+    stark.startKeygen();
+    const env = Envelope.decode(stark.step([])[0]);
+    expect(env.meta?.curve).toBe(Curve.CURVE_STARK);
+  });
+
+  test('디코딩할 수 없는 payload는 거부 사유와 함께 보고해야 한다', () => {
+    executor.startKeygen();
+
+    const env: Envelope = {
+      version: 1,
+      sessionId: SESSION_ID,
+      executionId: EXECUTION_ID,
+      round: Round.KEYGEN,
+      fromParty: 2,
+      toParties: [],
+      signature: new Uint8Array(),
+      meta: undefined,
+      keygen: { payload: new Uint8Array([0xff]) }
+    };
+    const garbage = Buffer.from([0xff, 0xff, 0xff]);
+
+    const report = executor.stepWithReport([garbage, Buffer.from(Envelope.encode(env).finish())]);
+    expect(report.rejected).toEqual([
+      expect.objectContaining({ index: 0, reason: 'MALFORMED_ENVELOPE' }),
+      expect.objectContaining({ index: 1, sender: 2, reason: 'UNDECODABLE_PAYLOAD' }),
+    ]);
+    expect(report.rejected[0].sender).toBeUndefined();
+    expect(JSON.parse(executor.snapshot()).errors).toHaveLength(2);
+  });
+
+  test('다른 파티의 Error Envelope를 받으면 중단 정보를 기록해야 한다', () => {
+    executor.startKeygen();
+
+    const errEnv: Envelope = {
+      version: 1,
+      sessionId: SESSION_ID,
+      executionId: EXECUTION_ID,
+      round: Round.ERROR,
+      fromParty: 1,
+      toParties: [],
+      signature: new Uint8Array(),
+      meta: undefined,
+      error: { code: 1, message: 'decommitment mismatch', retriable: false, reason: 'InvalidDecommitment', blamedParties: [2] }
+    };
+
+    const report = executor.stepWithReport([Buffer.from(Envelope.encode(errEnv).finish())]);
+    expect(report.outgoing).toHaveLength(0);
+    expect(report.abort).toMatchObject({ protocol: 'keygen', kind: 'PEER_ABORTED', reason: 'InvalidDecommitment', blamedParties: [2], reportedBy: 1 });
+
+    const snap = JSON.parse(executor.snapshot());
+    expect(snap.status).toBe('keygen_aborted');
+    expect(snap.abort.reportedBy).toBe(1);
+  });
+
+  test('stepAsync 실행 중에는 다른 호출이 실패해야 한다', async () => {
+    executor.startKeygen();
+
+    const pending = executor.stepAsync([]);
+    expect(() => executor.snapshot()).toThrow(/executor is busy/);
+    expect(() => executor.stepAsync([])).toThrow(/executor is busy/);
+
+    const report = await pending;
+    expect(report.rejected).toHaveLength(0);
+    expect(report.abort).toBeFalsy();
+    expect(report.outgoing.length).toBeGreaterThan(0);
+    expect(Envelope.decode(report.outgoing[0]).round).toBe(Round.KEYGEN);
+    expect(JSON.parse(executor.snapshot()).status).toBe('keygen_running');
+  });
+
+  test('serializeState로 저장한 상태에서 진행 중인 키 생성을 복원해야 한다', () => {
+    executor.startKeygen();
+    const sent = executor.step([]);
+
+    const restored = CggmpExecutor.restore(executor.serializeState());
+    const snap = JSON.parse(restored.snapshot());
+    expect(snap.sessionId).toBe(SESSION_ID);
+    expect(snap.partyIndex).toBe(PARTY_INDEX);
+    expect(snap.status).toBe('keygen_running');
+    expect(restored.sentEnvelopes()).toEqual(sent);
+
+    expect(() => CggmpExecutor.restore(Buffer.from([9, 0, 0, 0]))).toThrow(/unsupported state version/);
+  });
+
+  test('암호화된 키쉐어 가져오기는 잘못된 컨테이너와 키를 거부해야 한다', () => {
+    expect(() => executor.exportKeyshareEncrypted('passphrase')).toThrow(/keyshare not ready/);
+    expect(() => executor.importKeyshareEncrypted(Buffer.from('{}'), 'passphrase')).toThrow(/not an encrypted key container/);
+
+    const header = Buffer.concat([Buffer.from('CGMK'), Buffer.from([1, 1, 1, 2]), Buffer.alloc(24), Buffer.from([0, 0])]);
+    expect(readEncryptedKeyInfo(header)).toMatchObject({ version: 1, content: 'keyshare', curve: 'secp256k1', kdf: 'kek' });
+    expect(() => executor.importKeyshareEncrypted(header, 'passphrase')).toThrow(/encrypted with a KEK/);
+    expect(() => executor.importKeyshareEncrypted(header, Buffer.alloc(16))).toThrow(/KEK must be 32 bytes/);
+    expect(() => executor.importKeyshareEncrypted(header, Buffer.alloc(32))).toThrow(/decryption failed/);
+  });
+
+  test('바이너리 형식 가져오기는 버전과 내용 종류를 확인해야 한다', () => {
+    expect(() => executor.exportKeyshareBin()).toThrow(/keyshare not ready/);
+
+    const header = (version: number, content: number, curve: number) => Buffer.from([0x43, 0x47, 0x4d, 0x42, version, content, curve]);
+    expect(() => executor.importKeyshare(header(9, 1, 1))).toThrow(/unsupported binary format version/);
+    expect(() => executor.importAuxInfo(header(1, 1, 1))).toThrow(/expected aux_info, got keyshare/);
+    expect(() => executor.importKeyshare(header(1, 1, 2))).toThrow(/curve mismatch/);
+  });
+
+  test('키쉐어 없이 validateKeyshare를 호출하면 에러가 발생해야 한다', () => {
+    expect(() => executor.validateKeyshare()).toThrow(/keyshare missing/);
+    executor.startKeygen();
+    expect(() => executor.validateKeyshare()).toThrow(/keyshare missing/);
+  });
+
+  test('키쉐어 없이 자식 공개키를 파생하면 에러가 발생해야 한다', () => {
+    expect(() => executor.derivePublicKey('m/0/1')).toThrow(/keyshare missing/);
+    expect(() => executor.startSigning('00'.repeat(32), { derivationPath: 'm/0/1' })).toThrow(/keyshare missing/);
+    expect(() => executor.startSigning('00'.repeat(32), { derivationPath: "m/44'/0" })).toThrow(/hardened derivation is not supported/);
+  });
+
+  test('지원하지 않는 해싱 방식으로 서명을 시작하면 에러가 발생해야 한다', () => {
+    expect(() => executor.startSigning('00'.repeat(32), { hashMode: 'md5' })).toThrow(/unsupported hash mode: md5/);
+    expect(() => executor.signWithPresignature('00'.repeat(32), { hashMode: 'md5' })).toThrow(/unsupported hash mode: md5/);
+  });
+
+  test('서명 전에 서명을 인코딩하면 에러가 발생해야 한다', () => {
+    expect(() => executor.getSignature('der')).toThrow(/signature not ready/);
+    expect(() => executor.getSignature('raw')).toThrow(/unsupported signature format: raw/);
+  });
+
+  test('verifySignature는 DER와 Ethereum 서명을 검증해야 한다', () => {
+    const publicKey = '024b33c8be63aa2bfbc1307cdbe597c389fa6d15e13cdcf278ae6098b649b84919';
+    const eth = Buffer.from('281748d26cdf5c74655842f12492ac1da9c593aa534aa87a9587cc3bf201edee24d764fd273d3d072e4c977bcbecda00bebd4d8726223f0735c79f52ff35c0151b', 'hex');
+    const der = Buffer.from('30440220281748d26cdf5c74655842f12492ac1da9c593aa534aa87a9587cc3bf201edee022024d764fd273d3d072e4c977bcbecda00bebd4d8726223f0735c79f52ff35c015', 'hex');
+    expect(verifySignature(publicKey, 'aa', eth, 'keccak256')).toBe(true);
+    expect(verifySignature(publicKey, 'aa', der, 'keccak256')).toBe(true);
+    expect(verifySignature(publicKey, 'aa', der, 'sha256')).toBe(false);
+    expect(verifySignature(publicKey, 'ab', eth, 'keccak256')).toBe(false);
+    expect(() => verifySignature(publicKey, 'aa', Buffer.alloc(10), 'keccak256')).toThrow(/invalid signature encoding/);
+    expect(verifySignature(publicKey, 'aa', der, 'keccak256', 'secp256k1', 'der')).toBe(true);
+    expect(() => verifySignature(publicKey, 'aa', der, 'keccak256', 'secp256k1', 'compact')).toThrow(/compact signature must be 64 bytes/);
+    expect(() => verifySignature(publicKey, 'aa', eth, 'keccak256', 'secp256r1', 'ethereum')).toThrow(/not supported on the secp256r1 curve/);
+  });
+
+  test('simulateKeygen은 같은 공개키의 core 키쉐어를 파티 수만큼 반환해야 한다', () => {
+    const cores = simulateKeygen(3, 2).map((c) => JSON.parse(c.toString()));
+    expect(cores.map((c) => c.i)).toEqual([0, 1, 2]);
+    expect(new Set(cores.map((c) => c.shared_public_key)).size).toBe(1);
+    expect(() => simulateKeygen(3, 4)).toThrow(/invalid parameters/);
+    expect(() => simulateSigning([], [0, 1], '00')).toThrow(/key share of signer 0 missing/);
+  });
+
+  // aux info 생성은 오래 걸리므로 미리 생성한 소수로 딜러가 만든 키쉐어의 aux info를 사용
+  test('시뮬레이션으로 만든 키쉐어로 서명해야 한다', () => {
+    const auxInfos = dealtShares().map((ks) => Buffer.from(JSON.stringify(JSON.parse(ks.toString()).aux)));
+    const shares = simulateKeygen(3, 2, auxInfos);
+    const signer = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 1, THRESHOLD, PARTIES_COUNT);
+    signer.importKeyshare(shares[1]);
+    const publicKey = JSON.parse(signer.snapshot()).publicKey;
+    const sig = simulateSigning(shares, [1, 2], 'abcd', { hashMode: 'keccak256' });
+    expect(verifySignature(publicKey, 'abcd', sig, 'keccak256')).toBe(true);
+    const typedData = Buffer.from(JSON.stringify({
+      types: { Mail: [{ name: 'contents', type: 'string' }] },
+      primaryType: 'Mail',
+      domain: { name: 'Ether Mail', chainId: 1 },
+      message: { contents: 'Hello, Bob!' },
+    })).toString('hex');
+    const typedSig = simulateSigning(shares, [0, 2], typedData, { hashMode: 'eip712' });
+    expect(verifySignature(publicKey, typedData, typedSig, 'eip712')).toBe(true);
+  });
+
+  test('식별 키 없이 키 갱신을 시작하면 에러가 발생해야 한다', () => {
+    const executor = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 0, 2, 3);
+    executor.importKeyshare(dealtShares()[0]);
+    expect(() => executor.startKeyRefreshWithPrimes(primes[0])).toThrow(/key refresh requires setIdentity\(\)/);
+    expect(JSON.parse(executor.snapshot()).phase).toBe('INIT');
+  });
+
+  test('dealKeyShares는 잘못된 비밀키와 파라미터를 거부해야 한다', () => {
+    expect(() => dealKeyShares(Buffer.alloc(32), 3, 2)).toThrow(/invalid secret key: zero/);
+    expect(() => dealKeyShares(Buffer.alloc(32, 0xff), 3, 2)).toThrow(/less than the curve order/);
+    expect(() => dealKeyShares(Buffer.alloc(32, 0x11), 3, 4)).toThrow(/invalid parameters/);
+    expect(() => dealKeyShares(Buffer.alloc(32, 0x11), 3, 2, { chainCode: Buffer.alloc(5) })).toThrow(/chain code must be 32 bytes/);
+  });
+
+  test('key-export 기능 없이 빌드하면 비밀키 복원을 거부해야 한다', () => {
+    expect(() => reconstructSecretKey([])).toThrow(/build with the `key-export` cargo feature/);
+  });
+
+  test('startReshare는 역할에 맞지 않는 옵션을 거부해야 한다', () => {
+    const oldIndices = [0, 1, 2, null, null];
+    const newIndices = [0, 1, null, 2, 3];
+    const joiner = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 3, 3, 5);
+    expect(() => joiner.exportKeyInfo()).toThrow(/keyshare missing/);
+    expect(() => joiner.startReshare({ oldIndices, newIndices })).toThrow(/key info required/);
+    expect(() => joiner.startReshare({ oldIndices: [0], newIndices, keyInfo: Buffer.from('{}') })).toThrow(/expected old and new indices for 5 parties/);
+    expect(() => joiner.startReshare({ oldIndices, newIndices: [0, 1, null, 2, 2], keyInfo: Buffer.from('{}') })).toThrow(/new indices must be 0..4/);
+    expect(() => joiner.startReshare({ oldIndices, newIndices, keyInfo: Buffer.from('{}') })).toThrow(/invalid key info/);
+    const member = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 0, 2, 3);
+    member.importKeyshare(dealtShares()[0]);
+    expect(() => joiner.startReshare({ oldIndices, newIndices, keyInfo: member.exportKeyInfo() })).toThrow(/reshare requires setIdentity\(\)/);
+    expect(JSON.parse(joiner.snapshot()).status).toBe('init');
+  });
+
+  test('discardKeyshare는 키쉐어와 aux info를 삭제해야 한다', () => {
+    const executor = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 2, 2, 3);
+    executor.importKeyshare(dealtShares()[2]);
+    executor.discardKeyshare();
+    expect(() => executor.exportKeyshare()).toThrow(/keyshare not ready/);
+    expect(() => executor.exportAuxInfo()).toThrow(/aux info not ready/);
+    expect(JSON.parse(executor.snapshot()).status).toBe('init');
+  });
+
+  test('setIdentity 후에는 서명이 없거나 다른 파티 키로 서명한 Envelope를 거부해야 한다', () => {
+    const keys = [0, 1, 2].map(() => generateIdentityKey());
+    const roster = keys.map((k) => k.publicKey);
+    expect(keys[0].secretKey.length).toBe(32);
+    expect(keys[0].publicKey.length).toBe(64);
+    expect(identityPublicKey(keys[0].secretKey)).toEqual(keys[0].publicKey);
+    // Node.js crypto의 Ed25519 키: 식별 공개키의 앞 32바이트가 JWK `x`
+    const jwk = generateKeyPairSync('ed25519').privateKey.export({ format: 'jwk' });
+    expect(identityPublicKey(Buffer.from(jwk.d!, 'base64url')).subarray(0, 32)).toEqual(Buffer.from(jwk.x!, 'base64url'));
+    expect(() => executor.setIdentity(keys[1].secretKey, roster)).toThrow(/does not match the roster entry of party 0/);
+    expect(() => executor.setIdentity(keys[0].secretKey, roster.slice(1))).toThrow(/expected 3 identity public keys/);
+    executor.setIdentity(keys[0].secretKey, roster);
+    executor.startKeygen();
+
+    const unsigned = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 1, THRESHOLD, PARTIES_COUNT);
+    unsigned.startKeygen();
+    // 파티 2의 키로 서명했지만 from_party는 1
+    const forged = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 1, THRESHOLD, PARTIES_COUNT);
+    forged.setIdentity(keys[2].secretKey, [roster[0], keys[2].publicKey, roster[2]]);
+    forged.startKeygen();
+    const signed = new CggmpExecutor(SESSION_ID, EXECUTION_ID, 1, THRESHOLD, PARTIES_COUNT);
+    signed.setIdentity(keys[1].secretKey, roster);
+    signed.startKeygen();
+
+    const report = executor.stepWithReport([...unsigned.step([]), ...forged.step([]), ...signed.step([])]);
+    expect(report.rejected.map((r) => [r.reason, r.message])).toEqual([
+      ['INVALID_SIGNATURE', 'missing envelope signature'],
+      ['INVALID_SIGNATURE', 'envelope signature does not match party 1'],
+    ]);
+  });
+
+  test('setIdentity 후 P2P Envelope는 수신자만 복호화해 처리해야 한다', () => {
+    const keys = [0, 1, 2].map(() => generateIdentityKey());
+    const roster = keys.map((k) => k.publicKey);
+    const parties = [0, 1, 2].map((i) => {
+      const party = new CggmpExecutor(SESSION_ID, EXECUTION_ID, i, THRESHOLD, PARTIES_COUNT);
+      party.setIdentity(keys[i].secretKey, roster);
+      party.startKeygen();
+      return party;
+    });
+    const round1 = parties.map((party) => party.step([]));
+    const round2 = parties[0].step([...round1[1], ...round1[2]]);
+    const p2p = round2.filter((bytes) => Envelope.decode(bytes).toParties.length === 1);
+    expect(p2p.map((bytes) => Envelope.decode(bytes).toParties[0]).sort()).toEqual([1, 2]);
+
+    parties[1].step([...round1[0], ...round1[2]]);
+    const toParty1 = p2p.find((bytes) => Envelope.decode(bytes).toParties[0] === 1)!;
+    const toParty2 = p2p.find((bytes) => Envelope.decode(bytes).toParties[0] === 2)!;
+    const report = parties[1].stepWithReport([toParty2, toParty1]);
+    expect(report.rejected.map((r) => r.reason)).toEqual(['NOT_ADDRESSED']);
+
+    // 식별 키는 저장 상태에 포함되지 않으므로 복원할 때 다시 전달
+    const state = parties[1].serializeState();
+    expect(() => CggmpExecutor.restore(state)).toThrow(/identity key required/);
+    expect(CggmpExecutor.restore(state, keys[1].secretKey).sentEnvelopes()).toEqual(parties[1].sentEnvelopes());
+  });
+
+  test('setReliableBroadcast(true)이면 키 생성에 Reliability Check 라운드가 추가되어야 한다', () => {
+    expect(JSON.parse(executor.snapshot()).reliableBroadcast).toBeNull();
+    const parties = [0, 1, 2].map((i) => {
+      const party = new CggmpExecutor(SESSION_ID, EXECUTION_ID, i, THRESHOLD, PARTIES_COUNT);
+      party.setReliableBroadcast(true);
+      party.startKeygen();
+      return party;
+    });
+    const round1 = parties.map((party) => party.step([]));
+    const echo = parties[0].step([...round1[1], ...round1[2]]);
+    expect(echo).toHaveLength(1);
+    expect(Envelope.decode(echo[0]).toParties).toEqual([]);
+    const snapshot = JSON.parse(parties[0].snapshot());
+    expect(snapshot.internalRound).toBe('Reliability Check');
+    expect(snapshot.reliableBroadcast).toBe(true);
+
+    const restored = CggmpExecutor.restore(parties[0].serializeState());
+    expect(JSON.parse(restored.snapshot()).internalRound).toBe('Reliability Check');
+  });
+
+  test('setExecutionId는 32바이트만 받고, 파티마다 다르면 키 생성이 중단되어야 한다', () => {
+    expect(() => executor.setExecutionId(Buffer.alloc(16))).toThrow(/execution id must be 32 bytes, got 16/);
+    const ids = [Buffer.alloc(32, 1), Buffer.alloc(32, 1), Buffer.alloc(32, 2)];
+    const parties = [0, 1, 2].map((i) => {
+      const party = new CggmpExecutor(SESSION_ID, EXECUTION_ID, i, THRESHOLD, PARTIES_COUNT);
+      party.setExecutionId(ids[i]);
+      party.startKeygen();
+      return party;
+    });
+    const others = (outputs: Buffer[][], i: number) => outputs.filter((_, j) => j !== i).flat()
+      .filter((bytes) => { const to = Envelope.decode(bytes).toParties; return to.length === 0 || to.includes(i); });
+    const round1 = parties.map((party) => party.step([]));
+    const round2 = parties.map((party, i) => party.step(others(round1, i)));
+    const report = parties[0].stepWithReport(others(round2, 0));
+    expect(report.abort?.protocol).toBe('keygen');
+    expect(report.abort?.blamedParties).toEqual([2]);
+    expect(JSON.parse(parties[0].snapshot()).status).toBe('keygen_aborted');
+
+    const restored = CggmpExecutor.restore(parties[0].serializeState());
+    expect(JSON.parse(restored.snapshot()).status).toBe('keygen_aborted');
+  });
+
+  test('deterministic-rng 기능 없이 빌드하면 고정 시드 RNG를 거부해야 한다', () => {
+    expect(() => CggmpExecutor.withSeed(SESSION_ID, EXECUTION_ID, PARTY_INDEX, THRESHOLD, PARTIES_COUNT, Buffer.alloc(32))).toThrow(/build with the `deterministic-rng` cargo feature/);
+    expect(() => generatePrimes(Buffer.alloc(32))).toThrow(/build with the `deterministic-rng` cargo feature/);
+    expect(() => simulateKeygen(3, 2, undefined, undefined, Buffer.alloc(32))).toThrow(/build with the `deterministic-rng` cargo feature/);
+  });
 });
\ No newline at end of file
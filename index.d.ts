@@ -3,20 +3,265 @@
 
 /* auto-generated by NAPI-RS */
 
-export function generatePrimes(): Buffer
+/** `step()` 입력 중 처리되지 않은 메시지 */
+export interface MessageRejection {
+  /** 메시지를 전달한 `step()` 호출의 입력 배열 인덱스 */
+  index: number
+  /** Envelope의 `from_party` (Envelope 디코딩 실패 시 없음) */
+  sender?: number
+  /** 거부 사유 코드 (`RejectReason`) */
+  reason: string
+  message: string
+}
+export interface StepReport {
+  outgoing: Array<Buffer>
+  rejected: Array<MessageRejection>
+  /** 이번 호출에서 프로토콜이 중단된 경우 그 정보 */
+  abort?: ProtocolAbort
+}
+/** `startSigning()` / `signWithPresignature()` 옵션 */
+export interface SigningOptions {
+  /** "prehashed", "sha256", "sha256d", "keccak256", "eip191", "eip712", "eip712-hashed" (미지정 시 32바이트는 prehash, 그 외는 SHA-256) */
+  hashMode?: string
+  /** HD 파생 경로 ("m/0/5" 형식, 비강화 인덱스만). 프리서명 서명에서는 지원하지 않음 */
+  derivationPath?: string
+}
+/** `startReshare()` 옵션. 인덱스 배열은 이번 실행의 파티 인덱스 순서입니다. */
+export interface ReshareOptions {
+  /** 각 파티의 기존 키쉐어 인덱스 (기존 위원회가 아니면 null) */
+  oldIndices: Array<number | undefined | null>
+  /** 각 파티의 새 키쉐어 인덱스 (새 위원회가 아니면 null). 새 위원회는 0부터 빈틈없이 번호를 매깁니다 */
+  newIndices: Array<number | undefined | null>
+  /** 기존 위원회 파티의 키쉐어 (`exportKeyshare()` 결과) */
+  keyShare?: Buffer
+  /** 키쉐어가 없는 파티가 사용할 기존 키의 공개 정보 (`exportKeyInfo()` 결과) */
+  keyInfo?: Buffer
+  /** 새 위원회 파티의 aux info 생성에 사용할 소수 (`generatePrimes()` 결과, 미지정 시 생성) */
+  primes?: Buffer
+}
+/** 프로토콜 중단 정보 (식별 가능한 중단) */
+export interface ProtocolAbort {
+  /** "keygen", "aux_gen", "signing", "presigning", "key_refresh", "reshare" */
+  protocol: string
+  /** `AbortKind` 코드 */
+  kind: string
+  /** cggmp24 중단 사유 (예: "EncProofOfK", "InvalidDecommitment") */
+  reason: string
+  /** 책임 파티 (서명 시에도 전역 인덱스) */
+  blamedParties: Array<number>
+  /** 다른 파티의 Error Envelope로 중단된 경우 그 파티 */
+  reportedBy?: number
+  message: string
+}
+/** Envelope 인증과 P2P 암호화용 식별 키 쌍 */
+export interface IdentityKeyPair {
+  /** 32바이트 Ed25519 시드 (`setIdentity()`의 `secretKey`) */
+  secretKey: Buffer
+  /** 64바이트 식별 공개키: Ed25519 공개키 ‖ 시드에서 유도한 X25519 공개키 (다른 파티의 `roster`에 등록) */
+  publicKey: Buffer
+}
+export function generateIdentityKey(): IdentityKeyPair
+/** 식별 키(32바이트 시드, Node.js `crypto` Ed25519 JWK의 `d`)의 64바이트 식별 공개키 (`roster` 항목) */
+export function identityPublicKey(secretKey: Buffer): Buffer
+/** `seed`(32바이트, `deterministic-rng` 빌드 전용)를 주면 같은 소수를 만듭니다. */
+export function generatePrimes(seed?: Buffer | undefined | null): Buffer
+/**
+ * 임계값 이상의 키쉐어에서 전체 비밀키(32바이트 big-endian)를 복원하고 공유 공개키와 일치하는지 확인합니다.
+ * 오프라인 복구 절차 전용이며 `key-export` cargo 기능으로 빌드하지 않으면 항상 실패합니다.
+ */
+export function reconstructSecretKey(shares: Array<Buffer>, curve?: string | undefined | null): Buffer
+/** `dealKeyShares()` 옵션 */
+export interface DealerOptions {
+  /** 유지할 BIP-32 체인 코드 (32바이트). 없으면 새로 생성 */
+  chainCode?: Buffer
+  /** 파티별 `generatePrimes()` 결과. 없으면 안전 소수를 병렬로 생성 */
+  primes?: Array<Buffer>
+  /** "secp256k1"(기본값), "secp256r1" 또는 "stark" */
+  curve?: string
+}
+/**
+ * 신뢰 딜러로 기존 비밀키(32바이트 big-endian)를 `n`개 쉐어(임계값 `t`)로 나눕니다. aux info를 포함한 키쉐어를 파티 순서로 반환하며
+ * 각 파티는 `importKeyshare()`로 가져옵니다. 딜러가 모든 쉐어를 알게 되므로 가져온 뒤 `startKeyRefresh()`로 교체해야 합니다.
+ */
+export function dealKeyShares(secretKey: Buffer, n: number, t: number, options?: DealerOptions | undefined | null): Array<Buffer>
+/**
+ * 한 프로세스에서 `n`개 파티의 키 생성(임계값 `t`)을 실행합니다. 테스트와 픽스처 생성용입니다.
+ * `auxInfos`(파티 순서)를 주면 `importKeyshare()`로 가져올 수 있는 키쉐어를, 없으면 aux info 없는 core 키쉐어(JSON)를 반환합니다.
+ * `seed`(32바이트, `deterministic-rng` 빌드 전용)를 주면 같은 시드에서 같은 키쉐어를 만듭니다.
+ */
+export function simulateKeygen(n: number, t: number, auxInfos?: Array<Buffer> | undefined | null, curve?: string | undefined | null, seed?: Buffer | undefined | null): Array<Buffer>
+/**
+ * 한 프로세스에서 `n`개 파티의 aux info 생성을 실행합니다. `primes`(`generatePrimes()` 결과, 파티별)가 없으면 안전 소수를 병렬로 생성합니다.
+ * `seed`는 `simulateKeygen()`과 같습니다.
+ */
+export function simulateAuxGen(n: number, primes?: Array<Buffer> | undefined | null, seed?: Buffer | undefined | null): Array<Buffer>
+/**
+ * 한 프로세스에서 `signers`(키 생성 시 파티 인덱스)의 키쉐어로 메시지(hex)에 서명하고 compact 서명(r ‖ s)을 반환합니다.
+ * `shares`는 `exportKeyshare()` 형식이며 순서는 상관없습니다. 서명은 반환 전에 공개키로 검증됩니다.
+ * `seed`는 `simulateKeygen()`과 같습니다.
+ */
+export function simulateSigning(shares: Array<Buffer>, signers: Array<number>, message: string, options?: SigningOptions | undefined | null, curve?: string | undefined | null, seed?: Buffer | undefined | null): Buffer
+/**
+ * 공개키(압축/비압축 SEC1, hex)와 메시지(hex)로 서명을 검증합니다. `hashMode`는 `startSigning()`과 같은 규칙으로 메시지를 해싱하고,
+ * `curve`는 "secp256k1"(기본값), "secp256r1" 또는 "stark"입니다. `format`("der", "compact", "ethereum", "bitcoin")을 주지 않으면
+ * 서명 길이로 형식을 고릅니다: r ‖ s 길이(64바이트)는 compact, 65바이트는 Ethereum(r ‖ s ‖ v), 그 외는 DER
+ */
+export function verifySignature(publicKey: string, message: string, signature: Buffer, hashMode?: string | undefined | null, curve?: string | undefined | null, format?: string | undefined | null): boolean
+/** 암호화된 키 컨테이너 헤더 (복호화 없이 읽을 수 있는 값) */
+export interface EncryptedKeyInfo {
+  version: number
+  /** "keyshare" 또는 "aux_info" */
+  content: string
+  /** 키쉐어의 곡선 (aux info는 없음) */
+  curve?: string
+  /** "argon2id"(패스프레이즈) 또는 "kek" */
+  kdf: string
+  /** 압축 공개키 (hex) */
+  publicKey?: string
+  /** 공개키의 SHA-256 (hex) */
+  keyId?: string
+}
+/** `exportKeyshareEncrypted()` / `exportAuxInfoEncrypted()` 결과의 헤더를 복호화 없이 읽습니다. */
+export function readEncryptedKeyInfo(data: Buffer): EncryptedKeyInfo
+/** `validateKeyshare()` 결과 */
+export interface KeyshareReport {
+  /** 모든 검사를 통과했는지 여부 (`problems`가 비어 있음) */
+  valid: boolean
+  curve: string
+  /** 압축 공개키 (hex) */
+  publicKey: string
+  /** 공개키의 SHA-256 (hex), 암호화 컨테이너의 키 ID와 같음 */
+  keyId: string
+  partyIndex: number
+  partiesCount: number
+  /** 서명에 필요한 최소 파티 수 */
+  threshold: number
+  hasAux: boolean
+  /** aux info가 있고 키쉐어와 일치하는지 여부 (파티 수, N_i = p * q) */
+  auxConsistent: boolean
+  problems: Array<string>
+}
+/** `generatePrimes()`를 libuv 스레드 풀에서 실행합니다. */
+export function generatePrimesAsync(seed?: Buffer | undefined | null): Promise<Buffer>
 export class CggmpExecutor {
-  constructor(sessionId: string, executionId: string, partyIndex: number, threshold: number, partiesCount: number)
+  /** `curve`는 "secp256k1"(기본값), "secp256r1" 또는 "stark" */
+  constructor(sessionId: string, executionId: string, partyIndex: number, threshold: number, partiesCount: number, curve?: string | undefined | null)
+  /** RNG를 `seed`(32바이트)로 초기화한 실행기. 같은 시드와 입력이면 같은 Envelope를 만들므로 골든 테스트 벡터에 씁니다 (`deterministic-rng` 빌드 전용). */
+  static withSeed(sessionId: string, executionId: string, partyIndex: number, threshold: number, partiesCount: number, seed: Buffer, curve?: string | undefined | null): CggmpExecutor
   exportKeyshare(): Buffer
   exportAuxInfo(): Buffer
   importKeyshare(data: Buffer): void
   importAuxInfo(data: Buffer): void
+  /**
+   * 실행기의 키쉐어, aux info, 프리서명을 삭제합니다. 재분배로 위원회에서 빠진 파티는 새 위원회가
+   * 새 쉐어로 서명하는 등 확인을 마친 뒤 호출합니다. 프로토콜 실행 중에는 호출할 수 없습니다.
+   */
+  discardKeyshare(): void
+  /**
+   * 키쉐어를 암호화해 내보냅니다. `secret`이 문자열이면 패스프레이즈(Argon2id), Buffer면 32바이트 KEK입니다.
+   * 공개키와 키 ID는 헤더에 평문으로 남습니다.
+   */
+  exportKeyshareEncrypted(secret: string | Buffer): Buffer
+  importKeyshareEncrypted(data: Buffer, secret: string | Buffer): void
+  /** aux info를 암호화해 내보냅니다 (Paillier 비밀키 포함). */
+  exportAuxInfoEncrypted(secret: string | Buffer): Buffer
+  importAuxInfoEncrypted(data: Buffer, secret: string | Buffer): void
   startKeygen(): void
   startAuxGen(): void
   startAuxGenWithPrimes(primesBuf: Buffer): void
+  /**
+   * 기존 키쉐어를 새 쉐어로 교체합니다 (공개키 유지, aux info 재생성). 모든 파티가 참여해야 하고,
+   * 새 쉐어를 P2P로 주고받으므로 먼저 `setIdentity()`가 필요합니다.
+   */
+  startKeyRefresh(): void
+  startKeyRefreshWithPrimes(primesBuf: Buffer): void
+  /**
+   * 키 갱신이나 재공유로 받은 새 쉐어를 설치하고 기존 쉐어와 프리서명을 버립니다. 모든 파티가 `key_refresh_finished`
+   * (재공유는 새 위원회 전체가 `reshare_finished`)에 도달했음을 별도 채널로 확인한 뒤 호출합니다. 그 전까지는 기존 쉐어로 서명하고 내보낼 수 있습니다.
+   */
+  commitKeyRefresh(): void
+  /**
+   * 기존 위원회의 키를 파티 수와 threshold가 다른 새 위원회로 재공유합니다 (공개키 유지).
+   * 실행기는 이번 실행의 참가자(기존 ∪ 새 위원회) 기준으로 만들고 `threshold`에 새 threshold를 지정합니다.
+   * 완료 후 새 위원회 파티는 `commitKeyRefresh()`로 새 쉐어를 설치하고, `exportKeyshare()` 결과를 새 파티 인덱스로 만든 실행기에 가져옵니다.
+   * 새 쉐어를 P2P로 암호화해 보내므로 `setIdentity()`가 필요합니다.
+   */
+  startReshare(options: ReshareOptions): void
+  /** 키쉐어의 공개 정보 (공개키, 공개 쉐어, VSS 설정, 체인 코드). 재공유에서 키쉐어가 없는 새 파티에 전달합니다. */
+  exportKeyInfo(): Buffer
+  /**
+   * Envelope 인증을 켭니다. `secretKey`는 이 파티의 Ed25519 식별 키(32바이트 시드), `roster`는 파티 인덱스 순서의 64바이트 식별 공개키입니다.
+   * 이후 보내는 Envelope에 서명하고, 서명이 없거나 `from_party`의 공개키와 맞지 않는 입력은 `INVALID_SIGNATURE`로 거부합니다.
+   * P2P payload는 수신자의 X25519 공개키(식별 공개키 뒤 32바이트)로 암호화하며, 복호화할 수 없는 입력은 `UNDECRYPTABLE_PAYLOAD`로 거부합니다.
+   */
+  setIdentity(secretKey: Buffer, roster: Array<Buffer>): void
+  /**
+   * 다음에 시작하는 프로토콜의 ExecutionId(32바이트)를 지정합니다. 한 번의 `start*()` 호출에만 쓰이며, 세션의 모든 파티가 같은 값을 써야 합니다.
+   * 지정하지 않으면 세션/실행 ID, 프로토콜, 곡선, n, t, 참여 파티, 키 ID와 메시지 해시에서 유도합니다.
+   * 같은 실행기에서 이미 쓴 ExecutionId로는 다시 시작할 수 없으므로, 같은 파라미터로 재시도할 때는 새 값을 지정해야 합니다.
+   */
+  setExecutionId(id: Buffer): void
+  /**
+   * 브로드캐스트 메시지마다 Reliability Check 라운드를 추가해 모든 파티가 같은 메시지를 받았는지 확인합니다 (cggmp24 `enforce_reliable_broadcast`).
+   * 이후 시작하는 키 생성, aux gen, 키 갱신, 재공유, 서명과 프리사인에 적용되며, 설정하지 않으면 키 생성/aux gen은 끄고 서명/프리사인은 켭니다.
+   * 세션의 모든 파티가 같은 값을 써야 합니다.
+   */
+  setReliableBroadcast(enabled: boolean): void
   setSigners(json: string): void
-  startSigning(txHex: string): void
+  /**
+   * `options.hashMode`로 메시지 해싱 방식을, `options.derivationPath`로 서명할 HD 자식 키를 지정합니다.
+   * 모든 서명자가 같은 옵션을 지정해야 하며, 다른 옵션으로 보낸 메시지는 거부됩니다.
+   */
+  startSigning(txHex: string, options?: SigningOptions | undefined | null): void
+  startPresigning(): void
+  /** 저장된 프리서명을 실행기에서 꺼내 내보냅니다. 내보낸 뒤에는 실행기에 남지 않습니다. */
+  exportPresignature(): Buffer
+  /** 같은 키쉐어와 세션/실행 ID로 만든 프리서명만 가져올 수 있으며, 이미 부분 서명에 사용한 프리서명은 거부됩니다. */
+  importPresignature(data: Buffer): void
+  /**
+   * 저장된 프리서명으로 부분 서명을 발행합니다. 프리서명은 1회 사용 후 폐기됩니다.
+   * 이후 다른 서명자의 부분 서명을 `step()`으로 전달하면 결합된 서명이 완성됩니다.
+   * 먼저 도착해 보관된 부분 서명은 이 호출에서 함께 처리됩니다.
+   */
+  signWithPresignature(txHex: string, options?: SigningOptions | undefined | null): Array<Buffer>
+  /**
+   * 다른 파티가 `step()`에서 반환한 Envelope를 그대로 입력받아 세션/실행 ID, 라운드, 수신자, payload를 검증한 뒤 상태 머신에 전달합니다.
+   * 검증에 실패한 메시지는 건너뛰고 snapshot `errors`에 기록합니다.
+   */
   step(inputs: Array<Buffer>): Array<Buffer>
+  /** `step()`과 동일하며, 거부된 메시지 목록(입력 인덱스, 발신자, 사유 코드)을 함께 반환합니다. */
+  stepWithReport(inputs: Array<Buffer>): StepReport
+  /** `stepWithReport()`를 libuv 스레드 풀에서 실행합니다. 완료될 때까지 다른 메서드 호출은 실패합니다. */
+  stepAsync(inputs: Array<Buffer>): Promise<StepReport>
+  /** `startAuxGen()`을 libuv 스레드 풀에서 실행합니다 (안전 소수 생성 포함). */
+  startAuxGenAsync(): Promise<void>
   snapshot(): string
+  /**
+   * 마지막 서명을 `format`("der", "compact", "ethereum", "bitcoin")으로 인코딩해 반환합니다.
+   * 반환 전에 서명한 공개키(파생 경로가 있으면 자식 공개키)로 서명을 다시 검증합니다.
+   */
+  getSignature(format: string): Buffer
+  /** 키쉐어의 체인 코드로 자식 공개키(압축, hex)를 파생합니다. 경로는 "m/0/5" 형식이며 강화 인덱스는 지원하지 않습니다. */
+  derivePublicKey(path: string): string
+  /**
+   * 키쉐어에 cggmp24 검증(공개 쉐어, VSS, Paillier/Pedersen 파라미터, core와 aux 일치)을 다시 실행하고
+   * 실행기의 파티 인덱스, 임계값, 파티 수와 비교한 결과를 반환합니다. 키쉐어가 없으면 예외가 발생합니다.
+   */
+  validateKeyshare(): KeyshareReport
+  /** 키쉐어를 바이너리 형식(`CGMB` 헤더 + CBOR)으로 내보냅니다. `importKeyshare()`가 형식을 자동 판별합니다. */
   exportKeyshareBin(): Buffer
+  /** aux info를 바이너리 형식으로 내보냅니다. `importAuxInfo()`가 형식을 자동 판별합니다. */
   exportAuxInfoBin(): Buffer
+  /**
+   * 진행 중인 세션을 재시작 후 이어가기 위한 상태를 직렬화합니다. 키쉐어와 RNG 시드가 포함되므로 암호화해서 보관해야 합니다.
+   * `setIdentity()`의 식별 키는 포함되지 않으므로 `restore()`에 다시 전달해야 합니다.
+   */
+  serializeState(): Buffer
+  /**
+   * `serializeState()` 결과로 실행기를 복원합니다. 저장 시점까지의 호출을 재실행해 같은 상태와 발신 메시지를 재현합니다.
+   * `setIdentity()`를 호출한 실행기는 같은 식별 키를 `identityKey`로 전달해야 합니다.
+   */
+  static restore(data: Buffer, identityKey?: Buffer | undefined | null): CggmpExecutor
+  /** 현재 프로토콜을 시작한 뒤 보낸 Envelope. 복원 후 메시지를 받지 못한 파티에 다시 보낼 때 사용합니다. */
+  sentEnvelopes(): Array<Buffer>
 }
@@ -250,6 +250,17 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { CggmpExecutor } = nativeBinding
+const { CggmpExecutor, generateIdentityKey, identityPublicKey, generatePrimes, generatePrimesAsync, readEncryptedKeyInfo, verifySignature, simulateKeygen, simulateAuxGen, simulateSigning, dealKeyShares, reconstructSecretKey } = nativeBinding
 
 module.exports.CggmpExecutor = CggmpExecutor
+module.exports.generateIdentityKey = generateIdentityKey
+module.exports.identityPublicKey = identityPublicKey
+module.exports.generatePrimes = generatePrimes
+module.exports.generatePrimesAsync = generatePrimesAsync
+module.exports.readEncryptedKeyInfo = readEncryptedKeyInfo
+module.exports.verifySignature = verifySignature
+module.exports.simulateKeygen = simulateKeygen
+module.exports.simulateAuxGen = simulateAuxGen
+module.exports.simulateSigning = simulateSigning
+module.exports.dealKeyShares = dealKeyShares
+module.exports.reconstructSecretKey = reconstructSecretKey
@@ -0,0 +1,156 @@
+//! 키쉐어/aux info 바이너리 형식
+//!
+//! ```text
+//! magic "CGMB" | version u8 | content u8 | curve u8 | CBOR
+//! ```
+//!
+//! 점과 스칼라는 바이트열로 인코딩됩니다. serde가 16진 문자열(`{ radix: 16, value }`)로 내보내는
+//! Paillier/ring-Pedersen 정수는 CBOR bignum 태그(RFC 8949, tag 2)의 바이트열로 바꿔 저장합니다.
+//! cggmp24 키쉐어의 역직렬화가 `deserialize_any`를 사용하므로 bincode처럼 자기 기술적이지 않은 형식은 쓸 수 없습니다.
+
+use ciborium::Value;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::sealed::Content;
+
+const MAGIC: &[u8; 4] = b"CGMB";
+pub const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 3;
+const TAG_BIGNUM: u64 = 2;
+
+pub fn is_binary(data: &[u8]) -> bool {
+  data.starts_with(MAGIC)
+}
+
+pub fn encode<T: Serialize>(content: Content, curve: u8, value: &T) -> Result<Vec<u8>, String> {
+  let value = Value::serialized(value).map_err(|e| format!("serialize: {e}"))?;
+  let mut out = Vec::with_capacity(HEADER_LEN);
+  out.extend_from_slice(MAGIC);
+  out.extend_from_slice(&[VERSION, content as u8, curve]);
+  ciborium::into_writer(&pack_integers(value), &mut out).map_err(|e| format!("serialize: {e}"))?;
+  Ok(out)
+}
+
+/// 헤더를 확인하고 (내용 종류, 곡선, CBOR 본문)을 반환
+pub fn decode_header(data: &[u8]) -> Result<(Content, u8, &[u8]), String> {
+  if !is_binary(data) || data.len() < HEADER_LEN {
+    return Err("not a binary key export".to_string());
+  }
+  let version = data[4];
+  if version != VERSION {
+    return Err(format!("unsupported binary format version: {version}"));
+  }
+  let content = Content::from_u8(data[5]).ok_or_else(|| format!("unknown binary content: {}", data[5]))?;
+  Ok((content, data[6], &data[HEADER_LEN..]))
+}
+
+pub fn decode_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, String> {
+  let value: Value = ciborium::from_reader(body).map_err(|e| format!("parse binary: {e}"))?;
+  unpack_integers(value).deserialized().map_err(|e| format!("parse binary: {e}"))
+}
+
+/// `{ radix: 16, value: "<hex>" }` → tag 2 bytes
+fn pack_integers(value: Value) -> Value {
+  match value {
+    Value::Map(entries) => {
+      if let Some(bytes) = hex_integer(&entries) {
+        return Value::Tag(TAG_BIGNUM, Box::new(Value::Bytes(bytes)));
+      }
+      Value::Map(entries.into_iter().map(|(k, v)| (k, pack_integers(v))).collect())
+    }
+    Value::Array(items) => Value::Array(items.into_iter().map(pack_integers).collect()),
+    Value::Tag(tag, inner) => Value::Tag(tag, Box::new(pack_integers(*inner))),
+    other => other,
+  }
+}
+
+fn unpack_integers(value: Value) -> Value {
+  match value {
+    Value::Tag(TAG_BIGNUM, inner) => match *inner {
+      Value::Bytes(bytes) => {
+        let hex = hex::encode(bytes);
+        let digits = hex.trim_start_matches('0');
+        Value::Map(vec![
+          (Value::Text("radix".to_string()), Value::Integer(16.into())),
+          (Value::Text("value".to_string()), Value::Text(if digits.is_empty() { "0".to_string() } else { digits.to_string() })),
+        ])
+      }
+      other => Value::Tag(TAG_BIGNUM, Box::new(other)),
+    },
+    Value::Map(entries) => Value::Map(entries.into_iter().map(|(k, v)| (k, unpack_integers(v))).collect()),
+    Value::Array(items) => Value::Array(items.into_iter().map(unpack_integers).collect()),
+    Value::Tag(tag, inner) => Value::Tag(tag, Box::new(unpack_integers(*inner))),
+    other => other,
+  }
+}
+
+/// 음이 아닌 16진 정수 맵이면 big-endian 바이트열
+fn hex_integer(entries: &[(Value, Value)]) -> Option<Vec<u8>> {
+  let [(Value::Text(k1), Value::Integer(radix)), (Value::Text(k2), Value::Text(digits))] = entries else { return None };
+  if k1 != "radix" || k2 != "value" || i128::from(*radix) != 16 || digits.is_empty() {
+    return None;
+  }
+  if digits.len() % 2 == 1 { hex::decode(format!("0{digits}")).ok() } else { hex::decode(digits).ok() }
+}
+
+#[cfg(test)]
+mod tests {
+  use base64::Engine as _;
+  use cggmp24::supported_curves::Secp256k1;
+
+  use super::*;
+  use crate::{parse_key_export, proto, test_util, AuxInfoMsg, KeyShareWithLevel};
+
+  type KeyShare = KeyShareWithLevel<Secp256k1>;
+
+  fn json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap()
+  }
+
+  fn keyshare_json() -> Vec<u8> {
+    test_util::dealt_shares::<Secp256k1>(3, 2).swap_remove(0)
+  }
+
+  #[test]
+  fn keyshare_round_trips() {
+    let ks: KeyShare = serde_json::from_slice(&keyshare_json()).unwrap();
+    let data = encode(Content::KeyShare, proto::Curve::Secp256k1 as u8, &ks).unwrap();
+    assert!(data.len() < keyshare_json().len());
+
+    let (content, curve, body) = decode_header(&data).unwrap();
+    assert!(content == Content::KeyShare);
+    assert_eq!(curve, proto::Curve::Secp256k1 as u8);
+    let decoded: KeyShare = decode_body(body).unwrap();
+    assert_eq!(json(&decoded), json(&ks));
+  }
+
+  #[test]
+  fn aux_info_round_trips() {
+    let ks: KeyShare = serde_json::from_slice(&keyshare_json()).unwrap();
+    let aux: AuxInfoMsg = serde_json::from_value(json(&ks.aux)).unwrap();
+    let data = encode(Content::AuxInfo, proto::Curve::Unspecified as u8, &aux).unwrap();
+    let decoded: AuxInfoMsg = parse_key_export(&data, Content::AuxInfo, None).unwrap();
+    assert_eq!(json(&decoded), json(&aux));
+  }
+
+  #[test]
+  fn import_detects_the_format() {
+    let json_data = keyshare_json();
+    let ks: KeyShare = serde_json::from_slice(&json_data).unwrap();
+    let binary_data = encode(Content::KeyShare, proto::Curve::Secp256k1 as u8, &ks).unwrap();
+    let base64_data = base64::engine::general_purpose::STANDARD.encode(&json_data).into_bytes();
+    assert!(!is_binary(&json_data) && is_binary(&binary_data));
+
+    for data in [&json_data, &binary_data, &base64_data] {
+      let parsed: KeyShare = parse_key_export(data, Content::KeyShare, Some(proto::Curve::Secp256k1)).unwrap();
+      assert_eq!(json(&parsed), json(&ks));
+    }
+
+    let err = |data: &[u8], content, curve| parse_key_export::<KeyShare>(data, content, curve).err().unwrap().reason;
+    assert_eq!(err(&binary_data, Content::AuxInfo, None), "expected aux_info, got keyshare");
+    assert_eq!(err(&binary_data, Content::KeyShare, Some(proto::Curve::Secp256r1)), "curve mismatch: key share is secp256k1");
+    assert!(err(b"CGMB\x09\x01\x01", Content::KeyShare, None).starts_with("unsupported binary format version"));
+    assert!(err(b"not a key share", Content::KeyShare, None).starts_with("not json and not base64"));
+  }
+}
@@ -0,0 +1,98 @@
+//! 신뢰 딜러 키 가져오기 (`cggmp24::trusted_dealer`)
+//!
+//! 기존 단일 서명 비밀키를 `n`개 쉐어(임계값 `t`)로 나누고 aux info까지 만들어, 각 파티가
+//! `importKeyshare()`로 가져올 수 있는 키쉐어를 생성합니다. 공개키(주소)는 그대로 유지됩니다.
+//! 딜러는 비밀키와 모든 쉐어를 알게 되므로, 가져온 뒤 키 갱신으로 쉐어와 aux info를 교체해야 합니다.
+
+use napi::{Error, Result, Status};
+use rand::{rngs::StdRng, SeedableRng};
+use zeroize::Zeroizing;
+
+use cggmp24::key_share::Validate;
+use generic_ec::{NonZero, Scalar, SecretScalar};
+
+use crate::{sim, KeyShareWithLevel, SecLevel, SupportedCurve};
+
+/// `secret_key`(32바이트 big-endian)를 나눈 키쉐어(JSON, 파티 순서)
+pub fn deal<E: SupportedCurve>(secret_key: &[u8], n: u16, t: u16, chain_code: Option<&[u8]>, primes: Option<Vec<Vec<u8>>>) -> Result<Vec<Zeroizing<Vec<u8>>>> {
+  sim::check_parties(n, t)?;
+  let mut scalar = Scalar::<E>::from_be_bytes(secret_key).map_err(|_| Error::new(Status::InvalidArg, "invalid secret key: must be a big-endian integer less than the curve order"))?;
+  let secret_key = NonZero::from_secret_scalar(SecretScalar::new(&mut scalar)).ok_or_else(|| Error::new(Status::InvalidArg, "invalid secret key: zero"))?;
+  let chain_code = chain_code.map(|code| <[u8; 32]>::try_from(code).map_err(|_| Error::new(Status::InvalidArg, format!("chain code must be 32 bytes, got {}", code.len())))).transpose()?;
+  let primes = sim::party_primes(n, primes, &mut sim::SimRng::new(None))?;
+
+  let mut rng = StdRng::from_entropy();
+  let shares: Vec<KeyShareWithLevel<E>> = cggmp24::trusted_dealer::builder::<E, SecLevel>(n)
+    .set_threshold(Some(t))
+    .set_shared_secret_key(secret_key)
+    .set_pregenerated_primes(primes)
+    .generate_shares(&mut rng)
+    .map_err(|e| Error::new(Status::GenericFailure, format!("trusted dealer failed: {e}")))?;
+  shares.into_iter().map(|ks| {
+    let ks = match chain_code {
+      // 기존 BIP-32 키의 체인 코드를 유지하면 비강화 자식 주소도 그대로 유지됨
+      Some(code) => with_chain_code(ks, code)?,
+      None => ks,
+    };
+    serde_json::to_vec(&ks).map(Zeroizing::new).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))
+  }).collect()
+}
+
+fn with_chain_code<E: SupportedCurve>(ks: KeyShareWithLevel<E>, code: [u8; 32]) -> Result<KeyShareWithLevel<E>> {
+  let mut ks = ks.into_inner();
+  ks.core.key_info.chain_code = Some(code);
+  ks.validate().map_err(|e| Error::new(Status::GenericFailure, format!("invalid key share: {}", e.error())))
+}
+
+#[cfg(test)]
+mod tests {
+  use cggmp24::key_share::AnyKeyShare;
+  use cggmp24::supported_curves::Secp256k1;
+  use generic_ec::Point;
+  use generic_ec_zkp::polynomial::lagrange_coefficient_at_zero;
+
+  use super::*;
+  use crate::test_util::{self, SECRET_KEY};
+
+  fn shares(chain_code: Option<&[u8]>) -> Vec<KeyShareWithLevel<Secp256k1>> {
+    deal::<Secp256k1>(&SECRET_KEY, 3, 2, chain_code, Some(test_util::primes(3))).unwrap().iter()
+      .map(|ks| serde_json::from_slice(ks).unwrap())
+      .collect()
+  }
+
+  #[test]
+  fn dealt_shares_split_the_secret_key() {
+    let shares = shares(None);
+    let sk = Scalar::<Secp256k1>::from_be_bytes(SECRET_KEY).unwrap();
+    for (i, ks) in (0..).zip(&shares) {
+      assert_eq!((ks.core.i, ks.min_signers(), ks.n()), (i, 2, 3));
+      assert_eq!(*ks.core.shared_public_key, Point::generator() * sk);
+      assert_eq!(ks.core.public_shares[usize::from(i)], Point::generator() * &ks.core.x);
+    }
+
+    // 임계값 2의 모든 쉐어 쌍이 같은 비밀키로 보간됨
+    let vss = shares[0].core.vss_setup.as_ref().unwrap();
+    for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+      let xs = [vss.I[a], vss.I[b]];
+      let secret: Scalar<Secp256k1> = [a, b].iter().enumerate()
+        .map(|(k, &p)| *lagrange_coefficient_at_zero(k, &xs).unwrap() * AsRef::<Scalar<Secp256k1>>::as_ref(&shares[p].core.x))
+        .sum();
+      assert_eq!(secret, sk);
+    }
+  }
+
+  #[test]
+  fn dealt_shares_keep_the_chain_code() {
+    let code = [9u8; 32];
+    assert!(shares(Some(&code)).iter().all(|ks| ks.core.key_info.chain_code == Some(code)));
+  }
+
+  #[test]
+  fn invalid_inputs_are_rejected() {
+    let err = |sk: &[u8], t: u16, code: Option<&[u8]>| deal::<Secp256k1>(sk, 3, t, code, Some(test_util::primes(3))).err().unwrap().reason;
+    assert_eq!(err(&[0; 32], 2, None), "invalid secret key: zero");
+    assert!(err(&[0xff; 32], 2, None).contains("less than the curve order"));
+    assert!(err(&SECRET_KEY, 4, None).starts_with("invalid parameters"));
+    assert_eq!(err(&SECRET_KEY, 2, Some(&[0; 31])), "chain code must be 32 bytes, got 31");
+  }
+}
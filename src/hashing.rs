@@ -0,0 +1,366 @@
+//! 서명 메시지 해싱 방식
+//!
+//! | 방식 | 서명 대상 |
+//! |------|-----------|
+//! | `prehashed` | 32바이트 입력 그대로 |
+//! | `sha256` | SHA-256(msg) |
+//! | `sha256d` | SHA-256(SHA-256(msg)) (Bitcoin) |
+//! | `keccak256` | Keccak-256(msg) (Ethereum 트랜잭션) |
+//! | `eip191` | Keccak-256("\x19Ethereum Signed Message:\n" ‖ len(msg) ‖ msg) (`personal_sign`) |
+//! | `eip712` | Keccak-256(0x19 0x01 ‖ domainSeparator ‖ hashStruct(message)), 입력은 `eth_signTypedData_v4` typed data JSON(UTF-8) |
+//! | `eip712-hashed` | `eip712`와 같은 다이제스트, 입력은 호출자가 계산한 두 해시를 이은 64바이트 |
+//!
+//! 방식을 지정하지 않으면 이전 동작(32바이트는 prehash, 그 외는 SHA-256)을 따릅니다.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HashMode {
+  /// 방식 미지정 (이전 동작)
+  Auto,
+  Prehashed,
+  Sha256,
+  Sha256d,
+  Keccak256,
+  Eip191,
+  /// typed data JSON
+  Eip712,
+  /// 미리 계산한 `domainSeparator ‖ hashStruct(message)`
+  Eip712Hashed,
+}
+
+impl HashMode {
+  pub fn parse(name: Option<&str>) -> Result<Self, String> {
+    match name {
+      None => Ok(HashMode::Auto),
+      Some("prehashed") => Ok(HashMode::Prehashed),
+      Some("sha256") => Ok(HashMode::Sha256),
+      Some("sha256d") => Ok(HashMode::Sha256d),
+      Some("keccak256") => Ok(HashMode::Keccak256),
+      Some("eip191") => Ok(HashMode::Eip191),
+      Some("eip712") => Ok(HashMode::Eip712),
+      Some("eip712-hashed") => Ok(HashMode::Eip712Hashed),
+      Some(other) => Err(format!("unsupported hash mode: {other}")),
+    }
+  }
+
+  /// Envelope `Signing.hash_mode` 값 (미지정은 빈 문자열)
+  pub fn name(self) -> &'static str {
+    match self {
+      HashMode::Auto => "",
+      HashMode::Prehashed => "prehashed",
+      HashMode::Sha256 => "sha256",
+      HashMode::Sha256d => "sha256d",
+      HashMode::Keccak256 => "keccak256",
+      HashMode::Eip191 => "eip191",
+      HashMode::Eip712 => "eip712",
+      HashMode::Eip712Hashed => "eip712-hashed",
+    }
+  }
+
+  /// 미지정(`Auto`)을 실제 방식으로: 32바이트는 prehash, 그 외는 SHA-256
+  pub fn resolve(self, msg: &[u8]) -> Self {
+    match self {
+      HashMode::Auto if msg.len() == 32 => HashMode::Prehashed,
+      HashMode::Auto => HashMode::Sha256,
+      mode => mode,
+    }
+  }
+
+  /// 서명할 32바이트 다이제스트 (`Auto`는 [`resolve`](Self::resolve) 규칙)
+  pub fn digest(self, msg: &[u8]) -> Result<[u8; 32], String> {
+    Ok(match self {
+      HashMode::Auto => return self.resolve(msg).digest(msg),
+      HashMode::Prehashed => msg.try_into().map_err(|_| format!("prehashed message must be 32 bytes, got {}", msg.len()))?,
+      HashMode::Sha256 => Sha256::digest(msg).into(),
+      HashMode::Sha256d => Sha256::digest(Sha256::digest(msg)).into(),
+      HashMode::Keccak256 => Keccak256::digest(msg).into(),
+      HashMode::Eip191 => eip191_hasher(msg).finalize().into(),
+      HashMode::Eip712 => eip712_hasher(&eip712_typed_data_hashes(msg)?)?.finalize().into(),
+      HashMode::Eip712Hashed => eip712_hasher(msg)?.finalize().into(),
+    })
+  }
+}
+
+/// `personal_sign` 접두사를 붙인 Keccak-256 해셔
+pub fn eip191_hasher(msg: &[u8]) -> Keccak256 {
+  Keccak256::new()
+    .chain_update(b"\x19Ethereum Signed Message:\n")
+    .chain_update(msg.len().to_string())
+    .chain_update(msg)
+}
+
+/// `domainSeparator ‖ hashStruct(message)`(64바이트)에 대한 EIP-712 Keccak-256 해셔
+pub fn eip712_hasher(msg: &[u8]) -> Result<Keccak256, String> {
+  if msg.len() != 64 {
+    return Err(format!("eip712-hashed message must be domainSeparator and hashStruct (64 bytes), got {}", msg.len()));
+  }
+  Ok(Keccak256::new().chain_update([0x19, 0x01]).chain_update(msg))
+}
+
+/// typed data JSON(`types`, `primaryType`, `domain`, `message`)의 `domainSeparator ‖ hashStruct(message)`.
+/// `types`에 `EIP712Domain`이 없으면 `domain`에 있는 필드로 만듭니다.
+pub fn eip712_typed_data_hashes(json: &[u8]) -> Result<[u8; 64], String> {
+  let data: Value = serde_json::from_slice(json).map_err(|e| format!("invalid eip712 typed data: {e}"))?;
+  let field = |name: &str| data.get(name).ok_or_else(|| format!("eip712 typed data has no {name}"));
+  let mut types = BTreeMap::new();
+  for (name, fields) in field("types")?.as_object().ok_or("eip712 types must be an object")? {
+    let fields = fields.as_array().ok_or_else(|| format!("eip712 type {name} must be an array of fields"))?;
+    let fields = fields
+      .iter()
+      .map(|f| match (f.get("name").and_then(Value::as_str), f.get("type").and_then(Value::as_str)) {
+        (Some(n), Some(t)) => Ok((n.to_string(), t.to_string())),
+        _ => Err(format!("eip712 type {name} has a field without name and type")),
+      })
+      .collect::<Result<Vec<_>, String>>()?;
+    types.insert(name.clone(), fields);
+  }
+  let domain = field("domain")?;
+  if !types.contains_key("EIP712Domain") {
+    let known = [("name", "string"), ("version", "string"), ("chainId", "uint256"), ("verifyingContract", "address"), ("salt", "bytes32")];
+    let keys = domain.as_object().ok_or("eip712 domain must be an object")?;
+    if let Some(key) = keys.keys().find(|k| !known.iter().any(|(n, _)| n == k)) {
+      return Err(format!("unknown eip712 domain field {key}: list it in types.EIP712Domain"));
+    }
+    let fields = known.iter().filter(|(n, _)| keys.contains_key(*n)).map(|(n, t)| (n.to_string(), t.to_string())).collect();
+    types.insert("EIP712Domain".to_string(), fields);
+  }
+  let primary = field("primaryType")?.as_str().ok_or("eip712 primaryType must be a string")?;
+  let typed = TypedData { types };
+  let mut out = [0u8; 64];
+  out[..32].copy_from_slice(&typed.hash_struct("EIP712Domain", domain)?);
+  out[32..].copy_from_slice(&typed.hash_struct(primary, field("message")?)?);
+  Ok(out)
+}
+
+/// 구조체 이름 → (필드 이름, 타입) 목록
+struct TypedData {
+  types: BTreeMap<String, Vec<(String, String)>>,
+}
+
+impl TypedData {
+  fn fields(&self, name: &str) -> Result<&[(String, String)], String> {
+    self.types.get(name).map(Vec::as_slice).ok_or_else(|| format!("unknown eip712 type {name}"))
+  }
+
+  /// `encodeType`: 주 타입 뒤에 참조하는 구조체를 이름순으로
+  fn encode_type(&self, primary: &str) -> Result<String, String> {
+    let mut deps = BTreeSet::new();
+    self.collect_deps(primary, &mut deps)?;
+    deps.remove(primary);
+    let mut out = String::new();
+    for name in std::iter::once(primary).chain(deps.iter().map(String::as_str)) {
+      let fields: Vec<_> = self.fields(name)?.iter().map(|(n, t)| format!("{t} {n}")).collect();
+      out += &format!("{name}({})", fields.join(","));
+    }
+    Ok(out)
+  }
+
+  fn collect_deps(&self, name: &str, deps: &mut BTreeSet<String>) -> Result<(), String> {
+    if !deps.insert(name.to_string()) {
+      return Ok(());
+    }
+    for (_, ty) in self.fields(name)? {
+      let base = ty.split('[').next().unwrap_or(ty);
+      if self.types.contains_key(base) {
+        self.collect_deps(base, deps)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// `hashStruct(s) = keccak256(typeHash ‖ encodeData(s))`
+  fn hash_struct(&self, name: &str, value: &Value) -> Result<[u8; 32], String> {
+    let obj = value.as_object().ok_or_else(|| format!("eip712 value of type {name} must be an object"))?;
+    let mut hasher = Keccak256::new().chain_update(Keccak256::digest(self.encode_type(name)?));
+    for (field, ty) in self.fields(name)? {
+      let v = obj.get(field).ok_or_else(|| format!("eip712 {name} has no field {field}"))?;
+      hasher.update(self.encode_value(ty, v)?);
+    }
+    Ok(hasher.finalize().into())
+  }
+
+  fn encode_value(&self, ty: &str, value: &Value) -> Result<[u8; 32], String> {
+    let err = || format!("invalid eip712 value for type {ty}: {value}");
+    if let Some(inner) = ty.strip_suffix(']') {
+      let (item_ty, len) = inner.rsplit_once('[').ok_or_else(err)?;
+      let items = value.as_array().ok_or_else(err)?;
+      if !len.is_empty() && len.parse::<usize>().ok() != Some(items.len()) {
+        return Err(err());
+      }
+      let mut hasher = Keccak256::new();
+      for item in items {
+        hasher.update(self.encode_value(item_ty, item)?);
+      }
+      return Ok(hasher.finalize().into());
+    }
+    if self.types.contains_key(ty) {
+      return self.hash_struct(ty, value);
+    }
+    let mut word = [0u8; 32];
+    match ty {
+      "string" => return Ok(Keccak256::digest(value.as_str().ok_or_else(err)?).into()),
+      "bytes" => return Ok(Keccak256::digest(hex_value(value).ok_or_else(err)?).into()),
+      "bool" => word[31] = value.as_bool().ok_or_else(err)? as u8,
+      "address" => {
+        let bytes = hex_value(value).filter(|b| b.len() == 20).ok_or_else(err)?;
+        word[12..].copy_from_slice(&bytes);
+      }
+      _ => {
+        if let Some(n) = ty.strip_prefix("bytes").and_then(|n| n.parse::<usize>().ok()).filter(|n| (1..=32).contains(n)) {
+          let bytes = hex_value(value).filter(|b| b.len() == n).ok_or_else(err)?;
+          word[..n].copy_from_slice(&bytes);
+        } else if let Some((signed, bits)) = int_type(ty) {
+          word = int_word(value, signed, bits).ok_or_else(err)?;
+        } else {
+          return Err(format!("unknown eip712 type {ty}"));
+        }
+      }
+    }
+    Ok(word)
+  }
+}
+
+fn hex_value(value: &Value) -> Option<Vec<u8>> {
+  let s = value.as_str()?;
+  hex::decode(s.strip_prefix("0x").unwrap_or(s)).ok()
+}
+
+/// `uintN`/`intN` (N은 8의 배수, 8..=256) → (부호 여부, 비트 수)
+fn int_type(ty: &str) -> Option<(bool, usize)> {
+  let (signed, bits) = match ty.strip_prefix('u') {
+    Some(rest) => (false, rest.strip_prefix("int")?),
+    None => (true, ty.strip_prefix("int")?),
+  };
+  let bits = bits.parse::<usize>().ok()?;
+  (bits % 8 == 0 && (8..=256).contains(&bits)).then_some((signed, bits))
+}
+
+/// JSON 숫자나 10진/`0x` 16진 문자열 정수를 범위 검사 후 32바이트 2의 보수로
+fn int_word(value: &Value, signed: bool, bits: usize) -> Option<[u8; 32]> {
+  let text = match value {
+    Value::Number(n) if n.is_i64() || n.is_u64() => n.to_string(),
+    Value::String(s) => s.clone(),
+    _ => return None,
+  };
+  let (negative, digits) = match text.strip_prefix('-') {
+    Some(rest) => (true, rest),
+    None => (false, text.as_str()),
+  };
+  let (radix, digits) = match digits.strip_prefix("0x") {
+    Some(rest) => (16, rest),
+    None => (10, digits),
+  };
+  if digits.is_empty() {
+    return None;
+  }
+  let mut word = [0u8; 32];
+  for c in digits.chars() {
+    let mut carry = c.to_digit(radix)?;
+    for byte in word.iter_mut().rev() {
+      let v = *byte as u32 * radix + carry;
+      *byte = v as u8;
+      carry = v >> 8;
+    }
+    if carry != 0 {
+      return None;
+    }
+  }
+  let len = 256 - word.iter().position(|&b| b != 0).map_or(256, |i| i * 8 + word[i].leading_zeros() as usize);
+  let is_power_of_two = word.iter().map(|b| b.count_ones()).sum::<u32>() == 1;
+  let fits = match (signed, negative) {
+    (false, false) => len <= bits,
+    (false, true) => len == 0,
+    (true, false) => len < bits,
+    (true, true) => len < bits || (len == bits && is_power_of_two),
+  };
+  if !fits {
+    return None;
+  }
+  if negative {
+    let mut carry = 1u16;
+    for byte in word.iter_mut().rev() {
+      let v = (!*byte) as u16 + carry;
+      *byte = v as u8;
+      carry = v >> 8;
+    }
+  }
+  Some(word)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn digest(mode: Option<&str>, msg: &[u8]) -> String {
+    hex::encode(HashMode::parse(mode).unwrap().digest(msg).unwrap())
+  }
+
+  #[test]
+  fn digests_match_known_vectors() {
+    let sha256_abc = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    assert_eq!(digest(Some("sha256"), b"abc"), sha256_abc);
+    assert_eq!(digest(Some("sha256d"), b"abc"), hex::encode(Sha256::digest(hex::decode(sha256_abc).unwrap())));
+    assert_eq!(digest(Some("keccak256"), b""), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+    // personal_sign("hello")
+    assert_eq!(digest(Some("eip191"), b"hello"), "50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750");
+    // EIP-712 명세의 Mail 예제
+    let hashes = hex::decode("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090fc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e").unwrap();
+    assert_eq!(digest(Some("eip712-hashed"), &hashes), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2");
+  }
+
+  const MAIL: &str = r#"{"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"},{"name":"chainId","type":"uint256"},{"name":"verifyingContract","type":"address"}],"Person":[{"name":"name","type":"string"},{"name":"wallet","type":"address"}],"Mail":[{"name":"from","type":"Person"},{"name":"to","type":"Person"},{"name":"contents","type":"string"}]},"primaryType":"Mail","domain":{"name":"Ether Mail","version":"1","chainId":1,"verifyingContract":"0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"},"message":{"from":{"name":"Cow","wallet":"0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},"to":{"name":"Bob","wallet":"0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},"contents":"Hello, Bob!"}}"#;
+
+  #[test]
+  fn typed_data_is_hashed_like_the_eip712_example() {
+    let hashes = eip712_typed_data_hashes(MAIL.as_bytes()).unwrap();
+    assert_eq!(hex::encode(&hashes[..32]), "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f");
+    assert_eq!(hex::encode(&hashes[32..]), "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+    assert_eq!(digest(Some("eip712"), MAIL.as_bytes()), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2");
+    // EIP712Domain 생략 시 domain 필드로 구성
+    let mut data: Value = serde_json::from_str(MAIL).unwrap();
+    data["types"].as_object_mut().unwrap().remove("EIP712Domain");
+    assert_eq!(eip712_typed_data_hashes(data.to_string().as_bytes()).unwrap(), hashes);
+  }
+
+  #[test]
+  fn typed_data_encodes_arrays_bytes_and_signed_integers() {
+    let data = r#"{"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"chainId","type":"uint256"},{"name":"salt","type":"bytes32"}],"Order":[{"name":"maker","type":"Person"},{"name":"legs","type":"Leg[2]"},{"name":"tags","type":"string[]"},{"name":"data","type":"bytes"},{"name":"active","type":"bool"}],"Leg":[{"name":"amount","type":"int64"},{"name":"price","type":"uint128"},{"name":"flags","type":"uint8[]"}],"Person":[{"name":"name","type":"string"},{"name":"wallets","type":"address[]"}]},"primaryType":"Order","domain":{"name":"Exchange","chainId":"0x89","salt":"0x0101010101010101010101010101010101010101010101010101010101010101"},"message":{"maker":{"name":"Cow","wallets":["0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826","0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"]},"legs":[{"amount":-5,"price":"340282366920938463463374607431768211455","flags":[1,255]},{"amount":"-9223372036854775808","price":0,"flags":[]}],"tags":["a","b"],"data":"0xdeadbeef","active":true}}"#;
+    assert_eq!(TypedData { types: BTreeMap::new() }.encode_value("int8", &Value::from(-1)).unwrap(), [0xff; 32]);
+    let hashes = eip712_typed_data_hashes(data.as_bytes()).unwrap();
+    assert_eq!(hex::encode(&hashes[..32]), "d809c2c15e0bf73f1d07df117aee02f784bc374c6627a54c25ffa793dc8a3783");
+    assert_eq!(hex::encode(&hashes[32..]), "639215a35b5922df702115c22913f28f896bb760252054176621c58a49f5dfc2");
+    assert_eq!(digest(Some("eip712"), data.as_bytes()), "d5c778390a62f1f1efe61936307c88036de828e19c9ceadc40965a061d6dfd77");
+  }
+
+  #[test]
+  fn auto_prehashes_32_bytes_and_hashes_the_rest() {
+    assert_eq!(digest(None, &[7; 32]), digest(Some("prehashed"), &[7; 32]));
+    assert_eq!(digest(None, b"abc"), digest(Some("sha256"), b"abc"));
+    assert_eq!(HashMode::Auto.resolve(&[7; 31]), HashMode::Sha256);
+  }
+
+  #[test]
+  fn invalid_inputs_are_rejected() {
+    assert!(HashMode::parse(Some("md5")).is_err());
+    assert!(HashMode::Prehashed.digest(&[0; 31]).is_err());
+    assert!(HashMode::Eip712Hashed.digest(&[0; 32]).is_err());
+    assert!(HashMode::Eip712.digest(b"{}").unwrap_err().contains("no types"));
+    let typed = TypedData { types: BTreeMap::new() };
+    assert!(typed.encode_value("uint8", &Value::from(256)).is_err());
+    assert!(typed.encode_value("uint8", &Value::from(-1)).is_err());
+    assert!(typed.encode_value("int8", &Value::from(-128)).is_ok());
+    assert!(typed.encode_value("int8", &Value::from(-129)).is_err());
+    assert!(typed.encode_value("int8", &Value::from(128)).is_err());
+    assert!(typed.encode_value("uint7", &Value::from(1)).is_err());
+    assert!(typed.encode_value("address", &Value::from("0x00")).is_err());
+    assert!(typed.encode_value("bytes4", &Value::from("0x0102")).is_err());
+    assert!(typed.encode_value("bool[2]", &serde_json::json!([true])).is_err());
+    let missing = MAIL.replace(r#","contents":"Hello, Bob!""#, "");
+    assert!(HashMode::Eip712.digest(missing.as_bytes()).unwrap_err().contains("no field contents"));
+  }
+}
@@ -0,0 +1,256 @@
+//! 파티 식별 키로 Envelope 서명/검증 (Ed25519, RFC 8032)과 P2P payload 암호화
+//!
+//! 식별 키는 32바이트 시드로 Node.js `crypto`의 Ed25519 키(JWK `d`)와 호환됩니다. 검증은 `ed25519-dalek`의
+//! `verify_strict`로 비정규 s와 소위수(small-order) 공개키/R을 거부하며, 소위수 공개키는 명단에 넣을 수 없습니다.
+//!
+//! P2P 암호화는 서명 키를 재사용하지 않고, 시드에서 HKDF-SHA256으로 따로 유도한 X25519 키를 씁니다.
+//! 그래서 식별 공개키(명단 항목)는 Ed25519 공개키(32B) ‖ X25519 공개키(32B)입니다.
+//! 암호화는 수신자 X25519 공개키에 대한 ECIES입니다: 임시 키 E와 공유 비밀에서 HKDF-SHA256(info = 도메인 ‖ E ‖ 수신자 공개키)으로
+//! ChaCha20-Poly1305 키(32B)와 nonce(12B)를 유도하며, 암호문은 E(32B) ‖ AEAD 암호문입니다.
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use hkdf::Hkdf;
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use x25519_dalek::{EphemeralSecret, PublicKey as EncryptionKey, StaticSecret};
+use zeroize::{Zeroize, Zeroizing};
+
+pub const KEY_LEN: usize = 32;
+/// 식별 공개키 길이 (Ed25519 ‖ X25519)
+pub const PUBLIC_KEY_LEN: usize = 2 * KEY_LEN;
+const ENCRYPTION_KEY_DOMAIN: &[u8] = b"cggmp.v1.identity.x25519\0";
+const P2P_DOMAIN: &[u8] = b"cggmp.v1.p2p\0";
+
+/// 실행기의 식별 키와 파티 인덱스 순서의 식별 공개키 명단.
+/// 식별 키는 상태 저장(`serializeState()`)에 포함되지 않으며 (두 키 모두 해제 시 0으로 지움) 명단만 저장됨
+#[derive(Clone)]
+pub struct Identity {
+  key: SigningKey,
+  encryption_key: StaticSecret,
+  roster: Vec<(VerifyingKey, EncryptionKey)>,
+}
+
+impl Identity {
+  pub fn new<K: AsRef<[u8]>>(secret_key: &[u8], roster: &[K], party_index: u16) -> Result<Self, String> {
+    let seed = Zeroizing::new(<[u8; KEY_LEN]>::try_from(secret_key).map_err(|_| format!("identity key must be {KEY_LEN} bytes, got {}", secret_key.len()))?);
+    let (key, encryption_key) = (SigningKey::from_bytes(&seed), encryption_key(&seed));
+    let roster = roster.iter().enumerate().map(|(j, pk)| {
+      let pk: &[u8; PUBLIC_KEY_LEN] = pk.as_ref().try_into().map_err(|_| format!("identity public key of party {j} must be {PUBLIC_KEY_LEN} bytes, got {}", pk.as_ref().len()))?;
+      let (signing, encryption) = pk.split_at(KEY_LEN);
+      let signing = VerifyingKey::try_from(signing).ok().filter(|pk| !pk.is_weak()).ok_or_else(|| format!("invalid identity public key of party {j}"))?;
+      Ok((signing, EncryptionKey::from(<[u8; KEY_LEN]>::try_from(encryption).expect("32 bytes"))))
+    }).collect::<Result<Vec<_>, String>>()?;
+    if roster.get(usize::from(party_index)) != Some(&(key.verifying_key(), EncryptionKey::from(&encryption_key))) {
+      return Err(format!("identity key does not match the roster entry of party {party_index}"));
+    }
+    Ok(Self { key, encryption_key, roster })
+  }
+
+  /// 식별 공개키 명단 (파티 인덱스 순서)
+  pub fn roster(&self) -> Vec<Vec<u8>> {
+    self.roster.iter().map(|(signing, encryption)| [&signing.as_bytes()[..], encryption.as_bytes()].concat()).collect()
+  }
+
+  pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+    self.key.sign(msg).to_bytes().to_vec()
+  }
+
+  /// `recipient`의 X25519 공개키로 암호화
+  pub fn seal(&self, recipient: u16, aad: &[u8], plaintext: &[u8], rng: &mut (impl RngCore + CryptoRng)) -> Result<Vec<u8>, String> {
+    let (_, pk) = self.roster.get(usize::from(recipient)).ok_or_else(|| format!("no identity public key for party {recipient}"))?;
+    let e = EphemeralSecret::random_from_rng(rng);
+    let big_e = EncryptionKey::from(&e);
+    let shared = e.diffie_hellman(pk);
+    // 소위수 공개키면 공유 비밀이 고정값이 됨
+    if !shared.was_contributory() {
+      return Err(format!("invalid identity public key of party {recipient}"));
+    }
+    let (cipher, nonce) = p2p_cipher(big_e.as_bytes(), pk.as_bytes(), shared.as_bytes());
+    let ciphertext = cipher.encrypt(&nonce, Payload { msg: plaintext, aad }).map_err(|_| "encryption failed".to_string())?;
+    let mut out = big_e.as_bytes().to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+  }
+
+  /// 자신에게 암호화된 payload 복호화
+  pub fn open(&self, aad: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+    let (big_e, ciphertext) = data.split_first_chunk::<KEY_LEN>().ok_or("ciphertext too short")?;
+    let shared = self.encryption_key.diffie_hellman(&EncryptionKey::from(*big_e));
+    if !shared.was_contributory() {
+      return Err("invalid ephemeral key".to_string());
+    }
+    let (cipher, nonce) = p2p_cipher(big_e, EncryptionKey::from(&self.encryption_key).as_bytes(), shared.as_bytes());
+    cipher.decrypt(&nonce, Payload { msg: ciphertext, aad }).map_err(|_| "decryption failed".to_string())
+  }
+
+  /// `party`의 식별 공개키로 서명 검증. 명단에 없는 파티는 실패
+  pub fn verify(&self, party: u16, msg: &[u8], sig: &[u8]) -> bool {
+    self.roster.get(usize::from(party)).is_some_and(|(pk, _)| verify(pk, msg, sig))
+  }
+}
+
+/// 새 식별 키 (32바이트 시드)
+pub fn generate() -> [u8; KEY_LEN] {
+  let mut seed = [0u8; KEY_LEN];
+  rand::rngs::OsRng.fill_bytes(&mut seed);
+  seed
+}
+
+/// 시드의 식별 공개키 (Ed25519 공개키 ‖ X25519 공개키)
+pub fn public_key(seed: &[u8; KEY_LEN]) -> [u8; PUBLIC_KEY_LEN] {
+  let mut pk = [0u8; PUBLIC_KEY_LEN];
+  pk[..KEY_LEN].copy_from_slice(SigningKey::from_bytes(seed).verifying_key().as_bytes());
+  pk[KEY_LEN..].copy_from_slice(EncryptionKey::from(&encryption_key(seed)).as_bytes());
+  pk
+}
+
+/// 서명 키와 분리된 P2P 암호화 키 (시드에서 HKDF-SHA256으로 유도)
+fn encryption_key(seed: &[u8; KEY_LEN]) -> StaticSecret {
+  let mut okm = Zeroizing::new([0u8; KEY_LEN]);
+  Hkdf::<Sha256>::new(None, seed).expand(ENCRYPTION_KEY_DOMAIN, &mut *okm).expect("32-byte output");
+  StaticSecret::from(*okm)
+}
+
+/// RFC 8032 검증에 더해 비정규 s, 소위수 공개키와 R을 거부
+fn verify(pk: &VerifyingKey, msg: &[u8], sig: &[u8]) -> bool {
+  Signature::from_slice(sig).is_ok_and(|sig| pk.verify_strict(msg, &sig).is_ok())
+}
+
+fn p2p_cipher(big_e: &[u8], pk: &[u8], shared: &[u8]) -> (ChaCha20Poly1305, Nonce) {
+  let mut okm = [0u8; KEY_LEN + 12];
+  Hkdf::<Sha256>::new(None, shared).expand_multi_info(&[P2P_DOMAIN, big_e, pk], &mut okm).expect("44-byte output");
+  let cipher = ChaCha20Poly1305::new_from_slice(&okm[..KEY_LEN]).expect("32-byte key");
+  let mut nonce = [0u8; 12];
+  nonce.copy_from_slice(&okm[KEY_LEN..]);
+  okm.zeroize();
+  (cipher, Nonce::from(nonce))
+}
+
+#[cfg(test)]
+mod tests {
+  use rand::{rngs::StdRng, SeedableRng};
+
+  use super::*;
+
+  /// RFC 8032 7.1 TEST 1-3 (시드, 공개키, 메시지, 서명)
+  const VECTORS: [(&str, &str, &str, &str); 3] = [
+    ("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60", "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a", "",
+     "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"),
+    ("4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb", "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c", "72",
+     "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"),
+    ("c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7", "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025", "af82",
+     "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a"),
+  ];
+
+  /// 소위수 점 (항등원)
+  const IDENTITY_POINT: [u8; KEY_LEN] = {
+    let mut p = [0u8; KEY_LEN];
+    p[0] = 1;
+    p
+  };
+
+  fn bytes(s: &str) -> Vec<u8> {
+    hex::decode(s).unwrap()
+  }
+
+  fn identity(seed: &str, roster: &[Vec<u8>]) -> Identity {
+    Identity::new(&bytes(seed), roster, 0).unwrap()
+  }
+
+  fn roster_entry(seed: &str) -> Vec<u8> {
+    public_key(&bytes(seed).try_into().unwrap()).to_vec()
+  }
+
+  /// 테스트 벡터 시드를 파티 순서로 쓴 실행기별 식별 정보
+  fn parties() -> Vec<Identity> {
+    let roster: Vec<Vec<u8>> = VECTORS.iter().map(|v| roster_entry(v.0)).collect();
+    (0..).zip(VECTORS).map(|(i, v)| Identity::new(&bytes(v.0), &roster, i).unwrap()).collect()
+  }
+
+  #[test]
+  fn rfc8032_vectors() {
+    for (seed, pk, msg, sig) in VECTORS {
+      assert_eq!(roster_entry(seed)[..KEY_LEN], bytes(pk));
+      let id = identity(seed, &[roster_entry(seed)]);
+      assert_eq!(id.sign(&bytes(msg)), bytes(sig));
+      assert!(id.verify(0, &bytes(msg), &bytes(sig)));
+      assert!(!id.verify(0, &bytes("00"), &bytes(sig)));
+      assert!(!id.verify(1, &bytes(msg), &bytes(sig)));
+    }
+  }
+
+  #[test]
+  fn non_canonical_and_small_order_signatures_are_rejected() {
+    let (seed, _, msg, sig) = VECTORS[0];
+    let id = identity(seed, &[roster_entry(seed)]);
+
+    // s + L: 같은 스칼라의 비정규 인코딩
+    let mut s = bytes(sig)[KEY_LEN..].to_vec();
+    let order: [u8; KEY_LEN] = hex::decode("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010").unwrap().try_into().unwrap();
+    let mut carry = 0u16;
+    for (b, l) in s.iter_mut().zip(order) {
+      let sum = u16::from(*b) + u16::from(l) + carry;
+      *b = sum as u8;
+      carry = sum >> 8;
+    }
+    let mut malleated = bytes(sig)[..KEY_LEN].to_vec();
+    malleated.extend_from_slice(&s);
+    assert!(!id.verify(0, &bytes(msg), &malleated));
+
+    // 항등원 공개키에 대해 R = 항등원, s = 0은 검증식 s·B = R + k·A를 만족하지만 거부
+    let weak = VerifyingKey::from_bytes(&IDENTITY_POINT).unwrap();
+    let mut forged = IDENTITY_POINT.to_vec();
+    forged.extend_from_slice(&[0; KEY_LEN]);
+    assert!(!verify(&weak, b"any message", &forged));
+    assert!(!verify(&weak, b"any message", &forged[..63]));
+  }
+
+  #[test]
+  fn roster_checks() {
+    let seed = VECTORS[0].0;
+    let err = |key: &[u8], roster: &[Vec<u8>]| Identity::new(key, roster, 0).err().unwrap();
+    let weak = [&IDENTITY_POINT[..], &roster_entry(seed)[KEY_LEN..]].concat();
+    assert_eq!(err(&bytes(seed)[..31], &[roster_entry(seed)]), "identity key must be 32 bytes, got 31");
+    assert_eq!(err(&bytes(seed), &[roster_entry(seed), weak]), "invalid identity public key of party 1");
+    assert_eq!(err(&bytes(seed), &[roster_entry(seed), bytes(VECTORS[1].1)]), "identity public key of party 1 must be 64 bytes, got 32");
+    assert_eq!(err(&bytes(seed), &[roster_entry(VECTORS[1].0)]), "identity key does not match the roster entry of party 0");
+  }
+
+  #[test]
+  fn encryption_key_is_separate_from_the_signing_key() {
+    for (seed, pk, _, _) in VECTORS {
+      let entry = roster_entry(seed);
+      let signing = VerifyingKey::try_from(&bytes(pk)[..]).unwrap();
+      assert_ne!(entry[KEY_LEN..], signing.to_montgomery().to_bytes());
+      // 클램핑한 Ed25519 비밀 스칼라로 만든 X25519 공개키와도 다름
+      let scalar = SigningKey::from_bytes(&bytes(seed).try_into().unwrap()).to_scalar_bytes();
+      assert_ne!(entry[KEY_LEN..], EncryptionKey::from(&StaticSecret::from(scalar)).to_bytes());
+    }
+  }
+
+  #[test]
+  fn sealed_payload_opens_only_for_its_recipient() {
+    let parties = parties();
+    let mut rng = StdRng::seed_from_u64(1);
+    let sealed = parties[0].seal(1, b"header", b"p2p payload", &mut rng).unwrap();
+    assert_eq!(sealed.len(), KEY_LEN + b"p2p payload".len() + 16);
+    assert_eq!(parties[1].open(b"header", &sealed).unwrap(), b"p2p payload");
+
+    // 다른 수신자, 다른 헤더, 변조한 암호문은 복호화 실패
+    assert_eq!(parties[2].open(b"header", &sealed).unwrap_err(), "decryption failed");
+    assert_eq!(parties[0].open(b"header", &sealed).unwrap_err(), "decryption failed");
+    assert_eq!(parties[1].open(b"other header", &sealed).unwrap_err(), "decryption failed");
+    let mut tampered = sealed.clone();
+    tampered[KEY_LEN] ^= 1;
+    assert_eq!(parties[1].open(b"header", &tampered).unwrap_err(), "decryption failed");
+
+    // 소위수 임시 키와 짧은 암호문은 거부
+    let mut low_order = sealed;
+    low_order[..KEY_LEN].fill(0);
+    assert_eq!(parties[1].open(b"header", &low_order).unwrap_err(), "invalid ephemeral key");
+    assert_eq!(parties[1].open(b"header", &[0; 31]).unwrap_err(), "ciphertext too short");
+    assert_eq!(parties[0].seal(3, b"header", b"p2p payload", &mut rng).unwrap_err(), "no identity public key for party 3");
+  }
+}
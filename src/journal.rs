@@ -0,0 +1,84 @@
+//! 실행기 상태 저장/복원
+//!
+//! 상태 머신은 직렬화할 수 없으므로, 프로토콜을 시작할 때마다 체크포인트(키쉐어, aux info, 상태 필드, RNG 시드)를 남기고
+//! 그 이후의 호출과 `step()` 입력을 순서대로 기록합니다. 복원 시에는 체크포인트를 불러온 뒤 같은 시드로 기록을
+//! 재실행하므로, 상태 머신의 진행 상황과 아직 처리되지 않은 메시지가 그대로 재현됩니다.
+
+use serde::{Deserialize, Serialize};
+
+use crate::SigningOptions;
+
+/// 저장 형식 버전. 아직 배포되지 않은 형식이므로, 배포 후 `JournalEntry` 순서나 체크포인트가 바뀌면 올립니다.
+pub const STATE_VERSION: u32 = 1;
+
+/// 체크포인트 이후 상태를 바꾼 호출
+#[derive(Clone, Serialize, Deserialize)]
+pub enum JournalEntry {
+  ImportKeyshare(Vec<u8>),
+  ImportAuxInfo(Vec<u8>),
+  ImportPresignature(Vec<u8>),
+  SetSigners(String),
+  StartKeygen,
+  StartAuxGen,
+  StartAuxGenWithPrimes(Vec<u8>),
+  StartKeyRefresh,
+  StartKeyRefreshWithPrimes(Vec<u8>),
+  StartSigning(String),
+  StartPresigning,
+  SignWithPresignature(String),
+  /// `step()` 입력 (거부된 메시지 포함)
+  Step(Vec<Vec<u8>>),
+  /// 옵션을 지정한 `startSigning()` / `signWithPresignature()`
+  StartSigningWithOptions(String, SigningOptions),
+  SignWithPresignatureWithOptions(String, SigningOptions),
+  StartReshare(ReshareRequest),
+  /// `setIdentity()` 식별 공개키 명단 (식별 키는 저장하지 않고 `restore()`에서 다시 받음)
+  SetIdentity(Vec<Vec<u8>>),
+  SetReliableBroadcast(bool),
+  SetExecutionId(Vec<u8>),
+  ExportPresignature,
+  DiscardKeyshare,
+  CommitKeyRefresh,
+}
+
+/// `startReshare()` 옵션 (Buffer는 직렬화할 수 없어 바이트로 보관)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReshareRequest {
+  pub old_indices: Vec<Option<u16>>,
+  pub new_indices: Vec<Option<u16>>,
+  pub key_share: Option<Vec<u8>>,
+  pub key_info: Option<Vec<u8>>,
+  pub primes: Option<Vec<u8>>,
+}
+
+/// `serializeState()` 결과
+#[derive(Serialize, Deserialize)]
+pub struct SavedState {
+  pub version: u32,
+  pub curve: String,
+  pub session_id: String,
+  pub execution_id: String,
+  pub party_index: u16,
+  pub threshold: u16,
+  pub parties_count: u16,
+  /// 곡선별 체크포인트 (JSON)
+  pub checkpoint: Vec<u8>,
+  pub journal: Vec<JournalEntry>,
+  /// 체크포인트 이후 보낸 Envelope (재실행 결과 검증용)
+  pub sent: Vec<Vec<u8>>,
+}
+
+impl SavedState {
+  pub fn encode(&self) -> Result<Vec<u8>, String> {
+    bincode::serialize(self).map_err(|e| format!("serialize state: {e}"))
+  }
+
+  pub fn decode(data: &[u8]) -> Result<Self, String> {
+    // 버전이 다르면 나머지 구조도 다를 수 있으므로 먼저 확인
+    let version = data.get(..4).map(|v| u32::from_le_bytes([v[0], v[1], v[2], v[3]])).ok_or("invalid state: too short")?;
+    if version != STATE_VERSION {
+      return Err(format!("unsupported state version: {version}"));
+    }
+    bincode::deserialize(data).map_err(|e| format!("invalid state: {e}"))
+  }
+}
@@ -3,19 +3,24 @@
 #[macro_use]
 extern crate napi_derive;
 
-use napi::{Error, Result, Status};
+use napi::bindgen_prelude::{AsyncTask, Either};
+use napi::{Env, Error, Result, Status, Task};
 use prost::Message as _;
-use rand::{rngs::StdRng, CryptoRng, RngCore, SeedableRng};
-use round_based::{Incoming, MessageDestination, MessageType};
+use rand::{rngs::{OsRng, StdRng}, CryptoRng, Rng, RngCore, SeedableRng};
+use round_based::{Incoming, MessageDestination, MessageType, Outgoing};
 use round_based::state_machine::{ProceedResult, StateMachine};
 use serde::{Deserialize, Serialize};
 use base64::Engine as _;
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
-use cggmp24::key_share::{AnyKeyShare, AuxInfo, KeyShare};
-use cggmp24::supported_curves::Secp256k1;
-use cggmp24::signing::{SigningError, PrehashedDataToSign, AnyDataToSign};
-use cggmp24::{ExecutionId, Signature};
+use cggmp24::key_share::{AnyKeyShare, AuxInfo, KeyShare, Validate};
+use cggmp24::supported_curves::{Secp256k1, Secp256r1, Stark};
+use cggmp24::signing::{SigningError, PrehashedDataToSign, AnyDataToSign, PresignaturePublicData, PresignatureCommitment};
+use cggmp24::{DataToSign, ExecutionId, PartialSignature, Presignature, Signature};
+use generic_ec::coords::{AlwaysHasAffineX, HasAffineX};
+use generic_ec::{Curve, NonZero, Point};
 use cggmp24_keygen::key_share::CoreKeyShare;
 use cggmp24_keygen::msg::threshold as keygen_msg;
 use cggmp24_keygen::KeygenBuilder;
@@ -23,25 +28,56 @@ use cggmp24_keygen::KeygenBuilder;
 mod proto {
   include!(concat!(env!("OUT_DIR"), "/cggmp.v1.rs"));
 }
+mod binary;
+mod dealer;
+mod hashing;
+mod identity;
+mod journal;
+#[cfg(feature = "key-export")]
+mod recovery;
+mod reshare;
+mod sealed;
+mod signature;
+mod sim;
+#[cfg(test)]
+mod test_util;
+
+use hashing::HashMode;
+use identity::Identity;
+use journal::{JournalEntry, ReshareRequest, SavedState};
+use sealed::{Content, Secret};
+use signature::SignatureFormat;
+use zeroize::Zeroizing;
 
 use proto::{envelope::Payload, Envelope, Round};
 use sha2::{Digest as DigestTrait, Sha256};
+use sha3::Keccak256;
 
 const PAYLOAD_FORMAT_BINCODE: &str = "bincode";
 
-type Curve = Secp256k1;
 type AlgoDigest = Sha256;
 type SecLevel = cggmp24::security_level::SecurityLevel128;
 
-type KeygenMsg = keygen_msg::Msg<Curve, SecLevel, AlgoDigest>;
-type SigningMsg = cggmp24::signing::msg::Msg<Secp256k1, sha2::Sha256>;
+type KeygenMsg<E> = keygen_msg::Msg<E, SecLevel, AlgoDigest>;
+type SigningMsg<E> = cggmp24::signing::msg::Msg<E, AlgoDigest>;
 type AuxGenMsg = cggmp24::key_refresh::msg::Msg<AlgoDigest, SecLevel>;
 type AuxInfoMsg = AuxInfo<SecLevel>;
-type KeyShareWithLevel = KeyShare<Curve, SecLevel>;
+type KeyShareWithLevel<E> = KeyShare<E, SecLevel>;
+type PresignOutput<E> = (Presignature<E>, PresignaturePublicData<E>);
+type ReshareOutput<E> = std::result::Result<Option<CoreKeyShare<E>>, reshare::Error>;
+type AuxGenOutput = std::result::Result<AuxInfoMsg, cggmp24::KeyRefreshError>;
+
+/// 키 갱신은 쉐어 재분배와 aux 재생성을 동시에 진행하므로 두 메시지를 하나로 감싸서 전송
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+enum KeyRefreshMsg<E: Curve> {
+  Reshare(reshare::Msg<E>),
+  Aux(Box<AuxGenMsg>),
+}
 
 struct UnsafeRng(StdRng);
 impl UnsafeRng {
-  fn new() -> Self { Self(StdRng::from_entropy()) }
+  fn from_seed(seed: [u8; 32]) -> Self { Self(StdRng::from_seed(seed)) }
 }
 impl RngCore for UnsafeRng {
   fn next_u32(&mut self) -> u32 { self.0.next_u32() }
@@ -51,25 +87,144 @@ impl RngCore for UnsafeRng {
 }
 impl CryptoRng for UnsafeRng {}
 
-enum ProtocolState {
+enum ProtocolState<E: Curve> {
   None,
   Keygen {
-    sm: Box<dyn StateMachine<Output = std::result::Result<CoreKeyShare<Secp256k1>, cggmp24::KeygenError>, Msg = KeygenMsg> + 'static>,
-    pending: Vec<Incoming<KeygenMsg>>,
+    sm: Box<dyn StateMachine<Output = std::result::Result<CoreKeyShare<E>, cggmp24::KeygenError>, Msg = KeygenMsg<E>> + 'static>,
+    pending: Vec<Incoming<KeygenMsg<E>>>,
+    _eid: Box<[u8; 32]>,
   },
   AuxGen {
     sm: Box<dyn StateMachine<Output = std::result::Result<AuxInfoMsg, cggmp24::KeyRefreshError>, Msg = AuxGenMsg> + 'static>,
     pending: Vec<Incoming<AuxGenMsg>>,
+    _eid: Box<[u8; 32]>,
   },
   Signing {
-    sm: Box<dyn StateMachine<Output = std::result::Result<Signature<Secp256k1>, SigningError>, Msg = SigningMsg> + 'static>,
-    pending: Vec<Incoming<SigningMsg>>,
+    sm: Box<dyn StateMachine<Output = std::result::Result<Signature<E>, SigningError>, Msg = SigningMsg<E>> + 'static>,
+    pending: Vec<Incoming<SigningMsg<E>>>,
     tx_context: Vec<u8>,
-    _keyshare: Box<KeyShareWithLevel>,
+    derivation_path: Vec<u32>,
+    hash_mode: HashMode,
+    /// 서명을 검증할 공개키 (파생 경로가 있으면 자식 공개키)
+    public_key: Point<E>,
+    _data: Box<dyn AnyDataToSign<E>>,
+    _keyshare: Box<KeyShareWithLevel<E>>,
+    _signers: Vec<u16>,
+    _eid: Box<[u8; 32]>,
+  },
+  Presigning {
+    sm: Box<dyn StateMachine<Output = std::result::Result<PresignOutput<E>, SigningError>, Msg = SigningMsg<E>> + 'static>,
+    pending: Vec<Incoming<SigningMsg<E>>>,
+    _keyshare: Box<KeyShareWithLevel<E>>,
     _signers: Vec<u16>,
+    _eid: Box<[u8; 32]>,
+    /// ExecutionId를 유도한 실행 ID (`setExecutionId()`로 지정했으면 `None`)
+    derived_from: Option<String>,
+  },
+  KeyRefresh {
+    reshare_sm: Box<dyn StateMachine<Output = ReshareOutput<E>, Msg = KeyRefreshMsg<E>> + 'static>,
+    aux_sm: Box<dyn StateMachine<Output = std::result::Result<AuxInfoMsg, cggmp24::KeyRefreshError>, Msg = KeyRefreshMsg<E>> + 'static>,
+    reshare_pending: Vec<Incoming<KeyRefreshMsg<E>>>,
+    aux_pending: Vec<Incoming<KeyRefreshMsg<E>>>,
+    new_core: Option<CoreKeyShare<E>>,
+    new_aux: Option<Box<AuxInfoMsg>>,
+    /// 재분배, aux 생성의 ExecutionId
+    _eids: Box<[[u8; 32]; 2]>,
+    /// 재분배, aux 생성 상태 머신이 각각 빌려 쓰는 RNG
+    _rngs: Box<[StdRng; 2]>,
+  },
+  /// 새 위원회로 재공유: 기존/새 위원회 전체가 재분배에, 새 위원회만 aux 생성에 참여
+  Reshare {
+    reshare_sm: Box<dyn StateMachine<Output = ReshareOutput<E>, Msg = KeyRefreshMsg<E>> + 'static>,
+    aux_sm: Option<Box<dyn StateMachine<Output = AuxGenOutput, Msg = KeyRefreshMsg<E>> + 'static>>,
+    reshare_pending: Vec<Incoming<KeyRefreshMsg<E>>>,
+    aux_pending: Vec<Incoming<KeyRefreshMsg<E>>>,
+    /// 새 인덱스 순서의 새 위원회 파티 (실행 인덱스)
+    committee: Vec<u16>,
+    /// 재분배 결과 (새 위원회가 아니면 `Some(None)`)
+    new_core: Option<Option<CoreKeyShare<E>>>,
+    new_aux: Option<Box<AuxInfoMsg>>,
+    _eids: Box<[[u8; 32]; 2]>,
+    /// 재분배, aux 생성 상태 머신이 각각 빌려 쓰는 RNG
+    _rngs: Box<[StdRng; 2]>,
+  },
+  // 프리서명 기반 1라운드 서명: 각 서명자의 부분 서명을 모아 결합
+  PartialSigning {
+    partials: Vec<Option<PartialSignature<E>>>,
+    public_data: Box<PresignaturePublicData<E>>,
+    data: DataToSign<E>,
+    signers: Vec<u16>,
+    tx_context: Vec<u8>,
+    hash_mode: HashMode,
   },
 }
 
+impl<E: Curve> ProtocolState<E> {
+  /// 중단 보고와 상태 문자열에 쓰는 프로토콜 이름
+  fn name(&self) -> &'static str {
+    match self {
+      ProtocolState::None => "idle",
+      ProtocolState::Keygen { .. } => "keygen",
+      ProtocolState::AuxGen { .. } => "aux_gen",
+      ProtocolState::Signing { .. } | ProtocolState::PartialSigning { .. } => "signing",
+      ProtocolState::Presigning { .. } => "presigning",
+      ProtocolState::KeyRefresh { .. } => "key_refresh",
+      ProtocolState::Reshare { .. } => "reshare",
+    }
+  }
+}
+
+/// 마지막으로 완료된 서명과 검증에 필요한 공개키, 메시지 스칼라
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+struct SignedMessage<E: Curve> {
+  signature: Signature<E>,
+  public_key: Point<E>,
+  message: generic_ec::Scalar<E>,
+}
+
+/// 저장/이동 가능한 프리서명 (PresignaturePublicData는 serde 미지원이라 직접 보관).
+/// 만든 키와 실행에 묶여 있어 다른 키쉐어나 세션으로는 가져올 수 없음
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+struct StoredPresignature<E: Curve> {
+  signers: Vec<u16>,
+  presignature: Presignature<E>,
+  gamma: NonZero<Point<E>>,
+  commitments: Vec<(Point<E>, Point<E>)>,
+  /// 프리서명을 만든 키쉐어의 공개키
+  public_key: Point<E>,
+  /// 프리서명을 만든 실행기의 세션 ID
+  session_id: String,
+  /// 프리서명 생성에 쓴 ExecutionId
+  execution_id: [u8; 32],
+  /// ExecutionId를 유도한 실행 ID (`setExecutionId()`로 지정했으면 `None`)
+  derived_from: Option<String>,
+}
+
+impl<E: Curve> StoredPresignature<E> {
+  fn new(signers: Vec<u16>, presignature: Presignature<E>, public_data: PresignaturePublicData<E>, public_key: Point<E>, session_id: String, execution_id: [u8; 32], derived_from: Option<String>) -> Self {
+    let commitments = public_data.commitments.iter().map(|c| (c.tilde_Delta, c.tilde_S)).collect();
+    Self { signers, presignature, gamma: public_data.Gamma, commitments, public_key, session_id, execution_id, derived_from }
+  }
+
+  /// 사용 기록에 남기는 식별자: ExecutionId와 Gamma(실행마다 새로 뽑는 난수로 결정)의 해시
+  fn id(&self) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(PRESIGNATURE_ID_DOMAIN);
+    hash_field(&mut h, &self.execution_id);
+    hash_field(&mut h, &self.gamma.to_bytes(true));
+    h.finalize().into()
+  }
+
+  fn public_data(&self) -> PresignaturePublicData<E> {
+    PresignaturePublicData {
+      Gamma: self.gamma,
+      commitments: self.commitments.iter().map(|&(delta, s)| PresignatureCommitment { tilde_Delta: delta, tilde_S: s }).collect(),
+    }
+  }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ExecutorSnapshot {
@@ -82,7 +237,7 @@ struct ExecutorSnapshot {
   round: u32,
   processed: usize,
   status: String,
-  errors: Vec<String>,
+  errors: Vec<MessageRejection>,
   last_round: Option<String>,
   internal_round: String, // Added detailed internal round info
   curve: String,
@@ -91,397 +246,3070 @@ struct ExecutorSnapshot {
   public_key: Option<String>,
   key_share_threshold: Option<u16>,
   signature: Option<String>,
+  has_presignature: bool,
+  /// `commitKeyRefresh()`를 기다리는 새 쉐어가 있는지 여부
+  has_pending_keyshare: bool,
+  abort: Option<ProtocolAbort>,
+  /// `setReliableBroadcast()` 설정 (없으면 프로토콜별 기본값)
+  reliable_broadcast: Option<bool>,
 }
 
-#[napi]
-pub fn generate_primes() -> Result<napi::bindgen_prelude::Buffer> {
-  let mut rng = StdRng::from_entropy();
-  let primes: cggmp24::PregeneratedPrimes<SecLevel> = cggmp24::PregeneratedPrimes::generate(&mut rng);
-  let buf = bincode::serialize(&primes).map_err(|e| Error::new(Status::GenericFailure, format!("serialize: {e}")))?;
-  Ok(napi::bindgen_prelude::Buffer::from(buf))
+/// `step()` 입력 중 처리되지 않은 메시지
+#[napi(object)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageRejection {
+  /// 메시지를 전달한 `step()` 호출의 입력 배열 인덱스
+  pub index: u32,
+  /// Envelope의 `from_party` (Envelope 디코딩 실패 시 없음)
+  pub sender: Option<u32>,
+  /// 거부 사유 코드 (`RejectReason`)
+  pub reason: String,
+  pub message: String,
 }
 
-#[napi]
-pub struct CggmpExecutor {
-  session_id: String,
-  execution_id: String,
-  party_index: u16,
-  threshold: u16,
-  parties_count: u16,
-  signers_at_keygen: Option<Vec<u16>>,
-  rng: Box<UnsafeRng>,
-  state: ProtocolState,
-  core_keyshare: Option<CoreKeyShare<Secp256k1>>,
-  aux_info: Option<AuxInfoMsg>,
-  keyshare: Option<KeyShareWithLevel>,
-  processed: usize,
-  phase: String,
-  round: u32,
-  errors: Vec<String>,
-  last_round: Option<Round>,
-  internal_round: String, // Added detailed internal round info
-  status: String,
-  last_signature: Option<String>,
-  meta_sent: bool,
+#[napi(object)]
+pub struct StepReport {
+  pub outgoing: Vec<napi::bindgen_prelude::Buffer>,
+  pub rejected: Vec<MessageRejection>,
+  /// 이번 호출에서 프로토콜이 중단된 경우 그 정보
+  pub abort: Option<ProtocolAbort>,
 }
 
-#[napi]
-impl CggmpExecutor {
-  #[napi(constructor)]
-  pub fn new(session_id: String, execution_id: String, party_index: u16, threshold: u16, parties_count: u16) -> Result<Self> {
-    if session_id.is_empty() || execution_id.is_empty() {
-      return Err(Error::new(Status::InvalidArg, "session_id and execution_id are required"));
+/// `startSigning()` / `signWithPresignature()` 옵션
+#[napi(object)]
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SigningOptions {
+  /// "prehashed", "sha256", "sha256d", "keccak256", "eip191", "eip712", "eip712-hashed" (미지정 시 32바이트는 prehash, 그 외는 SHA-256)
+  pub hash_mode: Option<String>,
+  /// HD 파생 경로 ("m/0/5" 형식, 비강화 인덱스만). 프리서명 서명에서는 지원하지 않음
+  pub derivation_path: Option<String>,
+}
+
+/// `startReshare()` 옵션. 인덱스 배열은 이번 실행의 파티 인덱스 순서입니다.
+#[napi(object)]
+pub struct ReshareOptions {
+  /// 각 파티의 기존 키쉐어 인덱스 (기존 위원회가 아니면 null)
+  pub old_indices: Vec<Option<u16>>,
+  /// 각 파티의 새 키쉐어 인덱스 (새 위원회가 아니면 null). 새 위원회는 0부터 빈틈없이 번호를 매깁니다
+  pub new_indices: Vec<Option<u16>>,
+  /// 기존 위원회 파티의 키쉐어 (`exportKeyshare()` 결과)
+  pub key_share: Option<napi::bindgen_prelude::Buffer>,
+  /// 키쉐어가 없는 파티가 사용할 기존 키의 공개 정보 (`exportKeyInfo()` 결과)
+  pub key_info: Option<napi::bindgen_prelude::Buffer>,
+  /// 새 위원회 파티의 aux info 생성에 사용할 소수 (`generatePrimes()` 결과, 미지정 시 생성)
+  pub primes: Option<napi::bindgen_prelude::Buffer>,
+}
+
+impl From<ReshareOptions> for ReshareRequest {
+  fn from(options: ReshareOptions) -> Self {
+    Self {
+      old_indices: options.old_indices, new_indices: options.new_indices,
+      key_share: options.key_share.map(|b| b.to_vec()), key_info: options.key_info.map(|b| b.to_vec()), primes: options.primes.map(|b| b.to_vec()),
     }
-    Ok(Self {
-      session_id, execution_id, party_index, threshold, parties_count,
-      signers_at_keygen: None, rng: Box::new(UnsafeRng::new()), state: ProtocolState::None,
-      core_keyshare: None, aux_info: None, keyshare: None, processed: 0,
-      phase: "INIT".to_string(), round: 0, errors: Vec::new(), last_round: None,
-      internal_round: "Init".to_string(),
-      status: "init".to_string(), last_signature: None,
-      meta_sent: false,
-    })
   }
+}
 
-  #[napi]
-  pub fn export_keyshare(&self) -> Result<napi::bindgen_prelude::Buffer> {
-    let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare not ready"))?;
-    let buf = serde_json::to_vec(ks).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
-    Ok(napi::bindgen_prelude::Buffer::from(buf))
+/// 프로토콜 중단 정보 (식별 가능한 중단)
+#[napi(object)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolAbort {
+  /// "keygen", "aux_gen", "signing", "presigning", "key_refresh", "reshare"
+  pub protocol: String,
+  /// `AbortKind` 코드
+  pub kind: String,
+  /// cggmp24 중단 사유 (예: "EncProofOfK", "InvalidDecommitment")
+  pub reason: String,
+  /// 책임 파티 (서명 시에도 전역 인덱스)
+  pub blamed_parties: Vec<u32>,
+  /// 다른 파티의 Error Envelope로 중단된 경우 그 파티
+  pub reported_by: Option<u32>,
+  pub message: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AbortKind {
+  /// 악의적 파티가 검증에 실패 (책임 파티 식별 가능)
+  Aborted,
+  /// 다른 파티가 중단을 알림
+  PeerAborted,
+  Io,
+  Internal,
+}
+
+impl AbortKind {
+  fn code(self) -> &'static str {
+    match self {
+      AbortKind::Aborted => "ABORTED",
+      AbortKind::PeerAborted => "PEER_ABORTED",
+      AbortKind::Io => "IO",
+      AbortKind::Internal => "INTERNAL",
+    }
   }
+}
 
-  #[napi]
-  pub fn export_aux_info(&self) -> Result<napi::bindgen_prelude::Buffer> {
-    let aux = self.aux_info.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "aux info not ready"))?;
-    let buf = serde_json::to_vec(aux).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
-    Ok(napi::bindgen_prelude::Buffer::from(buf))
+impl ProtocolAbort {
+  fn new(protocol: &str, kind: AbortKind, reason: &str, blamed_parties: Vec<u16>, message: String) -> Self {
+    Self {
+      protocol: protocol.to_string(), kind: kind.code().to_string(), reason: reason.to_string(),
+      blamed_parties: blamed_parties.into_iter().map(u32::from).collect(), reported_by: None, message,
+    }
   }
 
-  #[napi]
-  pub fn import_keyshare(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> {
-    let ks: KeyShareWithLevel = if !data.is_empty() && data[0] == b'{' {
-      serde_json::from_slice(&data).map_err(|e| Error::new(Status::InvalidArg, format!("parse json: {e}")))?
-    } else {
-      match serde_json::from_slice(&data) {
-        Ok(k) => k,
-        Err(_) => {
-          let decoded = base64::engine::general_purpose::STANDARD.decode(&data)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("not json and not base64: {e}")))?;
-          serde_json::from_slice(&decoded)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("parse legacy base64-json: {e}")))?
-        }
-      }
-    };
-    self.keyshare = Some(ks);
-    self.status = "keyshare_ready".to_string();
-    Ok(())
+  /// cggmp24 오류에서 중단 정보 추출 (`parse_abort_debug`).
+  /// 서명/프리서명의 책임 파티는 서명자 내 위치이므로 `signers`로 전역 인덱스로 변환
+  fn from_debug(protocol: &str, debug: String, signers: &[u16]) -> Self {
+    let (kind, reason, blamed) = parse_abort_debug(&debug);
+    let mut blamed: Vec<u16> = blamed.into_iter()
+      .map(|i| if signers.is_empty() { i } else { signers.get(usize::from(i)).copied().unwrap_or(i) })
+      .collect();
+    blamed.sort_unstable(); blamed.dedup();
+    Self::new(protocol, kind, reason, blamed, debug.clone())
   }
 
-  #[napi]
-  pub fn import_aux_info(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> {
-    let aux: AuxInfoMsg = if !data.is_empty() && data[0] == b'{' {
-      serde_json::from_slice(&data).map_err(|e| Error::new(Status::InvalidArg, format!("parse json: {e}")))?
-    } else {
-      match serde_json::from_slice(&data) {
-        Ok(a) => a,
-        Err(_) => {
-          let decoded = base64::engine::general_purpose::STANDARD.decode(&data)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("not json and not base64: {e}")))?;
-          serde_json::from_slice(&decoded)
-            .map_err(|e| Error::new(Status::InvalidArg, format!("parse legacy base64-json: {e}")))?
-        }
-      }
+  fn from_reshare(protocol: &str, err: &reshare::Error) -> Self {
+    let (kind, reason, blamed) = match err {
+      reshare::Error::InvalidCommitment(p) => (AbortKind::Aborted, "InvalidCommitment", p.clone()),
+      reshare::Error::InvalidShare(p) => (AbortKind::Aborted, "InvalidShare", p.clone()),
+      reshare::Error::FalseComplaint(p) => (AbortKind::Aborted, "FalseComplaint", p.clone()),
+      reshare::Error::NotReliable(p) => (AbortKind::Aborted, "NotReliable", p.clone()),
+      // 확인 해시가 내 기록과 다르다는 것만으로는 누가 잘못했는지 알 수 없으므로 메시지에만 남김
+      reshare::Error::NotConfirmed(_) => (AbortKind::Aborted, "NotConfirmed", Vec::new()),
+      reshare::Error::Io(_) => (AbortKind::Io, "IoError", Vec::new()),
+      reshare::Error::InvalidSetup(_) => (AbortKind::Internal, "InvalidSetup", Vec::new()),
+      reshare::Error::InvalidKeyShare(_) => (AbortKind::Internal, "InvalidKeyShare", Vec::new()),
     };
-    self.aux_info = Some(aux);
-    self.try_combine_shares();
-    Ok(())
+    Self::new(protocol, kind, reason, blamed, err.to_string())
   }
 
-  #[napi]
-  pub fn start_keygen(&mut self) -> Result<()> {
-    let eid = ExecutionId::new(derive_execution_seed(&self.session_id, &self.execution_id, "keygen"));
-    let builder = KeygenBuilder::<Secp256k1>::new(eid, self.party_index, self.parties_count).set_threshold(self.threshold).enforce_reliable_broadcast(false);
-    self.state = ProtocolState::Keygen { sm: Box::new(builder.into_state_machine(extend_mut(&mut self.rng))), pending: Vec::new() };
-    self.phase = "KEYGEN".to_string(); self.status = "running".to_string(); self.round = Round::Keygen as u32; self.last_round = Some(Round::Keygen);
-    self.internal_round = "Round 1 (Commitment)".to_string(); // Initial round
-    Ok(())
+  fn from_peer(protocol: &str, from_party: u16, err: proto::Error) -> Self {
+    Self {
+      protocol: protocol.to_string(), kind: AbortKind::PeerAborted.code().to_string(), reason: err.reason,
+      blamed_parties: err.blamed_parties, reported_by: Some(u32::from(from_party)), message: err.message,
+    }
   }
 
-  #[napi]
-  pub fn start_aux_gen(&mut self) -> Result<()> {
-    let eid = ExecutionId::new(derive_execution_seed(&self.session_id, &self.execution_id, "aux_gen"));
-    let rng = extend_mut(&mut self.rng);
-    let primes: cggmp24::PregeneratedPrimes<SecLevel> = cggmp24::PregeneratedPrimes::generate(rng);
-    let builder = cggmp24::aux_info_gen(eid, self.party_index, self.parties_count, primes).enforce_reliable_broadcast(false);
-    self.state = ProtocolState::AuxGen { sm: Box::new(builder.into_state_machine(rng)), pending: Vec::new() };
-    self.phase = "AUX_GEN".to_string(); self.status = "running".to_string(); self.round = Round::AuxInfo as u32; self.last_round = Some(Round::AuxInfo);
-    self.internal_round = "Round 1 (Paillier Gen)".to_string(); // Initial round
-    Ok(())
+  fn to_proto(&self) -> proto::Error {
+    let (code, retriable) = match self.kind.as_str() {
+      "ABORTED" => (1, false),
+      "IO" => (2, true),
+      _ => (3, false),
+    };
+    proto::Error { code, message: self.message.clone(), retriable, reason: self.reason.clone(), blamed_parties: self.blamed_parties.clone() }
   }
+}
 
-  #[napi]
-  pub fn start_aux_gen_with_primes(&mut self, primes_buf: napi::bindgen_prelude::Buffer) -> Result<()> {
-    let eid = ExecutionId::new(derive_execution_seed(&self.session_id, &self.execution_id, "aux_gen"));
-    let primes: cggmp24::PregeneratedPrimes<SecLevel> = bincode::deserialize(&primes_buf).map_err(|e| Error::new(Status::InvalidArg, format!("invalid primes: {e}")))?;
-    let rng = extend_mut(&mut self.rng);
-    let builder = cggmp24::aux_info_gen(eid, self.party_index, self.parties_count, primes).enforce_reliable_broadcast(false);
-    self.state = ProtocolState::AuxGen { sm: Box::new(builder.into_state_machine(rng)), pending: Vec::new() };
-    self.phase = "AUX_GEN".to_string(); self.status = "running".to_string(); self.round = Round::AuxInfo as u32; self.last_round = Some(Round::AuxInfo);
-    self.internal_round = "Round 1 (Paillier Gen)".to_string(); // Initial round
-    Ok(())
+/// cggmp24 오류의 Debug 출력에서 중단 종류, 사유, 책임 파티(서명은 서명자 내 위치)를 추출.
+/// cggmp24는 사유(`Reason`)를 비공개 타입으로만 담고 있어 `AbortBlame { faulty_party: i, .. }`,
+/// `{ parties: [i, j] }`, `Round1NotReliable([(i, msg_id), ..])` 같은 Debug 출력 형식에 의존합니다.
+/// 의존성 버전이 이 형식을 확인한 버전과 다르면 `abort_parsing_matches_the_pinned_cggmp24` 테스트가 실패하므로,
+/// 버전을 올릴 때는 새 출력으로 중단 테스트를 확인한 뒤 이 함수와 테스트의 버전을 함께 고칩니다.
+fn parse_abort_debug(debug: &str) -> (AbortKind, &str, Vec<u16>) {
+  fn leading_ident(s: &str) -> &str {
+    let end = s.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(s.len());
+    if end == 0 { "Unknown" } else { &s[..end] }
   }
-
-  #[napi]
-  pub fn set_signers(&mut self, json: String) -> Result<()> {
-    let parsed: Vec<u16> = serde_json::from_str(&json).map_err(|e| Error::new(Status::InvalidArg, format!("invalid json: {e}")))?;
-    self.signers_at_keygen = Some(parsed);
-    Ok(())
+  fn leading_number(s: &str) -> Option<u16> {
+    s.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()
   }
 
-  #[napi]
-  pub fn start_signing(&mut self, tx_hex: String) -> Result<()> {
-    let ks = self.keyshare.clone().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare missing"))?;
-    let tx = hex::decode(tx_hex).map_err(|e| Error::new(Status::InvalidArg, format!("invalid hex: {e}")))?;
-    // 32바이트인 경우 이미 해시된 데이터로 처리, 아니면 SHA256으로 해싱
-    let data: Box<dyn AnyDataToSign<Secp256k1>> = if tx.len() == 32 {
-      Box::new(PrehashedDataToSign::from_scalar(generic_ec::Scalar::<Secp256k1>::from_be_bytes_mod_order(&tx)))
-    } else {
-      Box::new(cggmp24::DataToSign::<Secp256k1>::digest::<sha2::Sha256>(&tx))
-    };
-    let min = ks.min_signers();
-    let selected = self.signers_at_keygen.clone().unwrap_or_else(|| (0..min).collect());
-    let eid = ExecutionId::new(derive_execution_seed(&self.session_id, &self.execution_id, "signing"));
-    let ks_boxed = Box::new(ks);
-    let my_idx = selected.iter().position(|&p| p == self.party_index).ok_or_else(|| Error::new(Status::InvalidArg, "not in signers"))? as u16;
-    let sm = cggmp24::signing(eid, my_idx, extend_ref(selected.as_slice()), extend_ref(&*ks_boxed)).sign_sync(extend_mut(&mut self.rng), extend_ref(&*data));
-    self.state = ProtocolState::Signing { sm: Box::new(sm), pending: Vec::new(), tx_context: tx, _keyshare: ks_boxed, _signers: selected };
-    self.phase = "SIGNING".to_string(); self.status = "running".to_string(); self.round = Round::Signing as u32; self.last_round = Some(Round::Signing);
-    self.internal_round = "Round 1 (Partial Sign)".to_string(); // Initial round
-    Ok(())
+  let (kind, reason) = if let Some((_, rest)) = debug.split_once("Aborted(") {
+    (AbortKind::Aborted, leading_ident(rest.strip_prefix("ProtocolAborted { reason: ").unwrap_or(rest)))
+  } else if debug.contains("IoError(") {
+    (AbortKind::Io, "IoError")
+  } else {
+    (AbortKind::Internal, debug.split_once('(').map_or("Unknown", |(_, rest)| leading_ident(rest)))
+  };
+  let mut parties = Vec::new();
+  for part in debug.split("faulty_party: ").skip(1) {
+    parties.extend(leading_number(part));
+  }
+  for part in debug.split("parties: [").skip(1) {
+    let list = part.split(']').next().unwrap_or("");
+    parties.extend(list.split(", ").filter_map(|p| p.parse::<u16>().ok()));
   }
+  for part in debug.split("NotReliable([").skip(1) {
+    let list = part.split("])").next().unwrap_or("");
+    parties.extend(list.split('(').skip(1).filter_map(leading_number));
+  }
+  (kind, reason, parties)
+}
 
-  #[napi]
-  pub fn step(&mut self, inputs: Vec<napi::bindgen_prelude::Buffer>) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
-    self.processed += inputs.len();
-    
-    // 1. 스레드 안전한 Vec<u8>로 변환 (NAPI Buffer는 스레드 이동 불가)
-    let raw_inputs: Vec<Vec<u8>> = inputs.iter().map(|b| b.to_vec()).collect();
+/// 메시지 거부 사유. 전송 문제(Envelope/세션 불일치)와 상대 파티의 잘못된 메시지(payload/상태 머신)를 구분합니다.
+#[derive(Clone, Copy, Debug)]
+enum RejectReason {
+  MalformedEnvelope,
+  UnsupportedVersion,
+  SessionMismatch,
+  ExecutionMismatch,
+  CurveMismatch,
+  RoundMismatch,
+  TxContextMismatch,
+  DerivationPathMismatch,
+  InvalidSender,
+  InvalidSignature,
+  SenderNotSigner,
+  NotAddressed,
+  NoProtocol,
+  UndecodablePayload,
+  UndecryptablePayload,
+  DuplicateMessage,
+  RejectedByStateMachine,
+}
 
-    // 2. Phase 5: Rayon을 사용한 병렬 역직렬화
-    match &mut self.state {
-      ProtocolState::Keygen { pending, .. } => {
-        let decoded_msgs: Vec<Incoming<KeygenMsg>> = raw_inputs.par_iter().filter_map(|buf| {
-          if buf.len() < 5 { return None; }
-          let from_party = u32::from_le_bytes(buf[0..4].try_into().unwrap()) as u16;
-          let is_broadcast = buf[4] != 0;
-          let msg_type = if is_broadcast { MessageType::Broadcast } else { MessageType::P2P };
-          let msg: KeygenMsg = bincode::deserialize(&buf[5..]).ok()?;
-          Some(Incoming { id: 0, sender: from_party, msg_type, msg })
-        }).collect();
-        pending.extend(decoded_msgs);
-      }
-      ProtocolState::AuxGen { pending, .. } => {
-        let decoded_msgs: Vec<Incoming<AuxGenMsg>> = raw_inputs.par_iter().filter_map(|buf| {
-          if buf.len() < 5 { return None; }
-          let from_party = u32::from_le_bytes(buf[0..4].try_into().unwrap()) as u16;
-          let is_broadcast = buf[4] != 0;
-          let msg_type = if is_broadcast { MessageType::Broadcast } else { MessageType::P2P };
-          let msg: AuxGenMsg = bincode::deserialize(&buf[5..]).ok()?;
-          Some(Incoming { id: 0, sender: from_party, msg_type, msg })
-        }).collect();
-        pending.extend(decoded_msgs);
-      }
-      ProtocolState::Signing { pending, _signers, .. } => {
-        let decoded_msgs: Vec<Incoming<SigningMsg>> = raw_inputs.par_iter().filter_map(|buf| {
-          if buf.len() < 5 { return None; }
-          let from_party_global = u32::from_le_bytes(buf[0..4].try_into().unwrap()) as u16;
-          let is_broadcast = buf[4] != 0;
-          let msg_type = if is_broadcast { MessageType::Broadcast } else { MessageType::P2P };
-          let msg: SigningMsg = bincode::deserialize(&buf[5..]).ok()?;
-          let sender = _signers.iter().position(|&s| s == from_party_global)? as u16;
-          Some(Incoming { id: 0, sender, msg_type, msg })
-        }).collect();
-        pending.extend(decoded_msgs);
-      }
-      ProtocolState::None => { if !inputs.is_empty() { return Err(Error::new(Status::InvalidArg, "no protocol")); } }
-    }
-
-    // 2. 상태 머신 구동 (메시지 소진 시까지 반복)
-    let mut outgoing = Vec::new();
-    match &mut self.state {
-      ProtocolState::Keygen { sm, pending, .. } => {
-        let out = drive_sm(sm.as_mut(), pending, Round::Keygen as i32, &self.session_id, &self.execution_id, self.party_index, self.threshold, self.parties_count, &mut outgoing, &[], &mut self.meta_sent, &mut self.internal_round, |msg| {
-            match msg {
-                keygen_msg::Msg::Round1(_) => "Round 1 (Commitment)".to_string(),
-                keygen_msg::Msg::Round2Broad(_) | keygen_msg::Msg::Round2Uni(_) => "Round 2 (VSS & Share)".to_string(),
-                keygen_msg::Msg::Round3(_) => "Round 3 (Verify & Proofs)".to_string(),
-                _ => "Reliability Check".to_string(),
-            }
-        })?;
-        if let Some(res) = out {
-          self.core_keyshare = Some(res.map_err(|e| Error::new(Status::GenericFailure, format!("{e:?}")))?);
-          self.status = "keygen_finished".to_string(); self.state = ProtocolState::None; self.try_combine_shares();
-          self.internal_round = "Finished".to_string();
-        }
-      }
-      ProtocolState::AuxGen { sm, pending, .. } => {
-        let out = drive_sm(sm.as_mut(), pending, Round::AuxInfo as i32, &self.session_id, &self.execution_id, self.party_index, self.threshold, self.parties_count, &mut outgoing, &[], &mut self.meta_sent, &mut self.internal_round, |msg| {
-            match msg {
-                cggmp24::key_refresh::msg::Msg::Round1(_) => "Round 1 (Paillier Gen)".to_string(),
-                cggmp24::key_refresh::msg::Msg::Round2(_) => "Round 2 (ZKP Verify)".to_string(),
-                cggmp24::key_refresh::msg::Msg::Round3(_) => "Round 3 (Finalize)".to_string(),
-                _ => "Reliability Check".to_string(),
-            }
-        })?;
-        if let Some(res) = out {
-          self.aux_info = Some(res.map_err(|e| Error::new(Status::GenericFailure, format!("{e:?}")))?);
-          self.status = "aux_gen_finished".to_string(); self.state = ProtocolState::None; self.try_combine_shares();
-          self.internal_round = "Finished".to_string();
-        }
-      }
-      ProtocolState::Signing { sm, pending, tx_context, .. } => {
-        let out = drive_sm(sm.as_mut(), pending, Round::Signing as i32, &self.session_id, &self.execution_id, self.party_index, self.threshold, self.parties_count, &mut outgoing, tx_context, &mut self.meta_sent, &mut self.internal_round, |msg| {
-            match msg {
-                cggmp24::signing::msg::Msg::Round1a(_) | cggmp24::signing::msg::Msg::Round1b(_) => "Round 1 (Partial Sign)".to_string(),
-                cggmp24::signing::msg::Msg::Round2(_) => "Round 2 (Verify)".to_string(),
-                cggmp24::signing::msg::Msg::Round3(_) => "Round 3 (Combine)".to_string(),
-                cggmp24::signing::msg::Msg::Round4(_) => "Round 4 (Finalize)".to_string(),
-                _ => "Reliability Check".to_string(),
-            }
-        })?;
-        if let Some(res) = out {
-          let sig = res.map_err(|e| Error::new(Status::GenericFailure, format!("{e:?}")))?;
-          outgoing.push(make_envelope(&self.session_id, &self.execution_id, Round::Signing as i32, self.party_index, self.threshold, self.parties_count, &[], encode_msg(&sig)?, tx_context, self.meta_sent));
-          self.meta_sent = true;
-          self.status = "signing_finished".to_string(); self.state = ProtocolState::None;
-          self.last_signature = Some(serde_json::to_string(&sig).unwrap());
-          self.internal_round = "Finished".to_string();
-        }
-      }
-      ProtocolState::None => {}
+impl RejectReason {
+  fn code(self) -> &'static str {
+    match self {
+      RejectReason::MalformedEnvelope => "MALFORMED_ENVELOPE",
+      RejectReason::UnsupportedVersion => "UNSUPPORTED_VERSION",
+      RejectReason::SessionMismatch => "SESSION_MISMATCH",
+      RejectReason::ExecutionMismatch => "EXECUTION_MISMATCH",
+      RejectReason::CurveMismatch => "CURVE_MISMATCH",
+      RejectReason::RoundMismatch => "ROUND_MISMATCH",
+      RejectReason::TxContextMismatch => "TX_CONTEXT_MISMATCH",
+      RejectReason::DerivationPathMismatch => "DERIVATION_PATH_MISMATCH",
+      RejectReason::InvalidSender => "INVALID_SENDER",
+      RejectReason::InvalidSignature => "INVALID_SIGNATURE",
+      RejectReason::SenderNotSigner => "SENDER_NOT_SIGNER",
+      RejectReason::NotAddressed => "NOT_ADDRESSED",
+      RejectReason::NoProtocol => "NO_PROTOCOL",
+      RejectReason::UndecodablePayload => "UNDECODABLE_PAYLOAD",
+      RejectReason::UndecryptablePayload => "UNDECRYPTABLE_PAYLOAD",
+      RejectReason::DuplicateMessage => "DUPLICATE_MESSAGE",
+      RejectReason::RejectedByStateMachine => "REJECTED_BY_STATE_MACHINE",
     }
-    
-    if !self.status.ends_with("_finished") && self.status != "keyshare_ready" {
-      self.status = match &self.state {
-        ProtocolState::None => "idle".to_string(),
-        ProtocolState::Keygen { .. } => "keygen_running".to_string(),
-        ProtocolState::AuxGen { .. } => "aux_gen_running".to_string(),
-        ProtocolState::Signing { .. } => "signing_running".to_string(),
-      };
-    }
-    encode_envelopes_bin(&outgoing)
   }
 
-  #[napi]
-  pub fn snapshot(&self) -> Result<String> {
-    let (public_key, key_share_threshold) = if let Some(ks) = &self.keyshare {
-        (Some(hex::encode(ks.core.shared_public_key.to_bytes(true))), Some(ks.min_signers()))
-    } else if let Some(core) = &self.core_keyshare {
-        (Some(hex::encode(core.shared_public_key.to_bytes(true))), None)
-    } else { (None, None) };
-    let snap = ExecutorSnapshot {
-      session_id: self.session_id.clone(), execution_id: self.execution_id.clone(), party_index: self.party_index, threshold: self.threshold, parties_count: self.parties_count, phase: self.phase.clone(), round: self.round, processed: self.processed, status: self.status.clone(), errors: self.errors.clone(), last_round: self.last_round.map(|r| format!("{:?}", r)),
-      internal_round: self.internal_round.clone(), // Added
-      curve: "secp256k1".to_string(), has_aux: self.aux_info.is_some(), has_keyshare: self.keyshare.is_some(), public_key, key_share_threshold, signature: self.last_signature.clone(),
-    };
-    serde_json::to_string(&snap).map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))
+  fn reject(self, index: usize, sender: Option<u16>, message: impl Into<String>) -> MessageRejection {
+    MessageRejection { index: index as u32, sender: sender.map(u32::from), reason: self.code().to_string(), message: message.into() }
   }
+}
 
-  #[napi]
-  pub fn export_keyshare_bin(&self) -> Result<napi::bindgen_prelude::Buffer> {
-    let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare not ready"))?;
-    let buf = serde_json::to_vec(ks).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
-    Ok(napi::bindgen_prelude::Buffer::from(buf))
-  }
+/// Envelope 인증과 P2P 암호화용 식별 키 쌍
+#[napi(object)]
+pub struct IdentityKeyPair {
+  /// 32바이트 Ed25519 시드 (`setIdentity()`의 `secretKey`)
+  pub secret_key: napi::bindgen_prelude::Buffer,
+  /// 64바이트 식별 공개키: Ed25519 공개키 ‖ 시드에서 유도한 X25519 공개키 (다른 파티의 `roster`에 등록)
+  pub public_key: napi::bindgen_prelude::Buffer,
+}
 
-  #[napi]
-  pub fn export_aux_info_bin(&self) -> Result<napi::bindgen_prelude::Buffer> {
-    let aux = self.aux_info.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "aux info not ready"))?;
-    let buf = serde_json::to_vec(aux).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
-    Ok(napi::bindgen_prelude::Buffer::from(buf))
-  }
+#[napi]
+pub fn generate_identity_key() -> IdentityKeyPair {
+  let seed = Zeroizing::new(identity::generate());
+  IdentityKeyPair { secret_key: seed.to_vec().into(), public_key: identity::public_key(&seed).to_vec().into() }
+}
 
-  fn try_combine_shares(&mut self) {
-    if let (Some(core), Some(aux)) = (&self.core_keyshare, &self.aux_info) {
-      if let Ok(ks) = KeyShare::from_parts((core.clone(), aux.clone())) {
-        self.keyshare = Some(ks); self.status = "keyshare_ready".to_string();
-      }
-    }
-  }
+/// 식별 키(32바이트 시드, Node.js `crypto` Ed25519 JWK의 `d`)의 64바이트 식별 공개키 (`roster` 항목)
+#[napi]
+pub fn identity_public_key(secret_key: napi::bindgen_prelude::Buffer) -> Result<napi::bindgen_prelude::Buffer> {
+  let seed = Zeroizing::new(<[u8; identity::KEY_LEN]>::try_from(&*secret_key).map_err(|_| Error::new(Status::InvalidArg, format!("identity key must be {} bytes, got {}", identity::KEY_LEN, secret_key.len())))?);
+  Ok(identity::public_key(&seed).to_vec().into())
 }
 
-fn encode_envelopes_bin(envs: &[Envelope]) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
-  envs.iter().map(|env| {
-    let mut buf = Vec::new();
-    env.encode(&mut buf).map_err(|e| Error::new(Status::GenericFailure, format!("encode: {e}")))?;
-    Ok(napi::bindgen_prelude::Buffer::from(buf))
-  }).collect()
+/// `seed`(32바이트, `deterministic-rng` 빌드 전용)를 주면 같은 소수를 만듭니다.
+#[napi]
+pub fn generate_primes(seed: Option<napi::bindgen_prelude::Buffer>) -> Result<napi::bindgen_prelude::Buffer> {
+  let seed = seed.map(|s| test_seed(&s)).transpose()?;
+  Ok(napi::bindgen_prelude::Buffer::from(generate_primes_bytes(seed)?))
 }
 
-fn encode_msg<T: Serialize>(msg: &T) -> Result<Vec<u8>> {
-  bincode::serialize(msg).map_err(|e| Error::new(Status::GenericFailure, format!("encode: {e}")))
+/// `generatePrimes()`를 libuv 스레드 풀에서 실행합니다.
+#[napi(ts_return_type = "Promise<Buffer>")]
+pub fn generate_primes_async(seed: Option<napi::bindgen_prelude::Buffer>) -> AsyncTask<GeneratePrimesTask> {
+  AsyncTask::new(GeneratePrimesTask { seed: seed.map(|s| s.to_vec()) })
 }
 
-fn drive_sm<M, O, F>(
-    sm: &mut dyn StateMachine<Output = O, Msg = M>,
-    pending: &mut Vec<Incoming<M>>,
-    round: i32,
-    sid: &str, eid: &str, from: u16, t: u16, n: u16,
-    outgoing: &mut Vec<Envelope>,
-    tx: &[u8],
-    meta_sent: &mut bool,
-    internal_round: &mut String,
-    get_round_name: F
-) -> Result<Option<O>>
+/// 공개키(압축/비압축 SEC1, hex)와 메시지(hex)로 서명을 검증합니다. `hashMode`는 `startSigning()`과 같은 규칙으로 메시지를 해싱하고,
+/// `curve`는 "secp256k1"(기본값), "secp256r1" 또는 "stark"입니다. `format`("der", "compact", "ethereum", "bitcoin")을 주지 않으면
+/// 서명 길이로 형식을 고릅니다: r ‖ s 길이(64바이트)는 compact, 65바이트는 Ethereum(r ‖ s ‖ v), 그 외는 DER
+#[napi]
+pub fn verify_signature(public_key: String, message: String, signature: napi::bindgen_prelude::Buffer, hash_mode: Option<String>, curve: Option<String>, format: Option<String>) -> Result<bool> {
+  let hash_mode = HashMode::parse(hash_mode.as_deref()).map_err(|e| Error::new(Status::InvalidArg, e))?;
+  let format = format.as_deref().map(SignatureFormat::parse).transpose().map_err(|e| Error::new(Status::InvalidArg, e))?;
+  match curve.as_deref().unwrap_or("secp256k1") {
+    "secp256k1" => verify_signature_on::<Secp256k1>(&public_key, &message, &signature, hash_mode, format),
+    "secp256r1" => verify_signature_on::<Secp256r1>(&public_key, &message, &signature, hash_mode, format),
+    "stark" => verify_signature_on::<Stark>(&public_key, &message, &signature, hash_mode, format),
+    other => Err(Error::new(Status::InvalidArg, format!("unsupported curve: {other}"))),
+  }
+}
+
+fn verify_signature_on<E: SupportedCurve>(public_key: &str, message: &str, signature: &[u8], hash_mode: HashMode, format: Option<SignatureFormat>) -> Result<bool>
 where
-    M: Clone + Serialize + for<'de> Deserialize<'de>,
-    F: Fn(&M) -> String,
+  NonZero<Point<E>>: AlwaysHasAffineX<E>,
 {
-  loop {
-    match sm.proceed() {
-      ProceedResult::SendMsg(out) => {
-        let to = match out.recipient { MessageDestination::AllParties => Vec::new(), MessageDestination::OneParty(i) => vec![i as u32] };
-        *internal_round = get_round_name(&out.msg); // Update internal round
-        outgoing.push(make_envelope(sid, eid, round, from, t, n, &to, encode_msg(&out.msg)?, tx, *meta_sent));
-        *meta_sent = true;
-      }
-      ProceedResult::NeedsOneMoreMessage => {
-        if !pending.is_empty() {
-          let msg = pending.remove(0); // FIFO
-          sm.received_msg(msg).map_err(|_| Error::new(Status::GenericFailure, "rejected by state machine"))?;
-        } else {
-          return Ok(None);
-        }
-      }
-      ProceedResult::Yielded => continue,
-      ProceedResult::Output(o) => return Ok(Some(o)),
-      ProceedResult::Error(err) => return Err(Error::new(Status::GenericFailure, format!("{err}"))),
-    }
+  if let Some(format) = format.filter(|f| f.secp256k1_only() && E::CURVE_NAME != Secp256k1::CURVE_NAME) {
+    return Err(Error::new(Status::InvalidArg, format!("{} signature format is not supported on the {} curve", format.name(), E::CURVE_NAME)));
   }
+  let public_key = hex::decode(public_key).ok().and_then(|pk| Point::<E>::from_bytes(pk).ok())
+    .ok_or_else(|| Error::new(Status::InvalidArg, format!("invalid {} public key", E::CURVE_NAME)))?;
+  let message = hex::decode(message).map_err(|e| Error::new(Status::InvalidArg, format!("invalid hex: {e}")))?;
+  let data = E::data_to_sign(&message, hash_mode)?;
+  let signature = signature::decode::<E>(signature, format).map_err(|e| Error::new(Status::InvalidArg, e))?;
+  Ok(signature.verify(&public_key, &*data).is_ok())
 }
 
-fn make_envelope(sid: &str, eid: &str, round: i32, from: u16, t: u16, n: u16, to: &[u32], payload: Vec<u8>, tx: &[u8], meta_sent: bool) -> Envelope {
-  let p_enum = if round == Round::Keygen as i32 { Some(Payload::Keygen(proto::Keygen { payload })) }
-    else if round == Round::AuxInfo as i32 { Some(Payload::AuxInfo(proto::AuxInfo { payload })) }
-    else if round == Round::Signing as i32 { Some(Payload::Signing(proto::Signing { payload, tx_context: tx.to_vec() })) }
-    else { None };
-  
-  let curve = proto::Curve::Secp256k1 as i32;
-  
-  let meta = if meta_sent {
-    None
-  } else {
-    Some(proto::Meta { curve, threshold: t as u32, parties_count: n as u32, party_index: from as u32, tx_context: tx.to_vec(), retry: 0, payload_format: PAYLOAD_FORMAT_BINCODE.to_string(), key_id: String::new() })
+/// 시뮬레이션 난수원 (`seed`는 `test_seed()`로 확인)
+fn sim_rng(seed: Option<napi::bindgen_prelude::Buffer>) -> Result<sim::SimRng> {
+  Ok(sim::SimRng::new(seed.map(|s| test_seed(&s)).transpose()?))
+}
+
+/// 한 프로세스에서 `n`개 파티의 키 생성(임계값 `t`)을 실행합니다. 테스트와 픽스처 생성용입니다.
+/// `auxInfos`(파티 순서)를 주면 `importKeyshare()`로 가져올 수 있는 키쉐어를, 없으면 aux info 없는 core 키쉐어(JSON)를 반환합니다.
+/// `seed`(32바이트, `deterministic-rng` 빌드 전용)를 주면 같은 시드에서 같은 키쉐어를 만듭니다.
+#[napi]
+pub fn simulate_keygen(n: u16, t: u16, aux_infos: Option<Vec<napi::bindgen_prelude::Buffer>>, curve: Option<String>, seed: Option<napi::bindgen_prelude::Buffer>) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
+  let aux_infos = aux_infos.map(|list| list.iter().map(|aux| parse_key_export(aux, Content::AuxInfo, None)).collect::<Result<Vec<AuxInfoMsg>>>()).transpose()?;
+  let rng = &mut sim_rng(seed)?;
+  let shares = match curve.as_deref().unwrap_or("secp256k1") {
+    "secp256k1" => sim::keygen::<Secp256k1>(n, t, aux_infos, rng)?,
+    "secp256r1" => sim::keygen::<Secp256r1>(n, t, aux_infos, rng)?,
+    "stark" => sim::keygen::<Stark>(n, t, aux_infos, rng)?,
+    other => return Err(Error::new(Status::InvalidArg, format!("unsupported curve: {other}"))),
   };
+  Ok(shares.into_iter().map(napi::bindgen_prelude::Buffer::from).collect())
+}
 
-  Envelope { version: 1, session_id: sid.to_string(), execution_id: eid.to_string(), round, from_party: from as u32, to_parties: to.to_vec(), meta, payload: p_enum }
+/// 한 프로세스에서 `n`개 파티의 aux info 생성을 실행합니다. `primes`(`generatePrimes()` 결과, 파티별)가 없으면 안전 소수를 병렬로 생성합니다.
+/// `seed`는 `simulateKeygen()`과 같습니다.
+#[napi]
+pub fn simulate_aux_gen(n: u16, primes: Option<Vec<napi::bindgen_prelude::Buffer>>, seed: Option<napi::bindgen_prelude::Buffer>) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
+  let aux_infos = sim::aux_gen(n, primes.map(|list| list.iter().map(|p| p.to_vec()).collect()), &mut sim_rng(seed)?)?;
+  Ok(aux_infos.into_iter().map(napi::bindgen_prelude::Buffer::from).collect())
 }
 
-fn derive_execution_seed(sid: &str, eid: &str, phase: &str) -> &'static [u8; 32] {
-  let mut hasher = Sha256::new();
-  hasher.update(sid.as_bytes()); hasher.update(b":"); hasher.update(eid.as_bytes()); hasher.update(b":"); hasher.update(phase.as_bytes());
-  let mut seed = [0u8; 32]; seed.copy_from_slice(&hasher.finalize()); Box::leak(Box::new(seed))
+/// 한 프로세스에서 `signers`(키 생성 시 파티 인덱스)의 키쉐어로 메시지(hex)에 서명하고 compact 서명(r ‖ s)을 반환합니다.
+/// `shares`는 `exportKeyshare()` 형식이며 순서는 상관없습니다. 서명은 반환 전에 공개키로 검증됩니다.
+/// `seed`는 `simulateKeygen()`과 같습니다.
+#[napi]
+pub fn simulate_signing(shares: Vec<napi::bindgen_prelude::Buffer>, signers: Vec<u16>, message: String, options: Option<SigningOptions>, curve: Option<String>, seed: Option<napi::bindgen_prelude::Buffer>) -> Result<napi::bindgen_prelude::Buffer> {
+  let shares: Vec<Vec<u8>> = shares.iter().map(|s| s.to_vec()).collect();
+  let msg = hex::decode(message).map_err(|e| Error::new(Status::InvalidArg, format!("invalid hex: {e}")))?;
+  let options = options.unwrap_or_default();
+  let rng = &mut sim_rng(seed)?;
+  let sig = match curve.as_deref().unwrap_or("secp256k1") {
+    "secp256k1" => signature::compact(&sim::signing::<Secp256k1>(&shares, &signers, &msg, &options, rng)?),
+    "secp256r1" => signature::compact(&sim::signing::<Secp256r1>(&shares, &signers, &msg, &options, rng)?),
+    "stark" => signature::compact(&sim::signing::<Stark>(&shares, &signers, &msg, &options, rng)?),
+    other => return Err(Error::new(Status::InvalidArg, format!("unsupported curve: {other}"))),
+  };
+  Ok(sig.into())
 }
 
-fn extend_mut<T>(v: &mut T) -> &'static mut T { unsafe { &mut *(v as *mut T) } }
-fn extend_ref<T: ?Sized>(v: &T) -> &'static T { unsafe { &*(v as *const T) } }
\ No newline at end of file
+/// 임계값 이상의 키쉐어에서 전체 비밀키(32바이트 big-endian)를 복원하고 공유 공개키와 일치하는지 확인합니다.
+/// 오프라인 복구 절차 전용이며 `key-export` cargo 기능으로 빌드하지 않으면 항상 실패합니다.
+#[cfg(feature = "key-export")]
+#[napi]
+pub fn reconstruct_secret_key(shares: Vec<napi::bindgen_prelude::Buffer>, curve: Option<String>) -> Result<napi::bindgen_prelude::Buffer> {
+  let shares: Vec<&[u8]> = shares.iter().map(|s| s.as_ref()).collect();
+  let sk = match curve.as_deref().unwrap_or("secp256k1") {
+    "secp256k1" => recovery::reconstruct::<Secp256k1>(&shares)?,
+    "secp256r1" => recovery::reconstruct::<Secp256r1>(&shares)?,
+    "stark" => recovery::reconstruct::<Stark>(&shares)?,
+    other => return Err(Error::new(Status::InvalidArg, format!("unsupported curve: {other}"))),
+  };
+  Ok(sk.to_vec().into())
+}
+
+#[cfg(not(feature = "key-export"))]
+#[napi]
+pub fn reconstruct_secret_key(shares: Vec<napi::bindgen_prelude::Buffer>, curve: Option<String>) -> Result<napi::bindgen_prelude::Buffer> {
+  let _ = (shares, curve);
+  Err(Error::new(Status::GenericFailure, "secret key reconstruction is disabled: build with the `key-export` cargo feature"))
+}
+
+/// `dealKeyShares()` 옵션
+#[napi(object)]
+#[derive(Default)]
+pub struct DealerOptions {
+  /// 유지할 BIP-32 체인 코드 (32바이트). 없으면 새로 생성
+  pub chain_code: Option<napi::bindgen_prelude::Buffer>,
+  /// 파티별 `generatePrimes()` 결과. 없으면 안전 소수를 병렬로 생성
+  pub primes: Option<Vec<napi::bindgen_prelude::Buffer>>,
+  /// "secp256k1"(기본값), "secp256r1" 또는 "stark"
+  pub curve: Option<String>,
+}
+
+/// 신뢰 딜러로 기존 비밀키(32바이트 big-endian)를 `n`개 쉐어(임계값 `t`)로 나눕니다. aux info를 포함한 키쉐어를 파티 순서로 반환하며
+/// 각 파티는 `importKeyshare()`로 가져옵니다. 딜러가 모든 쉐어를 알게 되므로 가져온 뒤 `startKeyRefresh()`로 교체해야 합니다.
+#[napi]
+pub fn deal_key_shares(secret_key: napi::bindgen_prelude::Buffer, n: u16, t: u16, options: Option<DealerOptions>) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
+  let DealerOptions { chain_code, primes, curve } = options.unwrap_or_default();
+  let chain_code = chain_code.as_deref();
+  let primes = primes.map(|list| list.iter().map(|p| p.to_vec()).collect());
+  let shares = match curve.as_deref().unwrap_or("secp256k1") {
+    "secp256k1" => dealer::deal::<Secp256k1>(&secret_key, n, t, chain_code, primes)?,
+    "secp256r1" => dealer::deal::<Secp256r1>(&secret_key, n, t, chain_code, primes)?,
+    "stark" => dealer::deal::<Stark>(&secret_key, n, t, chain_code, primes)?,
+    other => return Err(Error::new(Status::InvalidArg, format!("unsupported curve: {other}"))),
+  };
+  Ok(shares.iter().map(|ks| napi::bindgen_prelude::Buffer::from(ks.to_vec())).collect())
+}
+
+/// 암호화된 키 컨테이너 헤더 (복호화 없이 읽을 수 있는 값)
+#[napi(object)]
+pub struct EncryptedKeyInfo {
+  pub version: u32,
+  /// "keyshare" 또는 "aux_info"
+  pub content: String,
+  /// 키쉐어의 곡선 (aux info는 없음)
+  pub curve: Option<String>,
+  /// "argon2id"(패스프레이즈) 또는 "kek"
+  pub kdf: String,
+  /// 압축 공개키 (hex)
+  pub public_key: Option<String>,
+  /// 공개키의 SHA-256 (hex)
+  pub key_id: Option<String>,
+}
+
+/// `exportKeyshareEncrypted()` / `exportAuxInfoEncrypted()` 결과의 헤더를 복호화 없이 읽습니다.
+#[napi]
+pub fn read_encrypted_key_info(data: napi::bindgen_prelude::Buffer) -> Result<EncryptedKeyInfo> {
+  let (header, _) = sealed::Header::decode(&data).map_err(|e| Error::new(Status::InvalidArg, e))?;
+  let non_empty = |v: &[u8]| (!v.is_empty()).then(|| hex::encode(v));
+  Ok(EncryptedKeyInfo {
+    version: u32::from(header.version), content: header.content.name().to_string(), curve: curve_name(header.curve).map(str::to_string),
+    kdf: header.kdf.name().to_string(), public_key: non_empty(&header.public_key), key_id: non_empty(&header.key_id),
+  })
+}
+
+/// `validateKeyshare()` 결과
+#[napi(object)]
+pub struct KeyshareReport {
+  /// 모든 검사를 통과했는지 여부 (`problems`가 비어 있음)
+  pub valid: bool,
+  pub curve: String,
+  /// 압축 공개키 (hex)
+  pub public_key: String,
+  /// 공개키의 SHA-256 (hex), 암호화 컨테이너의 키 ID와 같음
+  pub key_id: String,
+  pub party_index: u32,
+  pub parties_count: u32,
+  /// 서명에 필요한 최소 파티 수
+  pub threshold: u32,
+  pub has_aux: bool,
+  /// aux info가 있고 키쉐어와 일치하는지 여부 (파티 수, N_i = p * q)
+  pub aux_consistent: bool,
+  pub problems: Vec<String>,
+}
+
+/// 키쉐어/aux info 가져오기 형식 자동 판별: 바이너리(`CGMB`), JSON, 레거시 base64-JSON
+fn parse_key_export<T: serde::de::DeserializeOwned>(data: &[u8], content: Content, curve: Option<proto::Curve>) -> Result<T> {
+  if binary::is_binary(data) {
+    let (found, found_curve, body) = binary::decode_header(data).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    check_content(found, found_curve, content, curve)?;
+    return binary::decode_body(body).map_err(|e| Error::new(Status::InvalidArg, e));
+  }
+  if !data.is_empty() && data[0] == b'{' {
+    return serde_json::from_slice(data).map_err(|e| Error::new(Status::InvalidArg, format!("parse json: {e}")));
+  }
+  match serde_json::from_slice(data) {
+    Ok(v) => Ok(v),
+    Err(_) => {
+      let decoded = base64::engine::general_purpose::STANDARD.decode(data)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("not json and not base64: {e}")))?;
+      serde_json::from_slice(&decoded)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("parse legacy base64-json: {e}")))
+    }
+  }
+}
+
+/// 컨테이너를 복호화하고 내용 종류와 곡선을 확인
+fn open_sealed(data: &[u8], secret: &Secret, content: Content, curve: Option<proto::Curve>) -> Result<Zeroizing<Vec<u8>>> {
+  let (header, plain) = sealed::open(data, secret).map_err(|e| Error::new(Status::InvalidArg, e))?;
+  check_content(header.content, header.curve, content, curve)?;
+  Ok(plain)
+}
+
+fn check_content(found: Content, found_curve: u8, content: Content, curve: Option<proto::Curve>) -> Result<()> {
+  if found != content {
+    return Err(Error::new(Status::InvalidArg, format!("expected {}, got {}", content.name(), found.name())));
+  }
+  if let Some(curve) = curve {
+    if found_curve != curve as u8 {
+      return Err(Error::new(Status::InvalidArg, format!("curve mismatch: key share is {}", curve_name(found_curve).unwrap_or("unknown"))));
+    }
+  }
+  Ok(())
+}
+
+fn curve_name(curve: u8) -> Option<&'static str> {
+  match proto::Curve::try_from(i32::from(curve)).ok()? {
+    proto::Curve::Secp256k1 => Some(Secp256k1::CURVE_NAME),
+    proto::Curve::Secp256r1 => Some(Secp256r1::CURVE_NAME),
+    proto::Curve::Stark => Some(Stark::CURVE_NAME),
+    proto::Curve::Unspecified => None,
+  }
+}
+
+/// "m/0/5" 형식의 비강화 파생 경로 ("m" 또는 빈 문자열은 루트 키)
+fn parse_derivation_path(path: &str) -> Result<Vec<u32>> {
+  let path = path.trim();
+  let rest = path.strip_prefix('m').map(|r| r.strip_prefix('/').unwrap_or(r)).unwrap_or(path);
+  if rest.is_empty() {
+    return Ok(Vec::new());
+  }
+  rest.split('/').map(|seg| {
+    if seg.ends_with(['\'', 'h', 'H']) {
+      return Err(Error::new(Status::InvalidArg, format!("hardened derivation is not supported in threshold signing: {seg}")));
+    }
+    match seg.parse::<u32>() {
+      Ok(index) if index < cggmp24::hd_wallet::H => Ok(index),
+      Ok(_) => Err(Error::new(Status::InvalidArg, format!("hardened derivation is not supported in threshold signing: {seg}"))),
+      Err(_) => Err(Error::new(Status::InvalidArg, format!("invalid derivation path segment: {seg:?}"))),
+    }
+  }).collect()
+}
+
+fn format_derivation_path(path: &[u32]) -> String {
+  std::iter::once("m".to_string()).chain(path.iter().map(u32::to_string)).collect::<Vec<_>>().join("/")
+}
+
+fn hd_error<T: std::fmt::Debug>(e: cggmp24::key_share::HdError<T>) -> Error {
+  match e {
+    cggmp24::key_share::HdError::DisabledHd => Error::new(Status::InvalidArg, "key share has no chain code: HD derivation is disabled for this key"),
+    cggmp24::key_share::HdError::InvalidPath(e) => Error::new(Status::InvalidArg, format!("invalid derivation path: {e:?}")),
+  }
+}
+
+fn generate_primes_bytes(seed: Option<[u8; 32]>) -> Result<Vec<u8>> {
+  let mut rng = seed.map_or_else(StdRng::from_entropy, StdRng::from_seed);
+  let primes: cggmp24::PregeneratedPrimes<SecLevel> = cggmp24::PregeneratedPrimes::generate(&mut rng);
+  bincode::serialize(&primes).map_err(|e| Error::new(Status::GenericFailure, format!("serialize: {e}")))
+}
+
+/// 실행기가 지원하는 곡선과 proto `Curve` 값의 대응
+trait SupportedCurve: Curve {
+  const PROTO: proto::Curve;
+  /// HD 파생 알고리즘 (secp256k1/secp256r1: SLIP-10, BIP-32와 동일한 비강화 파생)
+  type Hd: cggmp24::hd_wallet::HdWallet<Self>;
+  /// 프리서명 기반 서명 지원 여부
+  const PRESIGNING: bool = true;
+
+  /// `startSigning` 메시지 해싱. 방식 미지정 시 32바이트인 경우 이미 해시된 데이터로 처리, 아니면 SHA256으로 해싱
+  fn data_to_sign(msg: &[u8], mode: HashMode) -> Result<Box<dyn AnyDataToSign<Self>>> {
+    let digest = mode.digest(msg).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    Ok(Box::new(PrehashedDataToSign::from_scalar(generic_ec::Scalar::<Self>::from_be_bytes_mod_order(digest))))
+  }
+
+  /// 프리서명은 원문을 알고 있는 메시지만 서명할 수 있으므로 prehash는 지원하지 않음. 방식 미지정 규칙은
+  /// `startSigning`과 같아서, prehash가 될 32바이트 메시지는 다른 다이제스트를 서명하지 않도록 거부
+  fn presign_data_to_sign(msg: &[u8], mode: HashMode) -> Result<DataToSign<Self>> {
+    match mode.resolve(msg) {
+      HashMode::Sha256 => Ok(DataToSign::<Self>::digest::<Sha256>(msg)),
+      HashMode::Sha256d => Ok(DataToSign::<Self>::digest::<Sha256>(&Sha256::digest(msg))),
+      HashMode::Keccak256 => Ok(DataToSign::<Self>::digest::<Keccak256>(msg)),
+      HashMode::Eip191 => Ok(DataToSign::<Self>::from_digest(hashing::eip191_hasher(msg))),
+      HashMode::Eip712 => {
+        let hashes = hashing::eip712_typed_data_hashes(msg).map_err(|e| Error::new(Status::InvalidArg, e))?;
+        Ok(DataToSign::<Self>::from_digest(hashing::eip712_hasher(&hashes).map_err(|e| Error::new(Status::InvalidArg, e))?))
+      }
+      HashMode::Eip712Hashed => Ok(DataToSign::<Self>::from_digest(hashing::eip712_hasher(msg).map_err(|e| Error::new(Status::InvalidArg, e))?)),
+      HashMode::Prehashed if mode == HashMode::Auto => Err(Error::new(Status::InvalidArg, "a 32-byte message is signed as a prehash without hashMode, which presignatures cannot do: pass hashMode explicitly")),
+      HashMode::Prehashed | HashMode::Auto => Err(Error::new(Status::InvalidArg, "presignatures cannot sign a prehashed message: the message must be known")),
+    }
+  }
+}
+impl SupportedCurve for Secp256k1 { const PROTO: proto::Curve = proto::Curve::Secp256k1; type Hd = cggmp24::hd_wallet::Slip10; }
+impl SupportedCurve for Secp256r1 { const PROTO: proto::Curve = proto::Curve::Secp256r1; type Hd = cggmp24::hd_wallet::Slip10; }
+impl SupportedCurve for Stark {
+  const PROTO: proto::Curve = proto::Curve::Stark;
+  type Hd = cggmp24::hd_wallet::Stark;
+  const PRESIGNING: bool = false;
+
+  /// 방식 미지정 시 StarkNet 해싱, `prehashed`는 32바이트 felt를 그대로 서명
+  fn data_to_sign(msg: &[u8], mode: HashMode) -> Result<Box<dyn AnyDataToSign<Self>>> {
+    let scalar = match mode {
+      HashMode::Auto => stark_message_hash(msg)?,
+      HashMode::Prehashed if msg.len() == 32 => stark_message_hash(msg)?,
+      mode => generic_ec::Scalar::<Self>::from_be_bytes_mod_order(mode.digest(msg).map_err(|e| Error::new(Status::InvalidArg, e))?),
+    };
+    Ok(Box::new(PrehashedDataToSign::from_scalar(scalar)))
+  }
+
+  // StarkNet은 트랜잭션 해시(felt)를 그대로 서명하는데, 프리서명은 prehash를 지원하지 않음
+  fn presign_data_to_sign(_msg: &[u8], _mode: HashMode) -> Result<DataToSign<Self>> {
+    Err(Error::new(Status::InvalidArg, "presignature signing is not supported on the stark curve"))
+  }
+}
+
+/// StarkNet 방식 메시지 해시: 32바이트 이하는 felt(< 2^251)로 그대로 사용하고,
+/// 그보다 긴 메시지는 sn_keccak(Keccak256의 하위 250비트)으로 해싱
+fn stark_message_hash(msg: &[u8]) -> Result<generic_ec::Scalar<Stark>> {
+  let mut felt = [0u8; 32];
+  if msg.len() <= 32 {
+    felt[32 - msg.len()..].copy_from_slice(msg);
+    if felt[0] >= 0x08 {
+      return Err(Error::new(Status::InvalidArg, "stark message hash must be less than 2^251"));
+    }
+  } else {
+    felt.copy_from_slice(&Keccak256::digest(msg));
+    felt[0] &= 0x03;
+  }
+  Ok(generic_ec::Scalar::<Stark>::from_be_bytes_mod_order(felt))
+}
+
+/// 곡선별 실행기. 세션 상태와 키쉐어는 모두 선택한 곡선에 묶입니다.
+enum CurveExecutor {
+  Secp256k1(Box<Executor<Secp256k1>>),
+  Secp256r1(Box<Executor<Secp256r1>>),
+  Stark(Box<Executor<Stark>>),
+}
+
+macro_rules! dispatch {
+  ($inner:expr, $ex:ident => $body:expr) => {
+    match $inner {
+      CurveExecutor::Secp256k1($ex) => $body,
+      CurveExecutor::Secp256r1($ex) => $body,
+      CurveExecutor::Stark($ex) => $body,
+    }
+  };
+}
+
+// SAFETY: `CurveExecutor`가 `Send`가 아닌 이유는 `ProtocolState`의 상태 머신 두 가지뿐입니다.
+// 1. `round_based::state_machine::wrap_protocol`은 상태 머신과 그 안의 프로토콜 future가 `Rc<RefCell<..>>`로
+//    공유 상태를 나눠 가집니다. 이 `Rc`의 복제본은 모두 같은 `ProtocolState` 값 안에 있고 밖으로 꺼내지 않으므로
+//    (`Incoming`/`Outgoing`에는 `Rc`가 없음) 항상 함께 한 스레드로 옮겨지며, 참조 카운트를 두 스레드가 동시에
+//    건드릴 수 없습니다. 상태 머신은 `Waker`를 보관하지 않는 noop waker로만 poll됩니다.
+// 2. `extend_mut`/`extend_ref`로 `'static`까지 연장한 참조(RNG, ExecutionId, 키쉐어, 서명할 데이터, 서명자 목록)는
+//    모두 같은 `Executor`가 소유한 힙 할당(`Box`, `Vec` 버퍼)을 가리킵니다. 실행기 자체도 `Box`에 들어 있어
+//    `CurveExecutor`를 옮겨도 주소가 바뀌지 않고, 상태 머신을 교체하거나 버릴 때까지 그 값들을 해제하지 않습니다.
+// 이 값들은 스레드 로컬이나 특정 스레드에 묶인 자원(JS 값 등)을 참조하지 않습니다. 또한 `CurveExecutor`는
+// `CggmpExecutor`의 `Arc<Mutex<..>>` 안에만 있어 JS 스레드와 libuv 작업 스레드 중 잠금을 가진 한 곳에서만 접근합니다.
+unsafe impl Send for CurveExecutor {}
+
+#[napi]
+pub struct CggmpExecutor {
+  inner: Arc<Mutex<CurveExecutor>>,
+  /// 비동기 작업 실행 중 여부 (JS 스레드에서 설정/해제)
+  busy: Arc<AtomicBool>,
+}
+
+struct Executor<E: Curve> {
+  session_id: String,
+  execution_id: String,
+  party_index: u16,
+  threshold: u16,
+  parties_count: u16,
+  signers_at_keygen: Option<Vec<u16>>,
+  rng: Box<UnsafeRng>,
+  state: ProtocolState<E>,
+  core_keyshare: Option<CoreKeyShare<E>>,
+  aux_info: Option<AuxInfoMsg>,
+  keyshare: Option<KeyShareWithLevel<E>>,
+  /// 키 갱신이나 재공유로 받은 새 쉐어. `commitKeyRefresh()` 전까지는 기존 쉐어를 그대로 사용
+  pending_keyshare: Option<KeyShareWithLevel<E>>,
+  processed: usize,
+  phase: String,
+  round: u32,
+  errors: Vec<MessageRejection>,
+  last_round: Option<Round>,
+  internal_round: String, // Added detailed internal round info
+  status: String,
+  last_signature: Option<SignedMessage<E>>,
+  presignature: Option<StoredPresignature<E>>,
+  /// 부분 서명을 발행한 프리서명의 ID (같은 프리서명을 다시 가져와 쓰지 못하도록 보관)
+  used_presignatures: Vec<[u8; 32]>,
+  /// 이 실행기가 시작한 프로토콜의 ExecutionId (같은 ID로 다시 시작하면 이전 실행의 Envelope를 재생할 수 있으므로 보관)
+  used_execution_ids: Vec<[u8; 32]>,
+  /// 보관한 프리서명으로 `signWithPresignature()`를 호출하기 전에 도착한 부분 서명 Envelope (보낸 파티, 원본)
+  early_partials: Vec<(u16, Vec<u8>)>,
+  meta_sent: bool,
+  abort: Option<ProtocolAbort>,
+  /// 설정되면 발신 Envelope에 서명하고 수신 Envelope의 서명을 요구
+  identity: Option<Identity>,
+  /// 신뢰 브로드캐스트(Reliability Check 라운드) 강제 여부. 없으면 키 생성/aux gen은 끄고 서명/프리사인/쉐어 재분배는 켬
+  reliable_broadcast: Option<bool>,
+  /// `setExecutionId()`로 지정한 다음 프로토콜의 ExecutionId (시작 시 소비)
+  next_execution_id: Option<[u8; 32]>,
+  /// `withSeed()`로 만든 실행기의 RNG 시드 출처 (없으면 OS 엔트로피)
+  seed_source: Option<SeedSource>,
+  /// P2P payload 암호화(ECIES 임시 키) RNG. 시드 실행기만 프로토콜 시드에서 따로 유도하고, 없으면 OsRng
+  seal_rng: Option<StdRng>,
+  /// 현재 프로토콜 시작 시점의 체크포인트 (JSON)
+  checkpoint: Vec<u8>,
+  /// 체크포인트 이후 호출 기록
+  journal: Vec<JournalEntry>,
+  /// 체크포인트 이후 보낸 Envelope
+  sent: Vec<Vec<u8>>,
+  /// `restore()` 중 기록 재실행 여부
+  replaying: bool,
+}
+
+/// 상태 머신을 제외한 실행기 상태와 RNG 시드
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct Checkpoint<E: Curve> {
+  seed: [u8; 32],
+  signers_at_keygen: Option<Vec<u16>>,
+  core_keyshare: Option<CoreKeyShare<E>>,
+  aux_info: Option<AuxInfoMsg>,
+  keyshare: Option<KeyShareWithLevel<E>>,
+  presignature: Option<StoredPresignature<E>>,
+  processed: usize,
+  phase: String,
+  round: u32,
+  errors: Vec<MessageRejection>,
+  last_round: Option<i32>,
+  internal_round: String,
+  status: String,
+  last_signature: Option<SignedMessage<E>>,
+  meta_sent: bool,
+  abort: Option<ProtocolAbort>,
+  /// 식별 공개키 명단 (식별 키는 저장하지 않음)
+  #[serde(default)]
+  identity_roster: Option<Vec<Vec<u8>>>,
+  #[serde(default)]
+  reliable_broadcast: Option<bool>,
+  #[serde(default)]
+  next_execution_id: Option<[u8; 32]>,
+  #[serde(default)]
+  seed_source: Option<SeedSource>,
+  #[serde(default)]
+  used_presignatures: Vec<[u8; 32]>,
+  #[serde(default)]
+  pending_keyshare: Option<KeyShareWithLevel<E>>,
+  #[serde(default)]
+  early_partials: Vec<(u16, Vec<u8>)>,
+  #[serde(default)]
+  used_execution_ids: Vec<[u8; 32]>,
+}
+
+#[napi]
+impl CggmpExecutor {
+  /// `curve`는 "secp256k1"(기본값), "secp256r1" 또는 "stark"
+  #[napi(constructor)]
+  pub fn new(session_id: String, execution_id: String, party_index: u16, threshold: u16, parties_count: u16, curve: Option<String>) -> Result<Self> {
+    let inner = match curve.as_deref().unwrap_or("secp256k1") {
+      "secp256k1" => CurveExecutor::Secp256k1(Box::new(Executor::new(session_id, execution_id, party_index, threshold, parties_count)?)),
+      "secp256r1" => CurveExecutor::Secp256r1(Box::new(Executor::new(session_id, execution_id, party_index, threshold, parties_count)?)),
+      "stark" => CurveExecutor::Stark(Box::new(Executor::new(session_id, execution_id, party_index, threshold, parties_count)?)),
+      other => return Err(Error::new(Status::InvalidArg, format!("unsupported curve: {other}"))),
+    };
+    Ok(Self { inner: Arc::new(Mutex::new(inner)), busy: Arc::new(AtomicBool::new(false)) })
+  }
+
+  /// RNG를 `seed`(32바이트)로 초기화한 실행기. 같은 시드와 입력이면 같은 Envelope를 만들므로 골든 테스트 벡터에 씁니다 (`deterministic-rng` 빌드 전용).
+  #[napi(factory)]
+  pub fn with_seed(session_id: String, execution_id: String, party_index: u16, threshold: u16, parties_count: u16, seed: napi::bindgen_prelude::Buffer, curve: Option<String>) -> Result<Self> {
+    let executor = Self::new(session_id, execution_id, party_index, threshold, parties_count, curve)?;
+    dispatch!(&mut *executor.lock()?, ex => ex.seed_rng(&seed))?;
+    Ok(executor)
+  }
+
+  #[napi]
+  pub fn export_keyshare(&self) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.export_keyshare()) }
+
+  #[napi]
+  pub fn export_aux_info(&self) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.export_aux_info()) }
+
+  #[napi]
+  pub fn import_keyshare(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.import_keyshare(data)) }
+
+  #[napi]
+  pub fn import_aux_info(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.import_aux_info(data)) }
+
+  /// 실행기의 키쉐어, aux info, 프리서명을 삭제합니다. 재분배로 위원회에서 빠진 파티는 새 위원회가
+  /// 새 쉐어로 서명하는 등 확인을 마친 뒤 호출합니다. 프로토콜 실행 중에는 호출할 수 없습니다.
+  #[napi]
+  pub fn discard_keyshare(&mut self) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.discard_keyshare()) }
+
+  /// 키쉐어를 암호화해 내보냅니다. `secret`이 문자열이면 패스프레이즈(Argon2id), Buffer면 32바이트 KEK입니다.
+  /// 공개키와 키 ID는 헤더에 평문으로 남습니다.
+  #[napi]
+  pub fn export_keyshare_encrypted(&self, secret: Either<String, napi::bindgen_prelude::Buffer>) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.export_keyshare_encrypted(&secret.into())) }
+
+  #[napi]
+  pub fn import_keyshare_encrypted(&mut self, data: napi::bindgen_prelude::Buffer, secret: Either<String, napi::bindgen_prelude::Buffer>) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.import_keyshare_encrypted(data, &secret.into())) }
+
+  /// aux info를 암호화해 내보냅니다 (Paillier 비밀키 포함).
+  #[napi]
+  pub fn export_aux_info_encrypted(&self, secret: Either<String, napi::bindgen_prelude::Buffer>) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.export_aux_info_encrypted(&secret.into())) }
+
+  #[napi]
+  pub fn import_aux_info_encrypted(&mut self, data: napi::bindgen_prelude::Buffer, secret: Either<String, napi::bindgen_prelude::Buffer>) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.import_aux_info_encrypted(data, &secret.into())) }
+
+  #[napi]
+  pub fn start_keygen(&mut self) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_keygen()) }
+
+  #[napi]
+  pub fn start_aux_gen(&mut self) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_aux_gen()) }
+
+  #[napi]
+  pub fn start_aux_gen_with_primes(&mut self, primes_buf: napi::bindgen_prelude::Buffer) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_aux_gen_with_primes(primes_buf)) }
+
+  /// 기존 키쉐어를 새 쉐어로 교체합니다 (공개키 유지, aux info 재생성). 모든 파티가 참여해야 하고,
+  /// 새 쉐어를 P2P로 주고받으므로 먼저 `setIdentity()`가 필요합니다.
+  #[napi]
+  pub fn start_key_refresh(&mut self) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_key_refresh()) }
+
+  #[napi]
+  pub fn start_key_refresh_with_primes(&mut self, primes_buf: napi::bindgen_prelude::Buffer) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_key_refresh_with_primes(primes_buf)) }
+
+  /// 키 갱신이나 재공유로 받은 새 쉐어를 설치하고 기존 쉐어와 프리서명을 버립니다. 모든 파티가 `key_refresh_finished`
+  /// (재공유는 새 위원회 전체가 `reshare_finished`)에 도달했음을 별도 채널로 확인한 뒤 호출합니다. 그 전까지는 기존 쉐어로 서명하고 내보낼 수 있습니다.
+  #[napi]
+  pub fn commit_key_refresh(&mut self) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.commit_key_refresh()) }
+
+  /// 기존 위원회의 키를 파티 수와 threshold가 다른 새 위원회로 재공유합니다 (공개키 유지).
+  /// 실행기는 이번 실행의 참가자(기존 ∪ 새 위원회) 기준으로 만들고 `threshold`에 새 threshold를 지정합니다.
+  /// 완료 후 새 위원회 파티는 `commitKeyRefresh()`로 새 쉐어를 설치하고, `exportKeyshare()` 결과를 새 파티 인덱스로 만든 실행기에 가져옵니다.
+  /// 새 쉐어를 P2P로 암호화해 보내므로 `setIdentity()`가 필요합니다.
+  #[napi]
+  pub fn start_reshare(&mut self, options: ReshareOptions) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_reshare(options.into())) }
+
+  /// 키쉐어의 공개 정보 (공개키, 공개 쉐어, VSS 설정, 체인 코드). 재공유에서 키쉐어가 없는 새 파티에 전달합니다.
+  #[napi]
+  pub fn export_key_info(&self) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.export_key_info()) }
+
+  /// Envelope 인증을 켭니다. `secretKey`는 이 파티의 Ed25519 식별 키(32바이트 시드), `roster`는 파티 인덱스 순서의 64바이트 식별 공개키입니다.
+  /// 이후 보내는 Envelope에 서명하고, 서명이 없거나 `from_party`의 공개키와 맞지 않는 입력은 `INVALID_SIGNATURE`로 거부합니다.
+  /// P2P payload는 수신자의 X25519 공개키(식별 공개키 뒤 32바이트)로 암호화하며, 복호화할 수 없는 입력은 `UNDECRYPTABLE_PAYLOAD`로 거부합니다.
+  #[napi]
+  pub fn set_identity(&mut self, secret_key: napi::bindgen_prelude::Buffer, roster: Vec<napi::bindgen_prelude::Buffer>) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.set_identity(&secret_key, &roster)) }
+
+  /// 다음에 시작하는 프로토콜의 ExecutionId(32바이트)를 지정합니다. 한 번의 `start*()` 호출에만 쓰이며, 세션의 모든 파티가 같은 값을 써야 합니다.
+  /// 지정하지 않으면 세션/실행 ID, 프로토콜, 곡선, n, t, 참여 파티, 키 ID와 메시지 해시에서 유도합니다.
+  /// 같은 실행기에서 이미 쓴 ExecutionId로는 다시 시작할 수 없으므로, 같은 파라미터로 재시도할 때는 새 값을 지정해야 합니다.
+  #[napi]
+  pub fn set_execution_id(&mut self, id: napi::bindgen_prelude::Buffer) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.set_execution_id(&id)) }
+
+  /// 브로드캐스트 메시지마다 Reliability Check 라운드를 추가해 모든 파티가 같은 메시지를 받았는지 확인합니다 (cggmp24 `enforce_reliable_broadcast`).
+  /// 이후 시작하는 키 생성, aux gen, 키 갱신, 재공유, 서명과 프리사인에 적용되며, 설정하지 않으면 키 생성/aux gen은 끄고 서명/프리사인은 켭니다.
+  /// 세션의 모든 파티가 같은 값을 써야 합니다.
+  #[napi]
+  pub fn set_reliable_broadcast(&mut self, enabled: bool) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.set_reliable_broadcast(enabled)) }
+
+  #[napi]
+  pub fn set_signers(&mut self, json: String) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.set_signers(json)) }
+
+  /// `options.hashMode`로 메시지 해싱 방식을, `options.derivationPath`로 서명할 HD 자식 키를 지정합니다.
+  /// 모든 서명자가 같은 옵션을 지정해야 하며, 다른 옵션으로 보낸 메시지는 거부됩니다.
+  #[napi]
+  pub fn start_signing(&mut self, tx_hex: String, options: Option<SigningOptions>) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_signing(tx_hex, options)) }
+
+  #[napi]
+  pub fn start_presigning(&mut self) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.start_presigning()) }
+
+  /// 저장된 프리서명을 실행기에서 꺼내 내보냅니다. 내보낸 뒤에는 실행기에 남지 않습니다.
+  #[napi]
+  pub fn export_presignature(&mut self) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&mut *self.lock()?, ex => ex.export_presignature()) }
+
+  /// 같은 키쉐어와 세션/실행 ID로 만든 프리서명만 가져올 수 있으며, 이미 부분 서명에 사용한 프리서명은 거부됩니다.
+  #[napi]
+  pub fn import_presignature(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> { dispatch!(&mut *self.lock()?, ex => ex.import_presignature(data)) }
+
+  /// 저장된 프리서명으로 부분 서명을 발행합니다. 프리서명은 1회 사용 후 폐기됩니다.
+  /// 이후 다른 서명자의 부분 서명을 `step()`으로 전달하면 결합된 서명이 완성됩니다.
+  /// 먼저 도착해 보관된 부분 서명은 이 호출에서 함께 처리됩니다.
+  #[napi]
+  pub fn sign_with_presignature(&mut self, tx_hex: String, options: Option<SigningOptions>) -> Result<Vec<napi::bindgen_prelude::Buffer>> { dispatch!(&mut *self.lock()?, ex => ex.sign_with_presignature(tx_hex, options)) }
+
+  /// 다른 파티가 `step()`에서 반환한 Envelope를 그대로 입력받아 세션/실행 ID, 라운드, 수신자, payload를 검증한 뒤 상태 머신에 전달합니다.
+  /// 검증에 실패한 메시지는 건너뛰고 snapshot `errors`에 기록합니다.
+  #[napi]
+  pub fn step(&mut self, inputs: Vec<napi::bindgen_prelude::Buffer>) -> Result<Vec<napi::bindgen_prelude::Buffer>> { dispatch!(&mut *self.lock()?, ex => ex.step(&inputs)).map(|r| r.outgoing) }
+
+  /// `step()`과 동일하며, 거부된 메시지 목록(입력 인덱스, 발신자, 사유 코드)을 함께 반환합니다.
+  #[napi]
+  pub fn step_with_report(&mut self, inputs: Vec<napi::bindgen_prelude::Buffer>) -> Result<StepReport> { dispatch!(&mut *self.lock()?, ex => ex.step(&inputs)) }
+
+  /// `stepWithReport()`를 libuv 스레드 풀에서 실행합니다. 완료될 때까지 다른 메서드 호출은 실패합니다.
+  #[napi(ts_return_type = "Promise<StepReport>")]
+  pub fn step_async(&self, inputs: Vec<napi::bindgen_prelude::Buffer>) -> Result<AsyncTask<StepTask>> {
+    let inputs = inputs.iter().map(|b| b.to_vec()).collect();
+    Ok(AsyncTask::new(StepTask { executor: self.begin_task()?, inputs }))
+  }
+
+  /// `startAuxGen()`을 libuv 스레드 풀에서 실행합니다 (안전 소수 생성 포함).
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn start_aux_gen_async(&self) -> Result<AsyncTask<StartAuxGenTask>> {
+    Ok(AsyncTask::new(StartAuxGenTask { executor: self.begin_task()? }))
+  }
+
+  #[napi]
+  pub fn snapshot(&self) -> Result<String> { dispatch!(&*self.lock()?, ex => ex.snapshot()) }
+
+  /// 마지막 서명을 `format`("der", "compact", "ethereum", "bitcoin")으로 인코딩해 반환합니다.
+  /// 반환 전에 서명한 공개키(파생 경로가 있으면 자식 공개키)로 서명을 다시 검증합니다.
+  #[napi]
+  pub fn get_signature(&self, format: String) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.get_signature(&format)) }
+
+  /// 키쉐어의 체인 코드로 자식 공개키(압축, hex)를 파생합니다. 경로는 "m/0/5" 형식이며 강화 인덱스는 지원하지 않습니다.
+  #[napi]
+  pub fn derive_public_key(&self, path: String) -> Result<String> { dispatch!(&*self.lock()?, ex => ex.derive_public_key(&path)) }
+
+  /// 키쉐어에 cggmp24 검증(공개 쉐어, VSS, Paillier/Pedersen 파라미터, core와 aux 일치)을 다시 실행하고
+  /// 실행기의 파티 인덱스, 임계값, 파티 수와 비교한 결과를 반환합니다. 키쉐어가 없으면 예외가 발생합니다.
+  #[napi]
+  pub fn validate_keyshare(&self) -> Result<KeyshareReport> { dispatch!(&*self.lock()?, ex => ex.validate_keyshare()) }
+
+  /// 키쉐어를 바이너리 형식(`CGMB` 헤더 + CBOR)으로 내보냅니다. `importKeyshare()`가 형식을 자동 판별합니다.
+  #[napi]
+  pub fn export_keyshare_bin(&self) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.export_keyshare_bin()) }
+
+  /// aux info를 바이너리 형식으로 내보냅니다. `importAuxInfo()`가 형식을 자동 판별합니다.
+  #[napi]
+  pub fn export_aux_info_bin(&self) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.export_aux_info_bin()) }
+
+  /// 진행 중인 세션을 재시작 후 이어가기 위한 상태를 직렬화합니다. 키쉐어와 RNG 시드가 포함되므로 암호화해서 보관해야 합니다.
+  /// `setIdentity()`의 식별 키는 포함되지 않으므로 `restore()`에 다시 전달해야 합니다.
+  #[napi]
+  pub fn serialize_state(&self) -> Result<napi::bindgen_prelude::Buffer> { dispatch!(&*self.lock()?, ex => ex.serialize_state()) }
+
+  /// `serializeState()` 결과로 실행기를 복원합니다. 저장 시점까지의 호출을 재실행해 같은 상태와 발신 메시지를 재현합니다.
+  /// `setIdentity()`를 호출한 실행기는 같은 식별 키를 `identityKey`로 전달해야 합니다.
+  #[napi(factory)]
+  pub fn restore(data: napi::bindgen_prelude::Buffer, identity_key: Option<napi::bindgen_prelude::Buffer>) -> Result<Self> {
+    let state = SavedState::decode(&data).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    let identity_key = identity_key.as_deref();
+    let inner = match state.curve.as_str() {
+      "secp256k1" => CurveExecutor::Secp256k1(Executor::restore(state, identity_key)?),
+      "secp256r1" => CurveExecutor::Secp256r1(Executor::restore(state, identity_key)?),
+      "stark" => CurveExecutor::Stark(Executor::restore(state, identity_key)?),
+      other => return Err(Error::new(Status::InvalidArg, format!("unsupported curve: {other}"))),
+    };
+    Ok(Self { inner: Arc::new(Mutex::new(inner)), busy: Arc::new(AtomicBool::new(false)) })
+  }
+
+  /// 현재 프로토콜을 시작한 뒤 보낸 Envelope. 복원 후 메시지를 받지 못한 파티에 다시 보낼 때 사용합니다.
+  #[napi]
+  pub fn sent_envelopes(&self) -> Result<Vec<napi::bindgen_prelude::Buffer>> { dispatch!(&*self.lock()?, ex => Ok(ex.sent_envelopes())) }
+}
+
+impl CggmpExecutor {
+  fn lock(&self) -> Result<MutexGuard<'_, CurveExecutor>> {
+    if self.busy.load(Ordering::Acquire) {
+      return Err(Error::new(Status::GenericFailure, "executor is busy: an async task is running"));
+    }
+    self.inner.lock().map_err(|_| Error::new(Status::GenericFailure, "executor poisoned by a failed async task"))
+  }
+
+  /// 비동기 작업 시작: 작업이 끝날 때까지(`finally`) 실행기를 잠금
+  fn begin_task(&self) -> Result<TaskExecutor> {
+    if self.busy.swap(true, Ordering::AcqRel) {
+      return Err(Error::new(Status::GenericFailure, "executor is busy: an async task is running"));
+    }
+    Ok(TaskExecutor { inner: self.inner.clone(), busy: self.busy.clone() })
+  }
+}
+
+/// 비동기 작업이 잡고 있는 실행기
+pub struct TaskExecutor {
+  inner: Arc<Mutex<CurveExecutor>>,
+  busy: Arc<AtomicBool>,
+}
+
+impl TaskExecutor {
+  fn lock(&self) -> Result<MutexGuard<'_, CurveExecutor>> {
+    self.inner.lock().map_err(|_| Error::new(Status::GenericFailure, "executor poisoned by a failed async task"))
+  }
+
+  fn release(&self) {
+    self.busy.store(false, Ordering::Release);
+  }
+}
+
+pub struct StepTask {
+  executor: TaskExecutor,
+  inputs: Vec<Vec<u8>>,
+}
+
+impl Task for StepTask {
+  type Output = StepReport;
+  type JsValue = StepReport;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    dispatch!(&mut *self.executor.lock()?, ex => ex.step(&self.inputs))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> { Ok(output) }
+
+  fn finally(&mut self, _env: Env) -> Result<()> { self.executor.release(); Ok(()) }
+}
+
+pub struct StartAuxGenTask {
+  executor: TaskExecutor,
+}
+
+impl Task for StartAuxGenTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    dispatch!(&mut *self.executor.lock()?, ex => ex.start_aux_gen())
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> { Ok(output) }
+
+  fn finally(&mut self, _env: Env) -> Result<()> { self.executor.release(); Ok(()) }
+}
+
+pub struct GeneratePrimesTask {
+  seed: Option<Vec<u8>>,
+}
+
+impl Task for GeneratePrimesTask {
+  type Output = Vec<u8>;
+  type JsValue = napi::bindgen_prelude::Buffer;
+
+  fn compute(&mut self) -> Result<Self::Output> { generate_primes_bytes(self.seed.as_deref().map(test_seed).transpose()?) }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> { Ok(output.into()) }
+}
+
+impl<E: SupportedCurve> Executor<E>
+where
+  Point<E>: HasAffineX<E>,
+  NonZero<Point<E>>: AlwaysHasAffineX<E>,
+{
+  fn new(session_id: String, execution_id: String, party_index: u16, threshold: u16, parties_count: u16) -> Result<Self> {
+    if session_id.is_empty() || execution_id.is_empty() {
+      return Err(Error::new(Status::InvalidArg, "session_id and execution_id are required"));
+    }
+    let seed = fresh_seed();
+    let mut ex = Self {
+      session_id, execution_id, party_index, threshold, parties_count,
+      signers_at_keygen: None, rng: Box::new(UnsafeRng::from_seed(seed)), state: ProtocolState::None,
+      core_keyshare: None, aux_info: None, keyshare: None, pending_keyshare: None, processed: 0,
+      phase: "INIT".to_string(), round: 0, errors: Vec::new(), last_round: None,
+      internal_round: "Init".to_string(),
+      status: "init".to_string(), last_signature: None, presignature: None, used_presignatures: Vec::new(), used_execution_ids: Vec::new(), early_partials: Vec::new(),
+      meta_sent: false,
+      abort: None, identity: None, reliable_broadcast: None, next_execution_id: None, seed_source: None, seal_rng: None,
+      checkpoint: Vec::new(), journal: Vec::new(), sent: Vec::new(), replaying: false,
+    };
+    ex.checkpoint = ex.make_checkpoint(seed)?;
+    Ok(ex)
+  }
+
+  fn export_keyshare(&self) -> Result<napi::bindgen_prelude::Buffer> {
+    let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare not ready"))?;
+    let buf = serde_json::to_vec(ks).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn export_aux_info(&self) -> Result<napi::bindgen_prelude::Buffer> {
+    let aux = self.aux_info.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "aux info not ready"))?;
+    let buf = serde_json::to_vec(aux).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn export_keyshare_encrypted(&self, secret: &Secret) -> Result<napi::bindgen_prelude::Buffer> {
+    let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare not ready"))?;
+    let plain = Zeroizing::new(serde_json::to_vec(ks).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?);
+    let public_key = ks.core.shared_public_key.to_bytes(true);
+    let buf = sealed::seal(Content::KeyShare, E::PROTO as u8, &public_key, secret, &plain).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn export_aux_info_encrypted(&self, secret: &Secret) -> Result<napi::bindgen_prelude::Buffer> {
+    let aux = self.aux_info.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "aux info not ready"))?;
+    let plain = Zeroizing::new(serde_json::to_vec(aux).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?);
+    let buf = sealed::seal(Content::AuxInfo, proto::Curve::Unspecified as u8, &[], secret, &plain).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn import_keyshare_encrypted(&mut self, data: napi::bindgen_prelude::Buffer, secret: &Secret) -> Result<()> {
+    let plain = open_sealed(&data, secret, Content::KeyShare, Some(E::PROTO))?;
+    self.import_keyshare(plain.to_vec().into())
+  }
+
+  fn import_aux_info_encrypted(&mut self, data: napi::bindgen_prelude::Buffer, secret: &Secret) -> Result<()> {
+    let plain = open_sealed(&data, secret, Content::AuxInfo, None)?;
+    self.import_aux_info(plain.to_vec().into())
+  }
+
+  fn import_keyshare(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> {
+    let ks: KeyShareWithLevel<E> = parse_key_export(&data, Content::KeyShare, Some(E::PROTO))?;
+    self.check_keyshare_params(ks.core.i, ks.core.public_shares.len(), ks.min_signers())?;
+    self.keyshare = Some(ks);
+    self.status = "keyshare_ready".to_string();
+    self.journal.push(JournalEntry::ImportKeyshare(data.to_vec()));
+    Ok(())
+  }
+
+  fn import_aux_info(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> {
+    let aux: AuxInfoMsg = parse_key_export(&data, Content::AuxInfo, None)?;
+    if aux.N.len() != usize::from(self.parties_count) {
+      return Err(Error::new(Status::InvalidArg, format!("aux info does not match executor: parties count is {}, expected {}", aux.N.len(), self.parties_count)));
+    }
+    // 가져온 키쉐어가 있으면 그 core와 결합 (기존 aux를 교체)
+    let core = match (&self.core_keyshare, &self.keyshare) {
+      (Some(core), _) => Some(core.clone()),
+      (None, Some(ks)) => Some(ks.core.clone().validate().map_err(|e| Error::new(Status::InvalidArg, format!("invalid key share: {}", e.error())))?),
+      (None, None) => None,
+    };
+    if let Some(core) = core {
+      let ks = KeyShare::from_parts((core, aux.clone()))
+        .map_err(|e| Error::new(Status::InvalidArg, format!("aux info does not match key share: {}", e.error())))?;
+      self.keyshare = Some(ks); self.status = "keyshare_ready".to_string();
+    }
+    self.aux_info = Some(aux);
+    self.journal.push(JournalEntry::ImportAuxInfo(data.to_vec()));
+    Ok(())
+  }
+
+  fn discard_keyshare(&mut self) -> Result<()> {
+    if !matches!(self.state, ProtocolState::None) {
+      return Err(Error::new(Status::GenericFailure, "cannot discard the key share while a protocol is running"));
+    }
+    self.core_keyshare = None; self.aux_info = None; self.keyshare = None; self.pending_keyshare = None; self.presignature = None;
+    self.status = "init".to_string();
+    self.journal.push(JournalEntry::DiscardKeyshare);
+    Ok(())
+  }
+
+  fn commit_key_refresh(&mut self) -> Result<()> {
+    if !matches!(self.state, ProtocolState::None) {
+      return Err(Error::new(Status::GenericFailure, "cannot commit the refreshed key share while a protocol is running"));
+    }
+    let ks = self.pending_keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "no refreshed key share to commit"))?;
+    let core = ks.core.clone().validate().map_err(|e| Error::new(Status::GenericFailure, format!("invalid key share: {}", e.error())))?;
+    let aux = ks.aux.clone().validate().map_err(|e| Error::new(Status::GenericFailure, format!("invalid aux info: {}", e.error())))?;
+    self.core_keyshare = Some(core); self.aux_info = Some(aux); self.keyshare = self.pending_keyshare.take();
+    self.presignature = None; // 이전 쉐어로 만든 프리서명은 사용할 수 없음
+    self.status = "keyshare_ready".to_string();
+    self.journal.push(JournalEntry::CommitKeyRefresh);
+    Ok(())
+  }
+
+  fn start_keygen(&mut self) -> Result<()> {
+    self.start_run(JournalEntry::StartKeygen, |ex| {
+      let everyone: Vec<u16> = (0..ex.parties_count).collect();
+      let eid = ex.execution_id(&EidTranscript { phase: "keygen", parties: &everyone, public_key: &[], message: &[] })?;
+      let builder = KeygenBuilder::<E>::new(ExecutionId::new(extend_ref(&*eid)), ex.party_index, ex.parties_count).set_threshold(ex.threshold).enforce_reliable_broadcast(ex.reliable_broadcast.unwrap_or(false));
+      ex.state = ProtocolState::Keygen { sm: Box::new(builder.into_state_machine(extend_mut(&mut ex.rng))), pending: Vec::new(), _eid: eid };
+      ex.phase = "KEYGEN".to_string(); ex.status = "running".to_string(); ex.round = Round::Keygen as u32; ex.last_round = Some(Round::Keygen);
+      ex.internal_round = "Round 1 (Commitment)".to_string(); // Initial round
+      Ok(())
+    })
+  }
+
+  fn start_aux_gen(&mut self) -> Result<()> {
+    self.start_run(JournalEntry::StartAuxGen, |ex| {
+      let everyone: Vec<u16> = (0..ex.parties_count).collect();
+      let eid = ex.execution_id(&EidTranscript { phase: "aux_gen", parties: &everyone, public_key: &[], message: &[] })?;
+      let rng = extend_mut(&mut ex.rng);
+      let primes: cggmp24::PregeneratedPrimes<SecLevel> = cggmp24::PregeneratedPrimes::generate(rng);
+      let builder = cggmp24::aux_info_gen(ExecutionId::new(extend_ref(&*eid)), ex.party_index, ex.parties_count, primes).enforce_reliable_broadcast(ex.reliable_broadcast.unwrap_or(false));
+      ex.state = ProtocolState::AuxGen { sm: Box::new(builder.into_state_machine(rng)), pending: Vec::new(), _eid: eid };
+      ex.phase = "AUX_GEN".to_string(); ex.status = "running".to_string(); ex.round = Round::AuxInfo as u32; ex.last_round = Some(Round::AuxInfo);
+      ex.internal_round = "Round 1 (Paillier Gen)".to_string(); // Initial round
+      Ok(())
+    })
+  }
+
+  fn start_aux_gen_with_primes(&mut self, primes_buf: napi::bindgen_prelude::Buffer) -> Result<()> {
+    self.start_run(JournalEntry::StartAuxGenWithPrimes(primes_buf.to_vec()), |ex| {
+      let everyone: Vec<u16> = (0..ex.parties_count).collect();
+      let eid = ex.execution_id(&EidTranscript { phase: "aux_gen", parties: &everyone, public_key: &[], message: &[] })?;
+      let primes: cggmp24::PregeneratedPrimes<SecLevel> = bincode::deserialize(&primes_buf).map_err(|e| Error::new(Status::InvalidArg, format!("invalid primes: {e}")))?;
+      let rng = extend_mut(&mut ex.rng);
+      let builder = cggmp24::aux_info_gen(ExecutionId::new(extend_ref(&*eid)), ex.party_index, ex.parties_count, primes).enforce_reliable_broadcast(ex.reliable_broadcast.unwrap_or(false));
+      ex.state = ProtocolState::AuxGen { sm: Box::new(builder.into_state_machine(rng)), pending: Vec::new(), _eid: eid };
+      ex.phase = "AUX_GEN".to_string(); ex.status = "running".to_string(); ex.round = Round::AuxInfo as u32; ex.last_round = Some(Round::AuxInfo);
+      ex.internal_round = "Round 1 (Paillier Gen)".to_string(); // Initial round
+      Ok(())
+    })
+  }
+
+  fn start_key_refresh(&mut self) -> Result<()> {
+    self.start_run(JournalEntry::StartKeyRefresh, |ex| {
+      ex.check_refresh_keyshare()?;
+      let primes: cggmp24::PregeneratedPrimes<SecLevel> = cggmp24::PregeneratedPrimes::generate(extend_mut(&mut ex.rng));
+      ex.start_key_refresh_inner(primes)
+    })
+  }
+
+  fn start_key_refresh_with_primes(&mut self, primes_buf: napi::bindgen_prelude::Buffer) -> Result<()> {
+    self.start_run(JournalEntry::StartKeyRefreshWithPrimes(primes_buf.to_vec()), |ex| {
+      let primes: cggmp24::PregeneratedPrimes<SecLevel> = bincode::deserialize(&primes_buf).map_err(|e| Error::new(Status::InvalidArg, format!("invalid primes: {e}")))?;
+      ex.start_key_refresh_inner(primes)
+    })
+  }
+
+  fn start_reshare(&mut self, request: ReshareRequest) -> Result<()> {
+    self.start_run(JournalEntry::StartReshare(request.clone()), |ex| ex.start_reshare_inner(&request))
+  }
+
+  fn export_key_info(&self) -> Result<napi::bindgen_prelude::Buffer> {
+    let key_info = match (&self.keyshare, &self.core_keyshare) {
+      (Some(ks), _) => &ks.core.key_info,
+      (None, Some(core)) => &core.key_info,
+      (None, None) => return Err(Error::new(Status::InvalidArg, "keyshare missing")),
+    };
+    let buf = serde_json::to_vec(key_info).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn set_identity<K: AsRef<[u8]>>(&mut self, secret_key: &[u8], roster: &[K]) -> Result<()> {
+    if roster.len() != usize::from(self.parties_count) {
+      return Err(Error::new(Status::InvalidArg, format!("expected {} identity public keys, got {}", self.parties_count, roster.len())));
+    }
+    let identity = Identity::new(secret_key, roster, self.party_index).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    // 식별 키는 기록하지 않으며 복원 시 다시 받음
+    self.journal.push(JournalEntry::SetIdentity(identity.roster()));
+    self.identity = Some(identity);
+    Ok(())
+  }
+
+  fn set_execution_id(&mut self, id: &[u8]) -> Result<()> {
+    let id: [u8; 32] = id.try_into().map_err(|_| Error::new(Status::InvalidArg, format!("execution id must be 32 bytes, got {}", id.len())))?;
+    self.next_execution_id = Some(id);
+    self.journal.push(JournalEntry::SetExecutionId(id.to_vec()));
+    Ok(())
+  }
+
+  /// 다음 프로토콜의 ExecutionId. `setExecutionId()` 값이 있으면 그대로 쓰고, 없으면 세션/실행 ID와 프로토콜 파라미터를 해시
+  /// (같은 파라미터로 다시 시도하면 유도한 ID도 같으므로 이미 쓴 ID는 거부)
+  fn execution_id(&mut self, transcript: &EidTranscript) -> Result<Box<[u8; 32]>> {
+    let eid = self.next_execution_id.unwrap_or_else(|| self.derive_execution_id(&self.execution_id, transcript));
+    if self.used_execution_ids.contains(&eid) {
+      return Err(Error::new(Status::InvalidArg, "execution id was already used by this executor: set a fresh one with setExecutionId() before retrying"));
+    }
+    self.used_execution_ids.push(eid);
+    Ok(Box::new(eid))
+  }
+
+  /// 세션 ID, `execution_id`와 프로토콜 파라미터에서 유도한 ExecutionId
+  fn derive_execution_id(&self, execution_id: &str, transcript: &EidTranscript) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(EXECUTION_ID_DOMAIN);
+    for field in [self.session_id.as_bytes(), execution_id.as_bytes(), transcript.phase.as_bytes(), E::CURVE_NAME.as_bytes()] {
+      hash_field(&mut h, field);
+    }
+    h.update(self.parties_count.to_be_bytes());
+    h.update(self.threshold.to_be_bytes());
+    hash_field(&mut h, &transcript.parties.iter().flat_map(|p| p.to_be_bytes()).collect::<Vec<u8>>());
+    hash_field(&mut h, &sealed::key_id(transcript.public_key));
+    hash_field(&mut h, transcript.message);
+    h.finalize().into()
+  }
+
+  fn set_reliable_broadcast(&mut self, enabled: bool) -> Result<()> {
+    self.reliable_broadcast = Some(enabled);
+    self.journal.push(JournalEntry::SetReliableBroadcast(enabled));
+    Ok(())
+  }
+
+  fn set_signers(&mut self, json: String) -> Result<()> {
+    let parsed: Vec<u16> = serde_json::from_str(&json).map_err(|e| Error::new(Status::InvalidArg, format!("invalid json: {e}")))?;
+    self.signers_at_keygen = Some(parsed);
+    self.journal.push(JournalEntry::SetSigners(json));
+    Ok(())
+  }
+
+  fn start_signing(&mut self, tx_hex: String, options: Option<SigningOptions>) -> Result<()> {
+    let entry = match &options {
+      Some(options) => JournalEntry::StartSigningWithOptions(tx_hex.clone(), options.clone()),
+      None => JournalEntry::StartSigning(tx_hex.clone()),
+    };
+    let options = options.unwrap_or_default();
+    self.start_run(entry, |ex| {
+      let path = parse_derivation_path(options.derivation_path.as_deref().unwrap_or(""))?;
+      let hash_mode = HashMode::parse(options.hash_mode.as_deref()).map_err(|e| Error::new(Status::InvalidArg, e))?;
+      let ks = ex.keyshare.clone().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare missing"))?;
+      let tx = hex::decode(tx_hex).map_err(|e| Error::new(Status::InvalidArg, format!("invalid hex: {e}")))?;
+      let data = E::data_to_sign(&tx, hash_mode)?;
+      let min = ks.min_signers();
+      let selected = ex.signers_at_keygen.clone().unwrap_or_else(|| (0..min).collect());
+      let ks_boxed = Box::new(ks);
+      let my_idx = selected.iter().position(|&p| p == ex.party_index).ok_or_else(|| Error::new(Status::InvalidArg, "not in signers"))? as u16;
+      let public_key = if path.is_empty() { *ks_boxed.core.shared_public_key } else { ks_boxed.core.derive_child_public_key::<E::Hd, _>(path.iter().copied()).map_err(hd_error)?.public_key };
+      let eid = ex.execution_id(&EidTranscript { phase: "signing", parties: &selected, public_key: &public_key.to_bytes(true), message: &data.to_scalar().to_be_bytes() })?;
+      let mut signing = cggmp24::signing(ExecutionId::new(extend_ref(&*eid)), my_idx, extend_ref(selected.as_slice()), extend_ref(&*ks_boxed)).enforce_reliable_broadcast(ex.reliable_broadcast.unwrap_or(true));
+      if !path.is_empty() {
+        signing = signing.set_derivation_path_with_algo::<E::Hd, _>(path.iter().copied()).map_err(hd_error)?;
+      }
+      let sm = signing.sign_sync(extend_mut(&mut ex.rng), extend_ref(&*data));
+      ex.state = ProtocolState::Signing { sm: Box::new(sm), pending: Vec::new(), tx_context: tx, derivation_path: path, hash_mode, public_key, _data: data, _keyshare: ks_boxed, _signers: selected, _eid: eid };
+      ex.phase = "SIGNING".to_string(); ex.status = "running".to_string(); ex.round = Round::Signing as u32; ex.last_round = Some(Round::Signing);
+      ex.internal_round = "Round 1 (Partial Sign)".to_string(); // Initial round
+      Ok(())
+    })
+  }
+
+  fn start_presigning(&mut self) -> Result<()> {
+    self.start_run(JournalEntry::StartPresigning, |ex| {
+      if !E::PRESIGNING {
+        return Err(Error::new(Status::InvalidArg, format!("presigning is not supported on the {} curve", E::CURVE_NAME)));
+      }
+      let ks = ex.keyshare.clone().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare missing"))?;
+      let min = ks.min_signers();
+      let selected = ex.signers_at_keygen.clone().unwrap_or_else(|| (0..min).collect());
+      let eid = ex.execution_id(&EidTranscript { phase: "presigning", parties: &selected, public_key: &ks.core.shared_public_key.to_bytes(true), message: &[] })?;
+      let derived_from = ex.next_execution_id.is_none().then(|| ex.execution_id.clone());
+      let ks_boxed = Box::new(ks);
+      let my_idx = selected.iter().position(|&p| p == ex.party_index).ok_or_else(|| Error::new(Status::InvalidArg, "not in signers"))? as u16;
+      let sm = cggmp24::signing(ExecutionId::new(extend_ref(&*eid)), my_idx, extend_ref(selected.as_slice()), extend_ref(&*ks_boxed)).enforce_reliable_broadcast(ex.reliable_broadcast.unwrap_or(true)).generate_presignature_sync(extend_mut(&mut ex.rng));
+      ex.state = ProtocolState::Presigning { sm: Box::new(sm), pending: Vec::new(), _keyshare: ks_boxed, _signers: selected, _eid: eid, derived_from };
+      ex.phase = "PRESIGNING".to_string(); ex.status = "running".to_string(); ex.round = Round::Presignature as u32; ex.last_round = Some(Round::Presignature);
+      ex.internal_round = "Round 1 (Commitment)".to_string(); // Initial round
+      Ok(())
+    })
+  }
+
+  /// 내보낸 프리서명이 실행기에 남아 있으면 가져온 사본과 함께 두 번 쓰일 수 있으므로 꺼내서 내보냄
+  fn export_presignature(&mut self) -> Result<napi::bindgen_prelude::Buffer> {
+    let presig = self.presignature.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "presignature not ready"))?;
+    let buf = serde_json::to_vec(presig).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
+    self.presignature = None;
+    self.early_partials.clear();
+    self.journal.push(JournalEntry::ExportPresignature);
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn import_presignature(&mut self, data: napi::bindgen_prelude::Buffer) -> Result<()> {
+    let presig: StoredPresignature<E> = serde_json::from_slice(&data).map_err(|e| Error::new(Status::InvalidArg, format!("parse json: {e}")))?;
+    if !presig.signers.contains(&self.party_index) {
+      return Err(Error::new(Status::InvalidArg, "not in signers"));
+    }
+    if presig.commitments.len() != presig.signers.len() {
+      return Err(Error::new(Status::InvalidArg, "presignature commitments do not match signers"));
+    }
+    let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare missing"))?;
+    if presig.public_key != *ks.core.shared_public_key {
+      return Err(Error::new(Status::InvalidArg, "presignature was generated for a different key"));
+    }
+    if presig.session_id != self.session_id {
+      return Err(Error::new(Status::InvalidArg, "presignature was generated in a different session"));
+    }
+    // 유도한 ExecutionId는 기록된 실행 ID로 다시 유도해 확인 (`setExecutionId()`로 지정한 값은 그대로 사용)
+    if let Some(execution_id) = &presig.derived_from {
+      let eid = self.derive_execution_id(execution_id, &EidTranscript { phase: "presigning", parties: &presig.signers, public_key: &presig.public_key.to_bytes(true), message: &[] });
+      if presig.execution_id != eid {
+        return Err(Error::new(Status::InvalidArg, "presignature was generated in a different execution"));
+      }
+    }
+    if self.used_presignatures.contains(&presig.id()) {
+      return Err(Error::new(Status::InvalidArg, "presignature was already used"));
+    }
+    self.presignature = Some(presig);
+    self.early_partials.clear();
+    self.journal.push(JournalEntry::ImportPresignature(data.to_vec()));
+    Ok(())
+  }
+
+  fn sign_with_presignature(&mut self, tx_hex: String, options: Option<SigningOptions>) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
+    let entry = match &options {
+      Some(options) => JournalEntry::SignWithPresignatureWithOptions(tx_hex.clone(), options.clone()),
+      None => JournalEntry::SignWithPresignature(tx_hex.clone()),
+    };
+    let options = options.unwrap_or_default();
+    self.start_run(entry, |ex| {
+      if options.derivation_path.is_some() {
+        return Err(Error::new(Status::InvalidArg, "derivation path is not supported with presignatures"));
+      }
+      let hash_mode = HashMode::parse(options.hash_mode.as_deref()).map_err(|e| Error::new(Status::InvalidArg, e))?;
+      if ex.keyshare.is_none() { return Err(Error::new(Status::InvalidArg, "keyshare missing")); }
+      let tx = hex::decode(tx_hex).map_err(|e| Error::new(Status::InvalidArg, format!("invalid hex: {e}")))?;
+      let data = E::presign_data_to_sign(&tx, hash_mode)?;
+      let stored = ex.presignature.take().ok_or_else(|| Error::new(Status::InvalidArg, "presignature missing"))?;
+      let my_pos = stored.signers.iter().position(|&p| p == ex.party_index).ok_or_else(|| Error::new(Status::InvalidArg, "not in signers"))?;
+      let id = stored.id();
+      if ex.used_presignatures.contains(&id) {
+        return Err(Error::new(Status::InvalidArg, "presignature was already used"));
+      }
+      ex.used_presignatures.push(id);
+      let public_data = stored.public_data();
+      let signers = stored.signers.clone();
+      let partial = stored.presignature.issue_partial_signature(data);
+
+      let mut partials = vec![None; signers.len()];
+      partials[my_pos] = Some(partial);
+      let ctx = EnvelopeCtx {
+        session_id: &ex.session_id, execution_id: &ex.execution_id, curve: E::PROTO, round: Round::Signing,
+        party_index: ex.party_index, threshold: ex.threshold, parties_count: ex.parties_count, tx_context: &tx, derivation_path: &[], hash_mode: hash_mode.name(), signers: &[],
+      };
+      let mut env = make_envelope(&ctx, &[], encode_msg(&partial)?, ex.meta_sent);
+      ex.meta_sent = true;
+      ex.state = ProtocolState::PartialSigning { partials, public_data: Box::new(public_data), data, signers, tx_context: tx.clone(), hash_mode };
+      ex.phase = "SIGNING".to_string(); ex.status = "running".to_string(); ex.round = Round::Signing as u32; ex.last_round = Some(Round::Signing);
+      ex.internal_round = "Partial Signature".to_string();
+      let mut out = ex.encode_outgoing(std::slice::from_mut(&mut env))?;
+      ex.sent.extend(out.iter().map(|b| b.to_vec()));
+      // 먼저 도착해 보관한 부분 서명은 이제 tx_context와 함께 검증
+      let early: Vec<Vec<u8>> = std::mem::take(&mut ex.early_partials).into_iter().map(|(_, buf)| buf).collect();
+      if !early.is_empty() {
+        out.extend(ex.process(&early)?.outgoing);
+      }
+      Ok(out)
+    })
+  }
+
+  fn step<B: AsRef<[u8]>>(&mut self, inputs: &[B]) -> Result<StepReport> {
+    self.processed += inputs.len();
+    let result = self.process(inputs);
+    self.journal.push(JournalEntry::Step(inputs.iter().map(|b| b.as_ref().to_vec()).collect()));
+    result
+  }
+
+  /// 입력 Envelope를 검증해 현재 프로토콜에 넘기고 발신 Envelope를 만듦 (저널 기록은 호출자가 담당)
+  fn process<B: AsRef<[u8]>>(&mut self, inputs: &[B]) -> Result<StepReport> {
+    let mut out = StepOutput::default();
+
+    // 1. Envelope 검증 후 payload 추출 (NAPI Buffer는 스레드 이동 불가하므로 먼저 복사)
+    let mut peer_abort = None;
+    let opened: Vec<OpenedEnvelope> = inputs.iter().enumerate().filter_map(|(index, b)| {
+      match self.open_envelope(index, b.as_ref()) {
+        Ok(Opened::Msg(env)) => Some(env),
+        Ok(Opened::PeerAbort(abort)) => { peer_abort.get_or_insert(abort); None }
+        Err(r) => { out.rejected.push(r); None }
+      }
+    }).collect();
+    // 다른 파티가 중단을 알리면 더 이상 진행할 수 없음
+    if let Some(abort) = peer_abort {
+      self.abort_protocol(abort, &mut out);
+    }
+
+    // 2. Phase 5: Rayon을 사용한 병렬 역직렬화
+    match &mut self.state {
+      ProtocolState::Keygen { pending, .. } => pending.extend(decode_incoming(&opened, &mut out.rejected)),
+      ProtocolState::AuxGen { pending, .. } => pending.extend(decode_incoming(&opened, &mut out.rejected)),
+      ProtocolState::Signing { pending, .. } => pending.extend(decode_incoming(&opened, &mut out.rejected)),
+      ProtocolState::Presigning { pending, .. } => pending.extend(decode_incoming(&opened, &mut out.rejected)),
+      ProtocolState::KeyRefresh { reshare_pending, aux_pending, .. } => {
+        for msg in decode_incoming::<KeyRefreshMsg<E>>(&opened, &mut out.rejected) {
+          match msg.msg { KeyRefreshMsg::Reshare(_) => reshare_pending.push(msg), KeyRefreshMsg::Aux(_) => aux_pending.push(msg) }
+        }
+      }
+      ProtocolState::Reshare { aux_sm, reshare_pending, aux_pending, committee, .. } => {
+        for mut msg in decode_incoming::<KeyRefreshMsg<E>>(&opened, &mut out.rejected) {
+          match msg.msg {
+            KeyRefreshMsg::Reshare(_) => reshare_pending.push(msg),
+            // 새 위원회가 아닌 파티는 aux 메시지를 처리하지 않음
+            KeyRefreshMsg::Aux(_) if aux_sm.is_none() => {}
+            KeyRefreshMsg::Aux(_) => match committee.iter().position(|&p| p == msg.sender) {
+              Some(local) => { msg.sender = local as u16; aux_pending.push(msg) }
+              None => out.rejected.push(RejectReason::SenderNotSigner.reject(msg.id as usize, Some(msg.sender), format!("sender {} not in new committee", msg.sender))),
+            },
+          }
+        }
+      }
+      ProtocolState::PartialSigning { partials, signers, .. } => {
+        for msg in decode_incoming::<PartialSignature<E>>(&opened, &mut out.rejected) {
+          let slot = &mut partials[usize::from(msg.sender)];
+          if slot.is_some() {
+            let from = signers[usize::from(msg.sender)];
+            out.rejected.push(RejectReason::DuplicateMessage.reject(msg.id as usize, Some(from), format!("duplicate partial signature from party {from}")));
+            continue;
+          }
+          *slot = Some(msg.msg);
+        }
+      }
+      // 보관한 프리서명의 부분 서명만 열리므로 `signWithPresignature()`까지 보관
+      ProtocolState::None => {
+        for env in opened {
+          if self.early_partials.iter().any(|(from, _)| *from == env.from_party) {
+            out.rejected.push(RejectReason::DuplicateMessage.reject(env.index, Some(env.from_party), format!("duplicate partial signature from party {}", env.from_party)));
+            continue;
+          }
+          self.early_partials.push((env.from_party, inputs[env.index].as_ref().to_vec()));
+        }
+      }
+    }
+
+    // 3. 상태 머신 구동 (메시지 소진 시까지 반복)
+    let result = self.drive(&mut out);
+    self.errors.extend(out.rejected.iter().cloned());
+    result?;
+    let outgoing = self.encode_outgoing(&mut out.outgoing)?;
+    self.sent.extend(outgoing.iter().map(|b| b.to_vec()));
+    Ok(StepReport { outgoing, rejected: out.rejected, abort: out.abort })
+  }
+
+  /// 프로토콜 중단 처리: 상태를 정리하고, 직접 감지한 중단이면 다른 파티에 Error Envelope 전송
+  fn abort_protocol(&mut self, abort: ProtocolAbort, step_out: &mut StepOutput) {
+    if abort.reported_by.is_none() {
+      let ctx = EnvelopeCtx {
+        session_id: &self.session_id, execution_id: &self.execution_id, curve: E::PROTO, round: Round::Error,
+        party_index: self.party_index, threshold: self.threshold, parties_count: self.parties_count, tx_context: &[], derivation_path: &[], hash_mode: "", signers: &[],
+      };
+      let mut env = make_envelope(&ctx, &[], Vec::new(), self.meta_sent);
+      env.payload = Some(Payload::Error(abort.to_proto()));
+      step_out.outgoing.push(env);
+      self.meta_sent = true;
+    }
+    self.status = format!("{}_aborted", abort.protocol);
+    self.state = ProtocolState::None;
+    self.internal_round = "Aborted".to_string();
+    step_out.abort = Some(abort.clone());
+    self.abort = Some(abort);
+  }
+
+  fn drive(&mut self, step_out: &mut StepOutput) -> Result<()> {
+    let base = EnvelopeCtx {
+      session_id: &self.session_id, execution_id: &self.execution_id, curve: E::PROTO, round: Round::Unspecified,
+      party_index: self.party_index, threshold: self.threshold, parties_count: self.parties_count, tx_context: &[], derivation_path: &[], hash_mode: "", signers: &[],
+    };
+    let mut abort = None;
+    match &mut self.state {
+      ProtocolState::Keygen { sm, pending, .. } => {
+        let out = drive_sm(sm.as_mut(), pending, &EnvelopeCtx { round: Round::Keygen, ..base }, step_out, &mut self.meta_sent, &mut self.internal_round, |msg| {
+            match msg {
+                keygen_msg::Msg::Round1(_) => "Round 1 (Commitment)".to_string(),
+                keygen_msg::Msg::Round2Broad(_) | keygen_msg::Msg::Round2Uni(_) => "Round 2 (VSS & Share)".to_string(),
+                keygen_msg::Msg::Round3(_) => "Round 3 (Verify & Proofs)".to_string(),
+                keygen_msg::Msg::ReliabilityCheck(_) => "Reliability Check".to_string(),
+            }
+        })?;
+        match out {
+          Some(Ok(core)) => {
+            self.core_keyshare = Some(core);
+            self.status = "keygen_finished".to_string(); self.state = ProtocolState::None; self.try_combine_shares();
+            self.internal_round = "Finished".to_string();
+          }
+          Some(Err(e)) => abort = Some(ProtocolAbort::from_debug("keygen", format!("{e:?}"), &[])),
+          None => {}
+        }
+      }
+      ProtocolState::AuxGen { sm, pending, .. } => {
+        let out = drive_sm(sm.as_mut(), pending, &EnvelopeCtx { round: Round::AuxInfo, ..base }, step_out, &mut self.meta_sent, &mut self.internal_round, |msg| {
+            match msg {
+                cggmp24::key_refresh::msg::Msg::Round1(_) => "Round 1 (Paillier Gen)".to_string(),
+                cggmp24::key_refresh::msg::Msg::Round2(_) => "Round 2 (ZKP Verify)".to_string(),
+                cggmp24::key_refresh::msg::Msg::Round3(_) => "Round 3 (Finalize)".to_string(),
+                cggmp24::key_refresh::msg::Msg::ReliabilityCheck(_) => "Reliability Check".to_string(),
+            }
+        })?;
+        match out {
+          Some(Ok(aux)) => {
+            self.aux_info = Some(aux);
+            self.status = "aux_gen_finished".to_string(); self.state = ProtocolState::None; self.try_combine_shares();
+            self.internal_round = "Finished".to_string();
+          }
+          Some(Err(e)) => abort = Some(ProtocolAbort::from_debug("aux_gen", format!("{e:?}"), &[])),
+          None => {}
+        }
+      }
+      ProtocolState::Signing { sm, pending, tx_context, derivation_path, hash_mode, public_key, _data, _signers, .. } => {
+        let hash_mode = hash_mode.name();
+        let out = drive_sm(sm.as_mut(), pending, &EnvelopeCtx { round: Round::Signing, tx_context, derivation_path, hash_mode, signers: _signers, ..base }, step_out, &mut self.meta_sent, &mut self.internal_round, |msg| {
+            match msg {
+                cggmp24::signing::msg::Msg::Round1a(_) | cggmp24::signing::msg::Msg::Round1b(_) => "Round 1 (Partial Sign)".to_string(),
+                cggmp24::signing::msg::Msg::Round2(_) => "Round 2 (Verify)".to_string(),
+                cggmp24::signing::msg::Msg::Round3(_) => "Round 3 (Combine)".to_string(),
+                cggmp24::signing::msg::Msg::Round4(_) => "Round 4 (Finalize)".to_string(),
+                cggmp24::signing::msg::Msg::ReliabilityCheck(_) => "Reliability Check".to_string(),
+            }
+        })?;
+        match out {
+          // 결합된 서명이 서명 공개키로 검증되지 않으면 내보내지 않고 중단
+          Some(Ok(sig)) if sig.verify(public_key, &**_data).is_err() => {
+            abort = Some(ProtocolAbort::new("signing", AbortKind::Aborted, "SignatureInvalid", Vec::new(), "combined signature does not verify against the public key".to_string()));
+          }
+          Some(Ok(sig)) => {
+            step_out.outgoing.push(make_envelope(&EnvelopeCtx { round: Round::Signing, tx_context, derivation_path, hash_mode, ..base }, &[], encode_msg(&sig)?, self.meta_sent));
+            self.meta_sent = true;
+            self.last_signature = Some(SignedMessage { signature: sig, public_key: *public_key, message: _data.to_scalar() });
+            self.status = "signing_finished".to_string(); self.state = ProtocolState::None;
+            self.internal_round = "Finished".to_string();
+          }
+          Some(Err(e)) => abort = Some(ProtocolAbort::from_debug("signing", format!("{e:?}"), _signers)),
+          None => {}
+        }
+      }
+      ProtocolState::Presigning { sm, pending, _keyshare, _signers, _eid, derived_from } => {
+        let out = drive_sm(sm.as_mut(), pending, &EnvelopeCtx { round: Round::Presignature, signers: _signers, ..base }, step_out, &mut self.meta_sent, &mut self.internal_round, |msg| {
+            match msg {
+                cggmp24::signing::msg::Msg::Round1a(_) | cggmp24::signing::msg::Msg::Round1b(_) => "Round 1 (Commitment)".to_string(),
+                cggmp24::signing::msg::Msg::Round2(_) => "Round 2 (MtA)".to_string(),
+                // 프리사인은 Round 3에서 끝나므로 Round 4 메시지를 보내지 않음
+                cggmp24::signing::msg::Msg::Round3(_) | cggmp24::signing::msg::Msg::Round4(_) => "Round 3 (Presignature)".to_string(),
+                cggmp24::signing::msg::Msg::ReliabilityCheck(_) => "Reliability Check".to_string(),
+            }
+        })?;
+        match out {
+          Some(Ok((presig, public_data))) => {
+            self.presignature = Some(StoredPresignature::new(_signers.clone(), presig, public_data, *_keyshare.core.shared_public_key, self.session_id.clone(), **_eid, derived_from.clone()));
+            self.early_partials.clear();
+            self.status = "presigning_finished".to_string(); self.state = ProtocolState::None;
+            self.internal_round = "Finished".to_string();
+          }
+          Some(Err(e)) => abort = Some(ProtocolAbort::from_debug("presigning", format!("{e:?}"), _signers)),
+          None => {}
+        }
+      }
+      ProtocolState::KeyRefresh { reshare_sm, aux_sm, reshare_pending, aux_pending, new_core, new_aux, .. } => {
+        let ctx = EnvelopeCtx { round: Round::KeyRefresh, ..base };
+        let round_name = key_refresh_round_name::<E>;
+        // 출력이 나온 상태 머신은 다시 구동하지 않음
+        if new_core.is_none() {
+          match drive_sm(reshare_sm.as_mut(), reshare_pending, &ctx, step_out, &mut self.meta_sent, &mut self.internal_round, round_name)? {
+            Some(Ok(core)) => *new_core = Some(core.ok_or_else(|| Error::new(Status::GenericFailure, "reshare produced no key share"))?),
+            Some(Err(e)) => abort = Some(ProtocolAbort::from_reshare("key_refresh", &e)),
+            None => {}
+          }
+        }
+        if new_aux.is_none() && abort.is_none() {
+          match drive_sm(aux_sm.as_mut(), aux_pending, &ctx, step_out, &mut self.meta_sent, &mut self.internal_round, round_name)? {
+            Some(Ok(aux)) => *new_aux = Some(Box::new(aux)),
+            Some(Err(e)) => abort = Some(ProtocolAbort::from_debug("key_refresh", format!("{e:?}"), &[])),
+            None => {}
+          }
+        }
+        // 이 파티가 끝났다고 다른 파티도 끝난 것은 아니므로 (확인 해시를 파티마다 다르게 보내거나 aux 생성이 일부에서만 중단)
+        // 새 쉐어는 따로 보관하고, 모든 파티가 끝났음을 애플리케이션이 확인한 뒤 `commitKeyRefresh()`로 교체
+        if let (Some(core), Some(aux)) = (new_core.as_ref(), new_aux.as_deref()) {
+          let ks = KeyShare::from_parts((core.clone(), aux.clone())).map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))?;
+          self.pending_keyshare = Some(ks);
+          self.status = "key_refresh_finished".to_string(); self.state = ProtocolState::None;
+          self.internal_round = "Finished".to_string();
+        }
+      }
+      ProtocolState::Reshare { reshare_sm, aux_sm, reshare_pending, aux_pending, committee, new_core, new_aux, .. } => {
+        let ctx = EnvelopeCtx { round: Round::KeyRefresh, ..base };
+        if new_core.is_none() {
+          match drive_sm(reshare_sm.as_mut(), reshare_pending, &ctx, step_out, &mut self.meta_sent, &mut self.internal_round, key_refresh_round_name::<E>)? {
+            Some(Ok(core)) => *new_core = Some(core),
+            Some(Err(e)) => abort = Some(ProtocolAbort::from_reshare("reshare", &e)),
+            None => {}
+          }
+        }
+        if let (Some(sm), None, None) = (aux_sm.as_mut(), new_aux.as_ref(), abort.as_ref()) {
+          // aux 생성은 새 위원회 안에서만 진행하므로 로컬 인덱스를 실행 인덱스로 변환
+          let aux_ctx = EnvelopeCtx { signers: committee, ..ctx };
+          match drive_sm(sm.as_mut(), aux_pending, &aux_ctx, step_out, &mut self.meta_sent, &mut self.internal_round, key_refresh_round_name::<E>)? {
+            Some(Ok(aux)) => *new_aux = Some(Box::new(aux)),
+            Some(Err(e)) => abort = Some(ProtocolAbort::from_debug("reshare", format!("{e:?}"), committee)),
+            None => {}
+          }
+        }
+        let finished = match (new_core.as_ref(), new_aux.as_deref()) {
+          // 키 갱신과 같이 새 위원회 일부만 끝났을 수 있으므로 새 쉐어는 `commitKeyRefresh()`까지 기존 쉐어와 따로 보관
+          (Some(Some(core)), Some(aux)) => {
+            let ks = KeyShare::from_parts((core.clone(), aux.clone())).map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))?;
+            self.pending_keyshare = Some(ks);
+            true
+          }
+          // 새 위원회가 아닌 파티는 새 위원회가 확인할 때까지 기존 쉐어를 유지 (`discardKeyshare()`로 삭제)
+          (Some(None), _) => {
+            self.presignature = None;
+            true
+          }
+          _ => false,
+        };
+        if finished {
+          self.status = "reshare_finished".to_string(); self.state = ProtocolState::None;
+          self.internal_round = "Finished".to_string();
+        }
+      }
+      ProtocolState::PartialSigning { partials, public_data, data, signers, .. } => {
+        if partials.iter().all(Option::is_some) {
+          let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare missing"))?;
+          let collected: Vec<PartialSignature<E>> = partials.iter().flatten().copied().collect();
+          let blamed = invalid_partial_signers(&collected, public_data, data, signers);
+          match PartialSignature::combine(&collected, public_data, *data).map(|sig| (sig, sig.verify(&ks.core.shared_public_key, data))) {
+            _ if !blamed.is_empty() => {
+              let message = format!("partial signature from {} does not match the presignature commitment", blamed.iter().map(|p| format!("party {p}")).collect::<Vec<_>>().join(", "));
+              abort = Some(ProtocolAbort::new("signing", AbortKind::Aborted, "InvalidPartialSignature", blamed, message));
+            }
+            Some((sig, Ok(()))) => {
+              self.last_signature = Some(SignedMessage { signature: sig, public_key: *ks.core.shared_public_key, message: data.to_scalar() });
+              self.status = "signing_finished".to_string(); self.state = ProtocolState::None;
+              self.internal_round = "Finished".to_string();
+            }
+            Some((_, Err(e))) => abort = Some(ProtocolAbort::new("signing", AbortKind::Aborted, "SignatureInvalid", Vec::new(), e.to_string())),
+            None => abort = Some(ProtocolAbort::new("signing", AbortKind::Aborted, "InvalidPartialSignatures", Vec::new(), "invalid partial signatures".to_string())),
+          }
+        }
+      }
+      ProtocolState::None => {}
+    }
+    if let Some(abort) = abort {
+      self.abort_protocol(abort, step_out);
+    }
+    
+    if !self.status.ends_with("_finished") && !self.status.ends_with("_aborted") && self.status != "keyshare_ready" {
+      self.status = match &self.state {
+        ProtocolState::None => "idle".to_string(),
+        ProtocolState::Keygen { .. } => "keygen_running".to_string(),
+        ProtocolState::AuxGen { .. } => "aux_gen_running".to_string(),
+        ProtocolState::Signing { .. } | ProtocolState::PartialSigning { .. } => "signing_running".to_string(),
+        ProtocolState::Presigning { .. } => "presigning_running".to_string(),
+        ProtocolState::KeyRefresh { .. } => "key_refresh_running".to_string(),
+        ProtocolState::Reshare { .. } => "reshare_running".to_string(),
+      };
+    }
+    Ok(())
+  }
+
+  fn snapshot(&self) -> Result<String> {
+    let (public_key, key_share_threshold) = if let Some(ks) = &self.keyshare {
+        (Some(hex::encode(ks.core.shared_public_key.to_bytes(true))), Some(ks.min_signers()))
+    } else if let Some(core) = &self.core_keyshare {
+        (Some(hex::encode(core.shared_public_key.to_bytes(true))), None)
+    } else { (None, None) };
+    let snap = ExecutorSnapshot {
+      session_id: self.session_id.clone(), execution_id: self.execution_id.clone(), party_index: self.party_index, threshold: self.threshold, parties_count: self.parties_count, phase: self.phase.clone(), round: self.round, processed: self.processed, status: self.status.clone(), errors: self.errors.clone(), last_round: self.last_round.map(|r| format!("{:?}", r)),
+      internal_round: self.internal_round.clone(), // Added
+      curve: E::CURVE_NAME.to_string(), has_aux: self.aux_info.is_some(), has_keyshare: self.keyshare.is_some(), public_key, key_share_threshold, signature: self.last_signature.as_ref().map(|s| serde_json::to_string(&s.signature).unwrap()),
+      has_presignature: self.presignature.is_some(), has_pending_keyshare: self.pending_keyshare.is_some(),
+      abort: self.abort.clone(), reliable_broadcast: self.reliable_broadcast,
+    };
+    serde_json::to_string(&snap).map_err(|e| Error::new(Status::GenericFailure, format!("{e}")))
+  }
+
+  fn export_keyshare_bin(&self) -> Result<napi::bindgen_prelude::Buffer> {
+    let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare not ready"))?;
+    let buf = binary::encode(Content::KeyShare, E::PROTO as u8, ks).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn export_aux_info_bin(&self) -> Result<napi::bindgen_prelude::Buffer> {
+    let aux = self.aux_info.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "aux info not ready"))?;
+    let buf = binary::encode(Content::AuxInfo, proto::Curve::Unspecified as u8, aux).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn serialize_state(&self) -> Result<napi::bindgen_prelude::Buffer> {
+    let state = SavedState {
+      version: journal::STATE_VERSION, curve: E::CURVE_NAME.to_string(),
+      session_id: self.session_id.clone(), execution_id: self.execution_id.clone(),
+      party_index: self.party_index, threshold: self.threshold, parties_count: self.parties_count,
+      checkpoint: self.checkpoint.clone(), journal: self.journal.clone(), sent: self.sent.clone(),
+    };
+    let buf = state.encode().map_err(|e| Error::new(Status::GenericFailure, e))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }
+
+  fn sent_envelopes(&self) -> Vec<napi::bindgen_prelude::Buffer> {
+    self.sent.iter().map(|b| napi::bindgen_prelude::Buffer::from(b.clone())).collect()
+  }
+
+  /// 체크포인트를 불러온 뒤 기록된 호출을 같은 RNG 시드로 재실행
+  /// (상태 머신이 실행기 필드를 참조하므로 재실행 전에 Box에 고정)
+  fn restore(state: SavedState, identity_key: Option<&[u8]>) -> Result<Box<Self>> {
+    let cp: Checkpoint<E> = serde_json::from_slice(&state.checkpoint).map_err(|e| Error::new(Status::InvalidArg, format!("invalid state checkpoint: {e}")))?;
+    let mut ex = Box::new(Self::new(state.session_id, state.execution_id, state.party_index, state.threshold, state.parties_count)?);
+    *ex.rng = UnsafeRng::from_seed(cp.seed);
+    ex.signers_at_keygen = cp.signers_at_keygen; ex.core_keyshare = cp.core_keyshare; ex.aux_info = cp.aux_info;
+    ex.keyshare = cp.keyshare; ex.pending_keyshare = cp.pending_keyshare; ex.presignature = cp.presignature; ex.processed = cp.processed;
+    ex.phase = cp.phase; ex.round = cp.round; ex.errors = cp.errors; ex.last_round = cp.last_round.and_then(|r| Round::try_from(r).ok());
+    ex.internal_round = cp.internal_round; ex.status = cp.status; ex.last_signature = cp.last_signature;
+    ex.meta_sent = cp.meta_sent; ex.abort = cp.abort; ex.reliable_broadcast = cp.reliable_broadcast;
+    ex.next_execution_id = cp.next_execution_id; ex.seed_source = cp.seed_source; ex.used_presignatures = cp.used_presignatures;
+    ex.early_partials = cp.early_partials; ex.used_execution_ids = cp.used_execution_ids;
+    ex.seal_rng = ex.seed_source.map(|_| seal_rng(cp.seed));
+    ex.checkpoint = state.checkpoint;
+    if let Some(roster) = cp.identity_roster {
+      ex.identity = Some(Identity::new(required_identity_key(identity_key)?, &roster, ex.party_index).map_err(|e| Error::new(Status::InvalidArg, e))?);
+    }
+
+    ex.replaying = true;
+    for entry in state.journal {
+      ex.apply(entry, identity_key)?;
+    }
+    ex.replaying = false;
+    // 저장 이후 이 프리서명으로 이미 서명했을 수 있으므로 복원된 실행기에는 남기지 않음
+    ex.presignature = None;
+    ex.early_partials.clear();
+    // OsRng로 암호화한 P2P payload와 그 서명은 재실행하면 달라지므로 빼고 비교한 뒤, 재전송에는 기록된 Envelope를 사용
+    let masked = |sent: &[Vec<u8>]| sent.iter().map(|b| masked_envelope(b, ex.identity.is_some())).collect::<Option<Vec<_>>>();
+    let (replayed, recorded) = (masked(&ex.sent), masked(&state.sent));
+    if replayed.is_none() || replayed != recorded {
+      return Err(Error::new(Status::GenericFailure, "state replay diverged from the recorded outgoing envelopes"));
+    }
+    ex.sent = state.sent;
+    Ok(ex)
+  }
+
+  fn apply(&mut self, entry: JournalEntry, identity_key: Option<&[u8]>) -> Result<()> {
+    match entry {
+      JournalEntry::ImportKeyshare(data) => self.import_keyshare(data.into()),
+      JournalEntry::ImportAuxInfo(data) => self.import_aux_info(data.into()),
+      JournalEntry::ImportPresignature(data) => self.import_presignature(data.into()),
+      JournalEntry::ExportPresignature => self.export_presignature().map(drop),
+      JournalEntry::DiscardKeyshare => self.discard_keyshare(),
+      JournalEntry::CommitKeyRefresh => self.commit_key_refresh(),
+      JournalEntry::SetSigners(json) => self.set_signers(json),
+      JournalEntry::StartKeygen => self.start_keygen(),
+      JournalEntry::StartAuxGen => self.start_aux_gen(),
+      JournalEntry::StartAuxGenWithPrimes(primes) => self.start_aux_gen_with_primes(primes.into()),
+      JournalEntry::StartKeyRefresh => self.start_key_refresh(),
+      JournalEntry::StartKeyRefreshWithPrimes(primes) => self.start_key_refresh_with_primes(primes.into()),
+      JournalEntry::StartSigning(tx_hex) => self.start_signing(tx_hex, None),
+      JournalEntry::StartSigningWithOptions(tx_hex, options) => self.start_signing(tx_hex, Some(options)),
+      JournalEntry::StartPresigning => self.start_presigning(),
+      JournalEntry::StartReshare(request) => self.start_reshare(request),
+      JournalEntry::SetIdentity(roster) => self.set_identity(required_identity_key(identity_key)?, &roster),
+      JournalEntry::SetReliableBroadcast(enabled) => self.set_reliable_broadcast(enabled),
+      JournalEntry::SetExecutionId(id) => self.set_execution_id(&id),
+      JournalEntry::SignWithPresignature(tx_hex) => self.sign_with_presignature(tx_hex, None).map(drop),
+      JournalEntry::SignWithPresignatureWithOptions(tx_hex, options) => self.sign_with_presignature(tx_hex, Some(options)).map(drop),
+      // 기록 당시 실패한 step은 재실행해도 같은 결과이므로 무시
+      JournalEntry::Step(inputs) => { let _ = self.step(&inputs); Ok(()) }
+    }
+  }
+
+  /// 새 프로토콜 시작: 체크포인트를 남기고 RNG를 새 시드로 교체한 뒤 기록을 새로 시작합니다. 시작에 실패하면 이전 기록을 유지합니다.
+  fn start_run<T>(&mut self, entry: JournalEntry, start: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+    if self.replaying {
+      let out = start(self)?;
+      self.next_execution_id = None;
+      self.journal.push(entry);
+      return Ok(out);
+    }
+    let prev_source = self.seed_source;
+    let seed = self.next_seed();
+    let checkpoint = self.make_checkpoint(seed)?;
+    let prev_rng = std::mem::replace(&mut self.rng.0, StdRng::from_seed(seed));
+    let prev_seal_rng = std::mem::replace(&mut self.seal_rng, self.seed_source.map(|_| seal_rng(seed)));
+    let prev_sent = std::mem::take(&mut self.sent);
+    let prev_used = self.used_execution_ids.len();
+    match start(self) {
+      Ok(out) => {
+        self.next_execution_id = None;
+        self.checkpoint = checkpoint;
+        self.journal = vec![entry];
+        Ok(out)
+      }
+      Err(e) => {
+        self.rng.0 = prev_rng;
+        self.seal_rng = prev_seal_rng;
+        self.sent = prev_sent;
+        self.seed_source = prev_source;
+        self.used_execution_ids.truncate(prev_used);
+        Err(e)
+      }
+    }
+  }
+
+  fn next_seed(&mut self) -> [u8; 32] {
+    match &mut self.seed_source {
+      Some(source) => source.next(),
+      None => fresh_seed(),
+    }
+  }
+
+  /// 고정 시드로 RNG를 다시 초기화 (`withSeed()`)
+  fn seed_rng(&mut self, seed: &[u8]) -> Result<()> {
+    self.seed_source = Some(SeedSource { seed: test_seed(seed)?, counter: 0 });
+    let seed = self.next_seed();
+    *self.rng = UnsafeRng::from_seed(seed);
+    self.seal_rng = Some(seal_rng(seed));
+    self.checkpoint = self.make_checkpoint(seed)?;
+    Ok(())
+  }
+
+  fn make_checkpoint(&self, seed: [u8; 32]) -> Result<Vec<u8>> {
+    let cp = Checkpoint::<E> {
+      seed, signers_at_keygen: self.signers_at_keygen.clone(), core_keyshare: self.core_keyshare.clone(),
+      aux_info: self.aux_info.clone(), keyshare: self.keyshare.clone(), presignature: self.presignature.clone(),
+      processed: self.processed, phase: self.phase.clone(), round: self.round, errors: self.errors.clone(),
+      last_round: self.last_round.map(|r| r as i32), internal_round: self.internal_round.clone(), status: self.status.clone(),
+      last_signature: self.last_signature.clone(), meta_sent: self.meta_sent, abort: self.abort.clone(), identity_roster: self.identity.as_ref().map(Identity::roster),
+      reliable_broadcast: self.reliable_broadcast, next_execution_id: self.next_execution_id,
+      seed_source: self.seed_source, used_presignatures: self.used_presignatures.clone(), pending_keyshare: self.pending_keyshare.clone(),
+      early_partials: self.early_partials.clone(), used_execution_ids: self.used_execution_ids.clone(),
+    };
+    serde_json::to_vec(&cp).map_err(|e| Error::new(Status::GenericFailure, format!("checkpoint: {e}")))
+  }
+
+  fn check_refresh_keyshare(&self) -> Result<&KeyShareWithLevel<E>> {
+    let ks = self.keyshare.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "keyshare missing"))?;
+    self.check_keyshare_params(ks.core.i, ks.core.public_shares.len(), ks.min_signers())?;
+    if self.identity.is_none() {
+      return Err(Error::new(Status::InvalidArg, "key refresh requires setIdentity(): new shares are sent point-to-point"));
+    }
+    Ok(ks)
+  }
+
+  /// 발신 Envelope 인코딩. 상태 머신이 쓰는 `self.rng`는 건드리지 않음
+  fn encode_outgoing(&mut self, envs: &mut [Envelope]) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
+    match &mut self.seal_rng {
+      Some(rng) => encode_envelopes_bin(envs, self.identity.as_ref(), rng),
+      None => encode_envelopes_bin(envs, self.identity.as_ref(), &mut OsRng),
+    }
+  }
+
+  /// 하위 상태 머신마다 따로 쓸 RNG (두 상태 머신이 `self.rng`를 함께 빌리지 않도록 분기)
+  fn fork_rng(&mut self) -> StdRng {
+    StdRng::from_seed(self.rng.gen())
+  }
+
+  /// 키쉐어의 (i, n, t)와 실행기 설정이 다른 항목
+  fn keyshare_param_mismatches(&self, i: u16, n: usize, t: u16) -> Vec<String> {
+    let mut problems = Vec::new();
+    if i != self.party_index {
+      problems.push(format!("party index is {i}, expected {}", self.party_index));
+    }
+    if n != usize::from(self.parties_count) {
+      problems.push(format!("parties count is {n}, expected {}", self.parties_count));
+    }
+    if t != self.threshold {
+      problems.push(format!("threshold is {t}, expected {}", self.threshold));
+    }
+    problems
+  }
+
+  fn check_keyshare_params(&self, i: u16, n: usize, t: u16) -> Result<()> {
+    let problems = self.keyshare_param_mismatches(i, n, t);
+    if !problems.is_empty() {
+      return Err(Error::new(Status::InvalidArg, format!("keyshare does not match executor: {}", problems.join(", "))));
+    }
+    Ok(())
+  }
+
+  fn get_signature(&self, name: &str) -> Result<napi::bindgen_prelude::Buffer> {
+    let format = SignatureFormat::parse(name).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    if format.secp256k1_only() && E::CURVE_NAME != Secp256k1::CURVE_NAME {
+      return Err(Error::new(Status::InvalidArg, format!("{name} signature format is not supported on the {} curve", E::CURVE_NAME)));
+    }
+    let signed = self.last_signature.as_ref().ok_or_else(|| Error::new(Status::InvalidArg, "signature not ready"))?;
+    signed.signature.verify(&signed.public_key, &PrehashedDataToSign::from_scalar(signed.message))
+      .map_err(|_| Error::new(Status::GenericFailure, "signature does not verify against the public key"))?;
+    let bytes = signature::encode(format, &signed.signature, &signed.public_key, &signed.message).map_err(|e| Error::new(Status::GenericFailure, e))?;
+    Ok(bytes.into())
+  }
+
+  fn derive_public_key(&self, path: &str) -> Result<String> {
+    let core: &cggmp24::key_share::DirtyIncompleteKeyShare<E> = match (&self.keyshare, &self.core_keyshare) {
+      (Some(ks), _) => &ks.core,
+      (None, Some(core)) => core,
+      (None, None) => return Err(Error::new(Status::InvalidArg, "keyshare missing")),
+    };
+    let path = parse_derivation_path(path)?;
+    let child = core.derive_child_public_key::<E::Hd, _>(path).map_err(hd_error)?;
+    Ok(hex::encode(child.public_key.to_bytes(true)))
+  }
+
+  fn validate_keyshare(&self) -> Result<KeyshareReport> {
+    let (core, aux): (&cggmp24::key_share::DirtyIncompleteKeyShare<E>, _) = match (&self.keyshare, &self.core_keyshare) {
+      (Some(ks), _) => (&ks.core, Some(&ks.aux)),
+      (None, Some(core)) => (core, self.aux_info.as_deref()),
+      (None, None) => return Err(Error::new(Status::InvalidArg, "keyshare missing")),
+    };
+    let t = core.vss_setup.as_ref().map(|s| s.min_signers).unwrap_or(self.parties_count);
+    let mut problems = self.keyshare_param_mismatches(core.i, core.public_shares.len(), t);
+    let core_valid = core.is_valid().map_err(|e| problems.push(format!("core share: {e}"))).is_ok();
+    let aux_consistent = match aux {
+      Some(aux) => {
+        let aux_valid = aux.is_valid().map_err(|e| problems.push(format!("aux info: {e}"))).is_ok();
+        let full = cggmp24::key_share::DirtyKeyShare { core: core.clone(), aux: aux.clone() };
+        // core가 유효하지 않으면 전체 검증은 core 오류를 다시 보고하므로 일치 여부만 따로 판단하지 않음
+        aux_valid && core_valid && full.is_valid().map_err(|e| problems.push(format!("aux info does not match core share: {e}"))).is_ok()
+      }
+      None => { problems.push("aux info missing".to_string()); false }
+    };
+    let public_key = core.shared_public_key.to_bytes(true);
+    Ok(KeyshareReport {
+      valid: problems.is_empty(), curve: E::CURVE_NAME.to_string(), public_key: hex::encode(&public_key), key_id: hex::encode(sealed::key_id(&public_key)),
+      party_index: u32::from(core.i), parties_count: core.public_shares.len() as u32, threshold: u32::from(t),
+      has_aux: aux.is_some(), aux_consistent, problems,
+    })
+  }
+
+  fn start_key_refresh_inner(&mut self, primes: cggmp24::PregeneratedPrimes<SecLevel>) -> Result<()> {
+    let ks = self.check_refresh_keyshare()?;
+    let everyone: Vec<Option<u16>> = (0..self.parties_count).map(Some).collect();
+    let public_key = ks.core.shared_public_key.to_bytes(true);
+    let setup = reshare::Setup {
+      i: self.party_index, old_key_info: ks.core.key_info.clone(), old_indices: everyone.clone(),
+      old_share: Some(ks.core.x.clone()), new_indices: everyone, new_t: ks.min_signers(),
+      reliable_broadcast: self.reliable_broadcast.unwrap_or(true),
+    };
+    let parties: Vec<u16> = (0..self.parties_count).collect();
+    let eid = self.execution_id(&EidTranscript { phase: "key_refresh", parties: &parties, public_key: &public_key, message: &[] })?;
+    let eids = Box::new([*eid, *sub_execution_id(&eid, "aux")]);
+    let mut rngs = Box::new([self.fork_rng(), self.fork_rng()]);
+    let [reshare_rng, aux_rng] = &mut *rngs;
+    let (reshare_rng, reshare_eid) = (extend_mut(reshare_rng), ExecutionId::new(extend_ref(&eids[0])));
+    let reshare_sm = round_based::state_machine::wrap_protocol(move |party| reshare::run(setup, reshare_eid, reshare_rng, party));
+    let aux_sm = cggmp24::aux_info_gen(ExecutionId::new(extend_ref(&eids[1])), self.party_index, self.parties_count, primes).enforce_reliable_broadcast(self.reliable_broadcast.unwrap_or(false)).into_state_machine(extend_mut(aux_rng));
+    self.state = ProtocolState::KeyRefresh {
+      reshare_sm: Box::new(TaggedSm { sm: reshare_sm, wrap: KeyRefreshMsg::Reshare, unwrap: |m| match m { KeyRefreshMsg::Reshare(m) => Ok(m), other => Err(other) } }),
+      aux_sm: Box::new(TaggedSm { sm: aux_sm, wrap: |m| KeyRefreshMsg::Aux(Box::new(m)), unwrap: |m| match m { KeyRefreshMsg::Aux(m) => Ok(*m), other => Err(other) } }),
+      reshare_pending: Vec::new(), aux_pending: Vec::new(), new_core: None, new_aux: None, _eids: eids, _rngs: rngs,
+    };
+    // 커밋하지 않은 이전 갱신 결과는 버리고 기존 쉐어에서 다시 갱신
+    self.pending_keyshare = None;
+    self.phase = "KEY_REFRESH".to_string(); self.status = "running".to_string(); self.round = Round::KeyRefresh as u32; self.last_round = Some(Round::KeyRefresh);
+    self.internal_round = "Round 1 (Reshare)".to_string(); // Initial round
+    Ok(())
+  }
+
+  fn start_reshare_inner(&mut self, request: &ReshareRequest) -> Result<()> {
+    let n = usize::from(self.parties_count);
+    if request.old_indices.len() != n || request.new_indices.len() != n {
+      return Err(Error::new(Status::InvalidArg, format!("expected old and new indices for {n} parties")));
+    }
+    let me = usize::from(self.party_index);
+    let (old_key_info, old_share) = match (request.old_indices[me], &request.key_share, &request.key_info) {
+      (Some(i), Some(data), _) => {
+        let ks: KeyShareWithLevel<E> = parse_key_export(data, Content::KeyShare, Some(E::PROTO))?;
+        if ks.core.i != i {
+          return Err(Error::new(Status::InvalidArg, format!("key share index is {}, expected {i}", ks.core.i)));
+        }
+        (ks.core.key_info.clone(), Some(ks.core.x.clone()))
+      }
+      (Some(_), None, _) => return Err(Error::new(Status::InvalidArg, "key share required for a party in the old committee")),
+      (None, Some(_), _) => return Err(Error::new(Status::InvalidArg, "key share given for a party outside the old committee")),
+      (None, None, Some(data)) => {
+        let info: cggmp24::key_share::DirtyKeyInfo<E> = serde_json::from_slice(data).map_err(|e| Error::new(Status::InvalidArg, format!("invalid key info: {e}")))?;
+        (info, None)
+      }
+      (None, None, None) => return Err(Error::new(Status::InvalidArg, "key info required for a party without a key share")),
+    };
+    let new_n = request.new_indices.iter().flatten().count();
+    let mut committee = vec![None; new_n];
+    for (j, &idx) in (0..self.parties_count).zip(&request.new_indices) {
+      match idx.and_then(|idx| committee.get_mut(usize::from(idx))) {
+        Some(slot @ None) => *slot = Some(j),
+        Some(Some(_)) | None if idx.is_some() => return Err(Error::new(Status::InvalidArg, format!("new indices must be 0..{new_n} without duplicates"))),
+        _ => {}
+      }
+    }
+    let committee: Vec<u16> = committee.into_iter().flatten().collect();
+    if self.threshold < 2 || usize::from(self.threshold) > new_n {
+      return Err(Error::new(Status::InvalidArg, format!("invalid parameters: threshold {} of {new_n} parties", self.threshold)));
+    }
+    if self.identity.is_none() {
+      return Err(Error::new(Status::InvalidArg, "reshare requires setIdentity(): new shares are sent point-to-point"));
+    }
+
+    let public_key = old_key_info.shared_public_key.to_bytes(true);
+    let eid = self.execution_id(&EidTranscript { phase: "reshare", parties: &committee, public_key: &public_key, message: &[] })?;
+    let eids = Box::new([*eid, *sub_execution_id(&eid, "aux")]);
+    let mut rngs = Box::new([self.fork_rng(), self.fork_rng()]);
+    let [reshare_rng, aux_rng] = &mut *rngs;
+    let aux_sm = match request.new_indices[me] {
+      Some(new_i) => {
+        let primes: cggmp24::PregeneratedPrimes<SecLevel> = match &request.primes {
+          Some(primes) => bincode::deserialize(primes).map_err(|e| Error::new(Status::InvalidArg, format!("invalid primes: {e}")))?,
+          None => cggmp24::PregeneratedPrimes::generate(&mut *self.rng),
+        };
+        let sm = cggmp24::aux_info_gen(ExecutionId::new(extend_ref(&eids[1])), new_i, new_n as u16, primes).enforce_reliable_broadcast(self.reliable_broadcast.unwrap_or(false)).into_state_machine(extend_mut(aux_rng));
+        let sm: Box<dyn StateMachine<Output = AuxGenOutput, Msg = KeyRefreshMsg<E>>> = Box::new(TaggedSm { sm, wrap: |m| KeyRefreshMsg::Aux(Box::new(m)), unwrap: |m| match m { KeyRefreshMsg::Aux(m) => Ok(*m), other => Err(other) } });
+        Some(sm)
+      }
+      None => None,
+    };
+    let setup = reshare::Setup {
+      i: self.party_index, old_key_info, old_indices: request.old_indices.clone(),
+      old_share, new_indices: request.new_indices.clone(), new_t: self.threshold,
+      reliable_broadcast: self.reliable_broadcast.unwrap_or(true),
+    };
+    let (reshare_rng, reshare_eid) = (extend_mut(reshare_rng), ExecutionId::new(extend_ref(&eids[0])));
+    let reshare_sm = round_based::state_machine::wrap_protocol(move |party| reshare::run(setup, reshare_eid, reshare_rng, party));
+    self.state = ProtocolState::Reshare {
+      reshare_sm: Box::new(TaggedSm { sm: reshare_sm, wrap: KeyRefreshMsg::Reshare, unwrap: |m| match m { KeyRefreshMsg::Reshare(m) => Ok(m), other => Err(other) } }),
+      aux_sm, reshare_pending: Vec::new(), aux_pending: Vec::new(), committee, new_core: None, new_aux: None, _eids: eids, _rngs: rngs,
+    };
+    self.pending_keyshare = None;
+    self.phase = "RESHARE".to_string(); self.status = "running".to_string(); self.round = Round::KeyRefresh as u32; self.last_round = Some(Round::KeyRefresh);
+    self.internal_round = "Round 1 (Reshare)".to_string(); // Initial round
+    Ok(())
+  }
+
+  /// keygen/aux 생성 완료 후 core와 aux를 결합. 서로 맞지 않으면 (다른 세션의 aux 등) 키쉐어를 만들지 않음
+  /// (`validateKeyshare()`로 원인 확인)
+  fn try_combine_shares(&mut self) {
+    if let (Some(core), Some(aux)) = (&self.core_keyshare, &self.aux_info) {
+      match KeyShare::from_parts((core.clone(), aux.clone())) {
+        Ok(ks) => { self.keyshare = Some(ks); self.status = "keyshare_ready".to_string(); }
+        Err(_) => self.status = "keyshare_invalid".to_string(),
+      }
+    }
+  }
+
+  /// 수신 Envelope를 현재 세션/라운드 기준으로 검증하고 payload를 꺼냄
+  fn open_envelope(&self, index: usize, buf: &[u8]) -> std::result::Result<Opened, MessageRejection> {
+    let mut env = Envelope::decode(buf).map_err(|e| RejectReason::MalformedEnvelope.reject(index, None, format!("invalid envelope: {e}")))?;
+    let claimed = u16::try_from(env.from_party).ok();
+    let reject = |reason: RejectReason, message: String| reason.reject(index, claimed, message);
+    if env.version != 1 {
+      return Err(reject(RejectReason::UnsupportedVersion, format!("unsupported envelope version: {}", env.version)));
+    }
+    if env.session_id != self.session_id {
+      return Err(reject(RejectReason::SessionMismatch, "session id mismatch".to_string()));
+    }
+    if env.execution_id != self.execution_id {
+      return Err(reject(RejectReason::ExecutionMismatch, "execution id mismatch".to_string()));
+    }
+    if let Some(meta) = &env.meta {
+      if meta.curve != E::PROTO as i32 {
+        return Err(reject(RejectReason::CurveMismatch, format!("curve mismatch: expected {}", E::CURVE_NAME)));
+      }
+    }
+
+    let (expected, tx_context, signers): (Round, Option<SigningContext>, &[u16]) = match &self.state {
+      ProtocolState::Keygen { .. } => (Round::Keygen, None, &[]),
+      ProtocolState::AuxGen { .. } => (Round::AuxInfo, None, &[]),
+      ProtocolState::Signing { tx_context, derivation_path, hash_mode, _signers, .. } => (Round::Signing, Some((tx_context, derivation_path, hash_mode.name())), _signers),
+      ProtocolState::Presigning { _signers, .. } => (Round::Presignature, None, _signers),
+      ProtocolState::KeyRefresh { .. } | ProtocolState::Reshare { .. } => (Round::KeyRefresh, None, &[]),
+      ProtocolState::PartialSigning { signers, tx_context, hash_mode, .. } => (Round::Signing, Some((tx_context, &[], hash_mode.name())), signers),
+      // 프리서명이 있으면 먼저 도착한 부분 서명을 받고, tx_context는 `signWithPresignature()`에서 확인
+      ProtocolState::None => match &self.presignature {
+        Some(presig) => (Round::Signing, None, &presig.signers),
+        None => return Err(reject(RejectReason::NoProtocol, "no protocol".to_string())),
+      },
+    };
+    let from_party = claimed.filter(|&p| p < self.parties_count && p != self.party_index)
+      .ok_or_else(|| reject(RejectReason::InvalidSender, format!("invalid sender: {}", env.from_party)))?;
+    if let Some(identity) = &self.identity {
+      let signature = std::mem::take(&mut env.signature);
+      if signature.is_empty() {
+        return Err(reject(RejectReason::InvalidSignature, "missing envelope signature".to_string()));
+      }
+      if !identity.verify(from_party, &envelope_signing_bytes(&env), &signature) {
+        return Err(reject(RejectReason::InvalidSignature, format!("envelope signature does not match party {from_party}")));
+      }
+      // 다른 파티에게 보낸 Envelope는 아래에서 NOT_ADDRESSED로 거부
+      if env.to_parties == [u32::from(self.party_index)] {
+        open_payload(&mut env, identity).map_err(|e| reject(RejectReason::UndecryptablePayload, format!("undecryptable payload: {e}")))?;
+      }
+    }
+    if env.round == Round::Error as i32 {
+      if let Some(Payload::Error(err)) = env.payload {
+        if matches!(self.state, ProtocolState::None) {
+          return Err(reject(RejectReason::NoProtocol, "no protocol".to_string()));
+        }
+        return Ok(Opened::PeerAbort(ProtocolAbort::from_peer(self.state.name(), from_party, err)));
+      }
+    }
+    if env.round != expected as i32 {
+      return Err(reject(RejectReason::RoundMismatch, format!("round mismatch: expected {:?}, got {}", expected, env.round)));
+    }
+    let (payload_round, payload) = match env.payload {
+      Some(Payload::Keygen(p)) => (Round::Keygen, p.payload),
+      Some(Payload::AuxInfo(p)) => (Round::AuxInfo, p.payload),
+      Some(Payload::Signing(p)) => {
+        if let Some((tx, path, hash_mode)) = tx_context {
+          if tx != p.tx_context.as_slice() {
+            return Err(reject(RejectReason::TxContextMismatch, "tx context mismatch".to_string()));
+          }
+          if hash_mode != p.hash_mode {
+            let name = |m: &str| if m.is_empty() { "default".to_string() } else { m.to_string() };
+            return Err(reject(RejectReason::TxContextMismatch, format!("hash mode mismatch: expected {}, got {}", name(hash_mode), name(&p.hash_mode))));
+          }
+          if path != p.derivation_path.as_slice() {
+            return Err(reject(RejectReason::DerivationPathMismatch, format!("derivation path mismatch: expected {}, got {}", format_derivation_path(path), format_derivation_path(&p.derivation_path))));
+          }
+        }
+        (Round::Signing, p.payload)
+      }
+      Some(Payload::Presignature(p)) => (Round::Presignature, p.payload),
+      Some(Payload::KeyRefresh(p)) => (Round::KeyRefresh, p.payload),
+      Some(Payload::Error(_) | Payload::Status(_)) | None => (Round::Unspecified, Vec::new()),
+    };
+    if payload_round != expected {
+      return Err(reject(RejectReason::RoundMismatch, format!("round mismatch: {:?} envelope with {:?} payload", expected, payload_round)));
+    }
+
+    let sender = if signers.is_empty() {
+      from_party
+    } else {
+      signers.iter().position(|&s| s == from_party).ok_or_else(|| reject(RejectReason::SenderNotSigner, format!("sender {from_party} not in signers")))? as u16
+    };
+    let msg_type = if env.to_parties.is_empty() {
+      MessageType::Broadcast
+    } else if env.to_parties.contains(&u32::from(self.party_index)) {
+      MessageType::P2P
+    } else {
+      return Err(reject(RejectReason::NotAddressed, "envelope not addressed to this party".to_string()));
+    };
+    Ok(Opened::Msg(OpenedEnvelope { index, from_party, sender, msg_type, payload }))
+  }
+}
+
+/// Envelope 서명 도메인 구분자
+const ENVELOPE_SIGNATURE_DOMAIN: &[u8] = b"cggmp.v1.Envelope.signature\0";
+
+/// 서명 대상 바이트: 도메인 구분자와 `signature`를 비운 Envelope 인코딩
+fn envelope_signing_bytes(env: &Envelope) -> Vec<u8> {
+  let mut buf = ENVELOPE_SIGNATURE_DOMAIN.to_vec();
+  buf.extend_from_slice(&env.encode_to_vec());
+  buf
+}
+
+/// Envelope 안의 프로토콜 payload (Error/Status Envelope에는 없음)
+fn payload_bytes_mut(env: &mut Envelope) -> Option<&mut Vec<u8>> {
+  match env.payload.as_mut()? {
+    Payload::Keygen(p) => Some(&mut p.payload),
+    Payload::AuxInfo(p) => Some(&mut p.payload),
+    Payload::Signing(p) => Some(&mut p.payload),
+    Payload::Presignature(p) => Some(&mut p.payload),
+    Payload::KeyRefresh(p) => Some(&mut p.payload),
+    Payload::Error(_) | Payload::Status(_) => None,
+  }
+}
+
+/// P2P payload를 수신자 식별 키로 암호화. payload와 서명을 비운 Envelope 인코딩(헤더)을 AAD로 사용
+fn seal_payload(env: &mut Envelope, identity: &Identity, recipient: u16, rng: &mut (impl RngCore + CryptoRng)) -> Result<()> {
+  let Some(payload) = payload_bytes_mut(env) else { return Ok(()) };
+  let plaintext = Zeroizing::new(std::mem::take(payload));
+  let sealed = identity.seal(recipient, &env.encode_to_vec(), &plaintext, rng).map_err(|e| Error::new(Status::GenericFailure, e))?;
+  if let Some(payload) = payload_bytes_mut(env) {
+    *payload = sealed;
+  }
+  Ok(())
+}
+
+fn open_payload(env: &mut Envelope, identity: &Identity) -> std::result::Result<(), String> {
+  let Some(payload) = payload_bytes_mut(env) else { return Ok(()) };
+  let sealed = std::mem::take(payload);
+  let plaintext = identity.open(&env.encode_to_vec(), &sealed)?;
+  if let Some(payload) = payload_bytes_mut(env) {
+    *payload = plaintext;
+  }
+  Ok(())
+}
+
+/// 식별 키가 있으면 P2P payload를 암호화하고 서명한 뒤 인코딩
+fn encode_envelopes_bin(envs: &mut [Envelope], identity: Option<&Identity>, rng: &mut (impl RngCore + CryptoRng)) -> Result<Vec<napi::bindgen_prelude::Buffer>> {
+  envs.iter_mut().map(|env| {
+    if let Some(identity) = identity {
+      if let [recipient] = env.to_parties[..] {
+        seal_payload(env, identity, recipient as u16, rng)?;
+      }
+      env.signature = identity.sign(&envelope_signing_bytes(env));
+    }
+    let mut buf = Vec::new();
+    env.encode(&mut buf).map_err(|e| Error::new(Status::GenericFailure, format!("encode: {e}")))?;
+    Ok(napi::bindgen_prelude::Buffer::from(buf))
+  }).collect()
+}
+
+/// 재실행 비교용 Envelope: 식별 키가 있으면 암호화된 P2P payload와 서명을 비움. 디코딩에 실패하면 `None`
+fn masked_envelope(bytes: &[u8], sealed: bool) -> Option<Envelope> {
+  let mut env = Envelope::decode(bytes).ok()?;
+  if sealed {
+    if env.to_parties.len() == 1 {
+      if let Some(payload) = payload_bytes_mut(&mut env) {
+        payload.clear();
+      }
+    }
+    env.signature.clear();
+  }
+  Some(env)
+}
+
+fn encode_msg<T: Serialize>(msg: &T) -> Result<Vec<u8>> {
+  bincode::serialize(msg).map_err(|e| Error::new(Status::GenericFailure, format!("encode: {e}")))
+}
+
+enum Opened {
+  Msg(OpenedEnvelope),
+  /// 다른 파티가 보낸 Error Envelope
+  PeerAbort(ProtocolAbort),
+}
+
+/// 검증을 통과한 수신 Envelope (sender는 상태 머신 기준 인덱스, from_party는 전역 인덱스)
+struct OpenedEnvelope {
+  index: usize,
+  from_party: u16,
+  sender: u16,
+  msg_type: MessageType,
+  payload: Vec<u8>,
+}
+
+/// `step()` 한 번의 결과: 발신 Envelope와 거부된 메시지
+#[derive(Default)]
+struct StepOutput {
+  outgoing: Vec<Envelope>,
+  rejected: Vec<MessageRejection>,
+  abort: Option<ProtocolAbort>,
+}
+
+/// payload 역직렬화. Incoming `id`에는 입력 인덱스를 담아 상태 머신 거부 시 보고에 사용
+fn decode_incoming<M: for<'de> Deserialize<'de> + Send>(opened: &[OpenedEnvelope], rejected: &mut Vec<MessageRejection>) -> Vec<Incoming<M>> {
+  let decoded: Vec<std::result::Result<Incoming<M>, MessageRejection>> = opened.par_iter().map(|env| {
+    bincode::deserialize(&env.payload)
+      .map(|msg| Incoming { id: env.index as u64, sender: env.sender, msg_type: env.msg_type, msg })
+      .map_err(|e| RejectReason::UndecodablePayload.reject(env.index, Some(env.from_party), format!("decode: {e}")))
+  }).collect();
+  decoded.into_iter().filter_map(|r| r.map_err(|e| rejected.push(e)).ok()).collect()
+}
+
+fn drive_sm<M, O, F>(
+    sm: &mut dyn StateMachine<Output = O, Msg = M>,
+    pending: &mut Vec<Incoming<M>>,
+    ctx: &EnvelopeCtx,
+    out: &mut StepOutput,
+    meta_sent: &mut bool,
+    internal_round: &mut String,
+    get_round_name: F
+) -> Result<Option<O>>
+where
+    M: Clone + Serialize + for<'de> Deserialize<'de>,
+    F: Fn(&M) -> String,
+{
+  loop {
+    match sm.proceed() {
+      ProceedResult::SendMsg(msg) => {
+        let to = match msg.recipient { MessageDestination::AllParties => Vec::new(), MessageDestination::OneParty(i) => vec![u32::from(ctx.global_index(i))] };
+        *internal_round = get_round_name(&msg.msg); // Update internal round
+        out.outgoing.push(make_envelope(ctx, &to, encode_msg(&msg.msg)?, *meta_sent));
+        *meta_sent = true;
+      }
+      ProceedResult::NeedsOneMoreMessage => {
+        if !pending.is_empty() {
+          let msg = pending.remove(0); // FIFO
+          if let Err(msg) = sm.received_msg(msg) {
+            let sender = ctx.global_index(msg.sender);
+            out.rejected.push(RejectReason::RejectedByStateMachine.reject(msg.id as usize, Some(sender), "rejected by state machine"));
+          }
+        } else {
+          return Ok(None);
+        }
+      }
+      ProceedResult::Yielded => continue,
+      ProceedResult::Output(o) => return Ok(Some(o)),
+      ProceedResult::Error(err) => return Err(Error::new(Status::GenericFailure, format!("{err}"))),
+    }
+  }
+}
+
+fn key_refresh_round_name<E: Curve>(msg: &KeyRefreshMsg<E>) -> String {
+  match msg {
+    KeyRefreshMsg::Reshare(reshare::Msg::Round1(_) | reshare::Msg::Round2(_)) => "Round 1 (Reshare)".to_string(),
+    KeyRefreshMsg::Aux(aux) => match **aux {
+      cggmp24::key_refresh::msg::Msg::Round1(_) => "Round 1 (Paillier Gen)".to_string(),
+      cggmp24::key_refresh::msg::Msg::Round2(_) => "Round 2 (ZKP Verify)".to_string(),
+      cggmp24::key_refresh::msg::Msg::Round3(_) => "Round 3 (Finalize)".to_string(),
+      cggmp24::key_refresh::msg::Msg::ReliabilityCheck(_) => "Reliability Check".to_string(),
+    },
+    KeyRefreshMsg::Reshare(reshare::Msg::ReliabilityCheck(_)) => "Reliability Check".to_string(),
+    KeyRefreshMsg::Reshare(reshare::Msg::Complaints(_) | reshare::Msg::Openings(_)) => "Round 2 (Complaints)".to_string(),
+    KeyRefreshMsg::Reshare(reshare::Msg::Confirm(_)) => "Round 3 (Confirm)".to_string(),
+  }
+}
+
+/// 하위 프로토콜 상태 머신의 메시지를 상위 메시지 타입으로 감싸는 어댑터
+struct TaggedSm<S: StateMachine, W> {
+  sm: S,
+  wrap: fn(S::Msg) -> W,
+  unwrap: fn(W) -> std::result::Result<S::Msg, W>,
+}
+
+impl<S: StateMachine, W> StateMachine for TaggedSm<S, W> {
+  type Output = S::Output;
+  type Msg = W;
+
+  fn proceed(&mut self) -> ProceedResult<S::Output, W> {
+    match self.sm.proceed() {
+      ProceedResult::SendMsg(out) => ProceedResult::SendMsg(Outgoing { recipient: out.recipient, msg: (self.wrap)(out.msg) }),
+      ProceedResult::NeedsOneMoreMessage => ProceedResult::NeedsOneMoreMessage,
+      ProceedResult::Yielded => ProceedResult::Yielded,
+      ProceedResult::Output(o) => ProceedResult::Output(o),
+      ProceedResult::Error(err) => ProceedResult::Error(err),
+    }
+  }
+
+  fn received_msg(&mut self, msg: Incoming<W>) -> std::result::Result<(), Incoming<W>> {
+    let Incoming { id, sender, msg_type, msg } = msg;
+    let msg = (self.unwrap)(msg).map_err(|msg| Incoming { id, sender, msg_type, msg })?;
+    self.sm.received_msg(Incoming { id, sender, msg_type, msg }).map_err(|m| Incoming { id, sender, msg_type, msg: (self.wrap)(m.msg) })
+  }
+}
+
+/// 서명 Envelope가 일치해야 하는 (tx_context, 파생 경로, 해싱 방식)
+type SigningContext<'a> = (&'a [u8], &'a [u32], &'a str);
+
+/// Envelope 헤더 생성에 필요한 세션 정보
+#[derive(Clone, Copy)]
+struct EnvelopeCtx<'a> {
+  session_id: &'a str,
+  execution_id: &'a str,
+  curve: proto::Curve,
+  round: Round,
+  party_index: u16,
+  threshold: u16,
+  parties_count: u16,
+  tx_context: &'a [u8],
+  derivation_path: &'a [u32],
+  hash_mode: &'a str,
+  /// 서명자 집합 (비어 있으면 상태 머신 인덱스가 곧 전역 인덱스)
+  signers: &'a [u16],
+}
+
+impl EnvelopeCtx<'_> {
+  /// 상태 머신의 로컬 인덱스를 전역 파티 인덱스로 변환
+  fn global_index(&self, i: u16) -> u16 {
+    if self.signers.is_empty() { i } else { self.signers[usize::from(i)] }
+  }
+}
+
+fn make_envelope(ctx: &EnvelopeCtx, to: &[u32], payload: Vec<u8>, meta_sent: bool) -> Envelope {
+  let tx = ctx.tx_context;
+  let p_enum = match ctx.round {
+    Round::Keygen => Some(Payload::Keygen(proto::Keygen { payload })),
+    Round::AuxInfo => Some(Payload::AuxInfo(proto::AuxInfo { payload })),
+    Round::Signing => Some(Payload::Signing(proto::Signing { payload, tx_context: tx.to_vec(), derivation_path: ctx.derivation_path.to_vec(), hash_mode: ctx.hash_mode.to_string() })),
+    Round::Presignature => Some(Payload::Presignature(proto::Presignature { payload })),
+    Round::KeyRefresh => Some(Payload::KeyRefresh(proto::KeyRefresh { payload })),
+    _ => None,
+  };
+  
+  let curve = ctx.curve as i32;
+
+  let meta = if meta_sent {
+    None
+  } else {
+    Some(proto::Meta { curve, threshold: ctx.threshold as u32, parties_count: ctx.parties_count as u32, party_index: ctx.party_index as u32, tx_context: tx.to_vec(), retry: 0, payload_format: PAYLOAD_FORMAT_BINCODE.to_string(), key_id: String::new() })
+  };
+
+  Envelope { version: 1, session_id: ctx.session_id.to_string(), execution_id: ctx.execution_id.to_string(), round: ctx.round as i32, from_party: ctx.party_index as u32, to_parties: to.to_vec(), meta, signature: Vec::new(), payload: p_enum }
+}
+
+fn fresh_seed() -> [u8; 32] {
+  let mut seed = [0u8; 32];
+  rand::rngs::OsRng.fill_bytes(&mut seed);
+  seed
+}
+
+/// 시드 실행기의 P2P 암호화 RNG. 프로토콜 시드에서 따로 유도해 상태 머신 RNG와 `SeedSource` 순서에 영향을 주지 않음
+fn seal_rng(seed: [u8; 32]) -> StdRng {
+  StdRng::from_seed(Sha256::new().chain_update(b"cggmp.v1.seal\0").chain_update(seed).finalize().into())
+}
+
+/// 고정 시드에서 프로토콜마다 쓸 RNG 시드를 순서대로 유도 (테스트 벡터 전용)
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct SeedSource {
+  seed: [u8; 32],
+  counter: u64,
+}
+
+impl SeedSource {
+  fn next(&mut self) -> [u8; 32] {
+    let out = Sha256::new().chain_update(b"cggmp.v1.rng\0").chain_update(self.seed).chain_update(self.counter.to_be_bytes()).finalize();
+    self.counter += 1;
+    out.into()
+  }
+}
+
+/// 테스트 벡터용 고정 시드. 시드를 받는 API(`withSeed()`, `generatePrimes()`, `simulate*()`)는 모두 이 함수를 거치며,
+/// 테스트 전용이므로 `deterministic-rng` cargo 기능으로 빌드하지 않으면 항상 실패
+#[cfg(feature = "deterministic-rng")]
+fn test_seed(seed: &[u8]) -> Result<[u8; 32]> {
+  seed.try_into().map_err(|_| Error::new(Status::InvalidArg, format!("seed must be 32 bytes, got {}", seed.len())))
+}
+
+#[cfg(not(feature = "deterministic-rng"))]
+fn test_seed(_seed: &[u8]) -> Result<[u8; 32]> {
+  Err(Error::new(Status::GenericFailure, "seeded rng is disabled: build with the `deterministic-rng` cargo feature"))
+}
+
+const EXECUTION_ID_DOMAIN: &[u8] = b"cggmp.v1.ExecutionId\0";
+
+/// ExecutionId에 묶는 프로토콜 파라미터 (세션/실행 ID, 곡선, n, t 외)
+struct EidTranscript<'a> {
+  phase: &'a str,
+  /// 참여 파티 (서명자, 새 위원회 등 실행 인덱스)
+  parties: &'a [u16],
+  /// 압축 공개키 (서명은 파생 경로를 적용한 공개키, 키가 없는 프로토콜은 비어 있음). 키 ID로 해시
+  public_key: &'a [u8],
+  /// 서명할 메시지 해시 (서명 외에는 비어 있음)
+  message: &'a [u8],
+}
+
+/// 프리서명 ID 도메인 구분자
+const PRESIGNATURE_ID_DOMAIN: &[u8] = b"cggmp.v1.Presignature\0";
+
+/// 부분 서명이 해당 서명자의 프리서명 공약과 맞지 않는 서명자 (`sigma_j·Gamma == m·tilde_Delta_j + r·tilde_S_j`)
+fn invalid_partial_signers<E: Curve>(partials: &[PartialSignature<E>], public_data: &PresignaturePublicData<E>, data: &DataToSign<E>, signers: &[u16]) -> Vec<u16>
+where
+  NonZero<Point<E>>: AlwaysHasAffineX<E>,
+{
+  let r = public_data.Gamma.x().to_scalar();
+  let m = data.to_scalar();
+  partials.iter().zip(&public_data.commitments).zip(signers)
+    .filter(|((partial, c), _)| partial.sigma * public_data.Gamma != m * c.tilde_Delta + r * c.tilde_S)
+    .map(|(_, &p)| p)
+    .collect()
+}
+
+/// 길이(u32 BE)를 앞에 붙여 해시에 추가해 필드 경계가 모호하지 않도록 함
+fn hash_field(h: &mut Sha256, bytes: &[u8]) {
+  h.update((bytes.len() as u32).to_be_bytes());
+  h.update(bytes);
+}
+
+/// 식별 공개키 명단이 저장된 상태를 복원할 때 다시 받아야 하는 식별 키
+fn required_identity_key(identity_key: Option<&[u8]>) -> Result<&[u8]> {
+  identity_key.ok_or_else(|| Error::new(Status::InvalidArg, "identity key required: the state was saved after setIdentity()"))
+}
+
+/// 키 갱신/재공유에 포함된 aux 생성 등 하위 프로토콜의 ExecutionId
+fn sub_execution_id(eid: &[u8; 32], phase: &str) -> Box<[u8; 32]> {
+  let mut h = Sha256::new();
+  h.update(EXECUTION_ID_DOMAIN);
+  hash_field(&mut h, eid);
+  hash_field(&mut h, phase.as_bytes());
+  Box::new(h.finalize().into())
+}
+
+fn extend_mut<T>(v: &mut T) -> &'static mut T { unsafe { &mut *(v as *mut T) } }
+fn extend_ref<T: ?Sized>(v: &T) -> &'static T { unsafe { &*(v as *const T) } }
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use generic_ec::Scalar;
+
+  use crate::test_util::{decode, dealt_shares, executors, run, set_identities, simulate};
+
+  const MSG: &str = "68656c6c6f";
+
+  /// 2-of-3 키로 파티 0, 1이 프리서명을 만든 실행기
+  #[allow(clippy::vec_box)]
+  fn presigned() -> Vec<Box<Executor<Secp256k1>>> {
+    presigned_with(None)
+  }
+
+  /// `execution_id`가 있으면 `setExecutionId()`로 지정한 뒤 프리서명 생성
+  #[allow(clippy::vec_box)]
+  fn presigned_with(execution_id: Option<[u8; 32]>) -> Vec<Box<Executor<Secp256k1>>> {
+    let mut execs = executors::<Secp256k1>(&dealt_shares::<Secp256k1>(3, 2), 2);
+    execs.truncate(2);
+    for ex in &mut execs {
+      if let Some(id) = execution_id {
+        ex.set_execution_id(&id).unwrap();
+      }
+      ex.start_presigning().unwrap();
+    }
+    let [a, b] = &mut execs[..] else { unreachable!() };
+    run(&mut [&mut **a, &mut **b], Vec::new());
+    assert!(execs.iter().all(|ex| ex.status == "presigning_finished"));
+    execs
+  }
+
+  fn partial_signing(execs: &mut [Box<Executor<Secp256k1>>], msgs: [&str; 2]) -> [Vec<u8>; 2] {
+    let mut envs = execs.iter_mut().zip(msgs).map(|(ex, msg)| ex.sign_with_presignature(msg.to_string(), None).unwrap().swap_remove(0).to_vec());
+    [envs.next().unwrap(), envs.next().unwrap()]
+  }
+
+  fn error_message<T>(res: Result<T>) -> String {
+    res.err().expect("expected an error").reason
+  }
+
+  #[test]
+  fn presignature_signs_once_combined() {
+    let mut execs = presigned();
+    let [_, from_b] = partial_signing(&mut execs, [MSG, MSG]);
+    let report = execs[0].step(&[from_b]).unwrap();
+    assert!(report.abort.is_none());
+    assert_eq!(execs[0].status, "signing_finished");
+    let signed = execs[0].last_signature.as_ref().unwrap();
+    let data = DataToSign::<Secp256k1>::digest::<Sha256>(&hex::decode(MSG).unwrap());
+    signed.signature.verify(&signed.public_key, &data).unwrap();
+  }
+
+  #[test]
+  fn partial_signature_arriving_before_signing_is_buffered() {
+    let mut execs = presigned();
+    let from_b = execs[1].sign_with_presignature(MSG.to_string(), None).unwrap().swap_remove(0).to_vec();
+    assert!(execs[0].step(std::slice::from_ref(&from_b)).unwrap().rejected.is_empty());
+    assert_eq!(execs[0].step(&[from_b]).unwrap().rejected[0].reason, "DUPLICATE_MESSAGE");
+    assert_eq!(execs[0].status, "presigning_finished");
+
+    execs[0].sign_with_presignature(MSG.to_string(), None).unwrap();
+    assert_eq!(execs[0].status, "signing_finished");
+  }
+
+  #[test]
+  fn buffered_partial_signature_for_another_message_is_rejected_at_signing() {
+    let mut execs = presigned();
+    let from_b = execs[1].sign_with_presignature("00".to_string(), None).unwrap().swap_remove(0).to_vec();
+    execs[0].step(&[from_b]).unwrap();
+    execs[0].sign_with_presignature(MSG.to_string(), None).unwrap();
+    assert_eq!(execs[0].status, "signing_running");
+    assert_eq!(execs[0].errors.last().unwrap().reason, "TX_CONTEXT_MISMATCH");
+  }
+
+  #[test]
+  fn presignature_does_not_sign_a_32_byte_message_without_hash_mode() {
+    let mut execs = presigned();
+    let prehash = "11".repeat(32);
+    assert!(error_message(execs[0].sign_with_presignature(prehash.clone(), None)).contains("pass hashMode explicitly"));
+    let options = SigningOptions { hash_mode: Some("sha256".to_string()), ..Default::default() };
+    assert!(execs[0].sign_with_presignature(prehash, Some(options)).is_ok());
+  }
+
+  #[test]
+  fn export_takes_the_presignature_out() {
+    let mut execs = presigned();
+    let exported = execs[0].export_presignature().unwrap();
+    assert!(execs[0].presignature.is_none());
+    assert_eq!(error_message(execs[0].export_presignature()), "presignature not ready");
+    assert_eq!(error_message(execs[0].sign_with_presignature(MSG.to_string(), None)), "presignature missing");
+
+    execs[0].import_presignature(exported.to_vec().into()).unwrap();
+    execs[0].sign_with_presignature(MSG.to_string(), None).unwrap();
+    assert_eq!(error_message(execs[0].import_presignature(exported.to_vec().into())), "presignature was already used");
+  }
+
+  #[test]
+  fn import_rejects_presignature_bound_to_another_key_or_execution() {
+    let mut execs = presigned();
+    let exported = execs[0].export_presignature().unwrap();
+
+    let mut other_key: serde_json::Value = serde_json::from_slice(&exported).unwrap();
+    other_key["publicKey"] = serde_json::to_value(Point::<Secp256k1>::generator().to_point()).unwrap();
+    let other_key = serde_json::to_vec(&other_key).unwrap();
+    assert_eq!(error_message(execs[0].import_presignature(other_key.into())), "presignature was generated for a different key");
+
+    let shares = dealt_shares::<Secp256k1>(3, 2);
+    let mut other_session = Executor::<Secp256k1>::new("other".to_string(), "execution".to_string(), 0, 2, 3).unwrap();
+    other_session.import_keyshare(shares[0].clone().into()).unwrap();
+    assert_eq!(error_message(other_session.import_presignature(exported.to_vec().into())), "presignature was generated in a different session");
+
+    let mut other_execution: serde_json::Value = serde_json::from_slice(&exported).unwrap();
+    other_execution["derivedFrom"] = "other".into();
+    let other_execution = serde_json::to_vec(&other_execution).unwrap();
+    assert_eq!(error_message(execs[0].import_presignature(other_execution.into())), "presignature was generated in a different execution");
+  }
+
+  #[test]
+  fn import_does_not_read_a_pending_execution_id() {
+    let mut execs = presigned();
+    let exported = execs[0].export_presignature().unwrap();
+    execs[0].set_execution_id(&[7; 32]).unwrap();
+    execs[0].import_presignature(exported.to_vec().into()).unwrap();
+
+    // setExecutionId()로 만든 프리서명은 기록된 ExecutionId를 그대로 사용
+    let mut execs = presigned_with(Some([9; 32]));
+    let exported = execs[0].export_presignature().unwrap();
+    assert!(serde_json::from_slice::<serde_json::Value>(&exported).unwrap()["derivedFrom"].is_null());
+    execs[0].import_presignature(exported.to_vec().into()).unwrap();
+    let [_, from_b] = partial_signing(&mut execs, [MSG, MSG]);
+    assert!(execs[0].step(&[from_b]).unwrap().abort.is_none());
+    assert_eq!(execs[0].status, "signing_finished");
+  }
+
+  /// 식별 키를 설정한 키 생성에서 파티 0이 P2P Envelope를 보낸 시점까지 (`seed`가 있으면 파티마다 다른 고정 시드)
+  #[allow(clippy::vec_box)]
+  fn sealed_keygen(seed: Option<u8>) -> Vec<Box<Executor<Secp256k1>>> {
+    let mut execs: Vec<Box<Executor<Secp256k1>>> = (0..3).map(|i| Box::new(Executor::new("session".to_string(), "execution".to_string(), i, 2, 3).unwrap())).collect();
+    if let Some(seed) = seed {
+      for (i, ex) in (0..).zip(execs.iter_mut()) {
+        ex.seed_rng(&[seed + i; 32]).unwrap();
+      }
+    }
+    set_identities(&mut execs);
+    let round1: Vec<Vec<u8>> = execs.iter_mut().flat_map(|ex| {
+      ex.start_keygen().unwrap();
+      ex.step::<Vec<u8>>(&[]).unwrap().outgoing
+    }).map(|b| b.to_vec()).collect();
+    execs[0].step(&round1).unwrap();
+    assert!(execs[0].sent.iter().any(|b| decode(b).to_parties.len() == 1));
+    execs
+  }
+
+  #[test]
+  fn restore_keeps_the_recorded_sealed_envelopes() {
+    // P2P payload는 OsRng로 암호화되므로 재실행한 Envelope와 바이트가 다름
+    let execs = sealed_keygen(None);
+    let keys: Vec<[u8; 32]> = (1..=3).map(|i| [i; 32]).collect();
+    let saved = SavedState::decode(&execs[0].serialize_state().unwrap()).unwrap();
+    let restored = Executor::<Secp256k1>::restore(saved, Some(&keys[0])).unwrap();
+    assert_eq!(restored.sent, execs[0].sent);
+
+    let mut saved = SavedState::decode(&execs[0].serialize_state().unwrap()).unwrap();
+    let broadcast = saved.sent.iter_mut().find(|b| decode(b).to_parties.is_empty()).unwrap();
+    let mut env = decode(broadcast);
+    env.session_id = "other".to_string();
+    *broadcast = env.encode_to_vec();
+    assert_eq!(error_message(Executor::<Secp256k1>::restore(saved, Some(&keys[0]))), "state replay diverged from the recorded outgoing envelopes");
+  }
+
+  #[cfg(feature = "deterministic-rng")]
+  #[test]
+  fn seeded_executors_seal_with_a_derived_rng() {
+    let sent = sealed_keygen(Some(1))[0].sent.clone();
+    assert_eq!(sealed_keygen(Some(1))[0].sent, sent);
+    // 암호화 RNG는 상태 머신 RNG와 별개이므로 식별 키가 없어도 같은 메시지를 만듦
+    let mut plain: Vec<Box<Executor<Secp256k1>>> = (0..3).map(|i| Box::new(Executor::new("session".to_string(), "execution".to_string(), i, 2, 3).unwrap())).collect();
+    for (i, ex) in (0..).zip(plain.iter_mut()) {
+      ex.seed_rng(&[1 + i; 32]).unwrap();
+      ex.start_keygen().unwrap();
+    }
+    let round1: Vec<Vec<u8>> = plain.iter_mut().flat_map(|ex| ex.step::<Vec<u8>>(&[]).unwrap().outgoing).map(|b| b.to_vec()).collect();
+    plain[0].step(&round1).unwrap();
+    let broadcasts = |sent: &[Vec<u8>]| sent.iter().map(|b| decode(b)).filter(|env| env.to_parties.is_empty()).map(|mut env| { env.signature.clear(); env }).collect::<Vec<_>>();
+    assert_eq!(broadcasts(&plain[0].sent), broadcasts(&sent));
+  }
+
+  #[test]
+  fn derived_execution_id_is_not_reused() {
+    let execs = presigned();
+    let saved = SavedState::decode(&execs[0].serialize_state().unwrap()).unwrap();
+    let mut restored = Executor::<Secp256k1>::restore(saved, None).unwrap();
+    assert_eq!(error_message(restored.start_presigning()), "execution id was already used by this executor: set a fresh one with setExecutionId() before retrying");
+    restored.set_execution_id(&[7; 32]).unwrap();
+    restored.start_presigning().unwrap();
+  }
+
+  #[test]
+  fn restore_does_not_bring_back_a_presignature() {
+    let execs = presigned();
+    let saved = SavedState::decode(&execs[0].serialize_state().unwrap()).unwrap();
+    let restored = Executor::<Secp256k1>::restore(saved, None).unwrap();
+    assert_eq!(restored.status, "presigning_finished");
+    assert!(restored.presignature.is_none());
+  }
+
+  #[test]
+  fn saved_state_needs_the_identity_key_again() {
+    let mut ex = executors::<Secp256k1>(&dealt_shares::<Secp256k1>(3, 2), 2).swap_remove(0);
+    let keys: Vec<[u8; 32]> = (1..=3).map(|i| [i; 32]).collect();
+    let roster: Vec<Vec<u8>> = keys.iter().map(|k| identity::public_key(k).to_vec()).collect();
+    ex.set_identity(&keys[0], &roster).unwrap();
+    let state = ex.serialize_state().unwrap();
+    assert!(!state.windows(32).any(|w| w == keys[0]));
+
+    let restore = |key: Option<&[u8]>| Executor::<Secp256k1>::restore(SavedState::decode(&state).unwrap(), key);
+    assert_eq!(error_message(restore(None)), "identity key required: the state was saved after setIdentity()");
+    assert_eq!(error_message(restore(Some(&keys[1]))), "identity key does not match the roster entry of party 0");
+    let restored = restore(Some(&keys[0])).unwrap();
+    assert_eq!(restored.identity.as_ref().unwrap().roster(), roster);
+
+    // 프로토콜 시작 후에는 체크포인트에 명단만 남음
+    ex.start_presigning().unwrap();
+    let state = ex.serialize_state().unwrap();
+    assert!(!state.windows(32).any(|w| w == keys[0]));
+    assert!(Executor::<Secp256k1>::restore(SavedState::decode(&state).unwrap(), Some(&keys[0])).unwrap().identity.is_some());
+  }
+
+  #[test]
+  fn invalid_partial_signature_blames_its_signer() {
+    let mut execs = presigned();
+    let [_, from_b] = partial_signing(&mut execs, [MSG, MSG]);
+    let mut env = decode(&from_b);
+    let Some(Payload::Signing(signing)) = env.payload.as_mut() else { panic!("expected a signing payload") };
+    let mut partial: PartialSignature<Secp256k1> = bincode::deserialize(&signing.payload).unwrap();
+    partial.sigma += generic_ec::Scalar::one();
+    signing.payload = bincode::serialize(&partial).unwrap();
+
+    let abort = execs[0].step(&[env.encode_to_vec()]).unwrap().abort.unwrap();
+    assert_eq!(abort.reason, "InvalidPartialSignature");
+    assert_eq!(abort.blamed_parties, vec![1]);
+    assert_eq!(execs[0].status, "signing_aborted");
+  }
+
+  #[test]
+  fn duplicate_partial_signature_is_rejected() {
+    let mut execs = presigned();
+    let [_, from_b] = partial_signing(&mut execs, [MSG, MSG]);
+    let report = execs[0].step(&[from_b.clone(), from_b]).unwrap();
+    assert_eq!(report.rejected.len(), 1);
+    assert_eq!(report.rejected[0].reason, "DUPLICATE_MESSAGE");
+    assert_eq!(execs[0].status, "signing_finished");
+  }
+
+  #[test]
+  fn partial_signature_for_another_message_is_rejected() {
+    let mut execs = presigned();
+    let [_, from_b] = partial_signing(&mut execs, [MSG, "00"]);
+    let report = execs[0].step(&[from_b]).unwrap();
+    assert_eq!(report.rejected.len(), 1);
+    assert_eq!(report.rejected[0].reason, "TX_CONTEXT_MISMATCH");
+    assert_eq!(execs[0].status, "signing_running");
+  }
+
+  type KeygenMsg = keygen_msg::Msg<Secp256k1, SecLevel, Sha256>;
+
+  /// `parse_abort_debug`가 해석하는 Debug 출력의 cggmp24 버전
+  const CGGMP24_DEBUG_FORMAT: &str = "0.7.0-alpha.3";
+
+  /// cggmp24 버전을 바꾸면 실패: 아래 중단 테스트로 `parse_abort_debug`를 새 Debug 출력에 맞춘 뒤 `CGGMP24_DEBUG_FORMAT`을 고침
+  #[test]
+  fn abort_parsing_matches_the_pinned_cggmp24() {
+    let lock = include_str!("../Cargo.lock");
+    for name in ["cggmp24", "cggmp24-keygen"] {
+      let version = lock.split(&format!("name = \"{name}\"\nversion = \"")).nth(1).and_then(|rest| rest.split('"').next());
+      assert_eq!(version, Some(CGGMP24_DEBUG_FORMAT), "{name} changed: check parse_abort_debug against its error output");
+    }
+  }
+
+  /// 2-of-3 키 생성을 `tamper`로 변조해 실행하고 각 파티의 중단 정보를 반환
+  fn keygen_aborts(reliable: bool, tamper: impl Fn(u16, u16, &mut KeygenMsg)) -> Vec<Option<ProtocolAbort>> {
+    let mut rngs: Vec<StdRng> = (0..3).map(StdRng::seed_from_u64).collect();
+    let sms = (0..3).zip(&mut rngs).map(|(i, rng)| {
+      KeygenBuilder::<Secp256k1>::new(ExecutionId::new(&[3; 32]), i, 3).set_threshold(2).enforce_reliable_broadcast(reliable).into_state_machine(rng)
+    }).collect();
+    simulate(sms, tamper).into_iter()
+      .map(|out| out?.err().map(|e| ProtocolAbort::from_debug("keygen", format!("{e:?}"), &[])))
+      .collect()
+  }
+
+  fn assert_aborted(abort: &Option<ProtocolAbort>, reason: &str, blamed: &[u32]) {
+    let abort = abort.as_ref().expect("expected an abort");
+    assert_eq!((abort.kind.as_str(), abort.reason.as_str(), abort.blamed_parties.as_slice()), ("ABORTED", reason, blamed), "{}", abort.message);
+  }
+
+  #[test]
+  fn unconfirmed_reshare_blames_nobody() {
+    let abort = ProtocolAbort::from_reshare("key_refresh", &reshare::Error::NotConfirmed(vec![2]));
+    assert_eq!((abort.kind.as_str(), abort.reason.as_str(), abort.blamed_parties.as_slice()), ("ABORTED", "NotConfirmed", &[][..]));
+    assert_eq!(abort.message, "reshare not confirmed, mismatch from parties [2]");
+  }
+
+  #[test]
+  fn inconsistent_keygen_share_blames_its_dealer() {
+    let aborts = keygen_aborts(false, |from, to, msg| {
+      if let (0, 1, KeygenMsg::Round2Uni(m)) = (from, to, msg) {
+        m.sigma += Scalar::one();
+      }
+    });
+    assert_aborted(&aborts[1], "FeldmanVerificationFailed", &[0]);
+  }
+
+  #[test]
+  fn wrong_keygen_decommitment_blames_its_sender() {
+    let aborts = keygen_aborts(false, |from, _, msg| {
+      if let (2, KeygenMsg::Round2Broad(m)) = (from, msg) {
+        m.decommit.as_mut()[0] ^= 1;
+      }
+    });
+    assert_aborted(&aborts[0], "InvalidDecommitment", &[2]);
+    assert_aborted(&aborts[1], "InvalidDecommitment", &[2]);
+  }
+
+  /// 해시가 다른 파티를 모두 지목하므로 수신자 1은 속인 파티 2와 정직한 파티 0을 구분하지 못함
+  #[test]
+  fn unreliable_keygen_broadcast_blames_parties_with_a_different_hash() {
+    let aborts = keygen_aborts(true, |from, to, msg| {
+      if let (2, 1, KeygenMsg::Round1(m)) = (from, to, msg) {
+        m.commitment[0] ^= 1;
+      }
+    });
+    assert_aborted(&aborts[1], "Round1NotReliable", &[0, 2]);
+    assert_aborted(&aborts[0], "Round1NotReliable", &[1]);
+  }
+
+  /// 2-of-3 키로 파티 0, 2가 서명하며 두 번째 서명자(전역 2)의 Round 1a 메시지를 모두에게 변조해 전달하고 파티 0의 중단 정보를 반환
+  fn signing_abort(reliable: bool) -> Option<ProtocolAbort> {
+    let execs = executors::<Secp256k1>(&dealt_shares::<Secp256k1>(3, 2), 2);
+    let signers = [0u16, 2];
+    let data = DataToSign::<Secp256k1>::digest::<Sha256>(b"abort");
+    let mut rngs: Vec<StdRng> = (0..2).map(StdRng::seed_from_u64).collect();
+    let sms = (0..).zip(&signers).zip(&mut rngs).map(|((i, &p), rng)| {
+      let ks = execs[usize::from(p)].keyshare.as_ref().unwrap();
+      cggmp24::signing(ExecutionId::new(&[4; 32]), i, &signers, ks).enforce_reliable_broadcast(reliable).sign_sync(rng, &data)
+    }).collect();
+    let outputs = simulate(sms, |from, _, msg| {
+      if let (1, cggmp24::signing::msg::Msg::Round1a(m)) = (from, msg) {
+        m.Y += Point::generator();
+      }
+    });
+    outputs[0].as_ref().unwrap().as_ref().err().map(|e| ProtocolAbort::from_debug("signing", format!("{e:?}"), &signers))
+  }
+
+  #[test]
+  fn signing_abort_blames_global_party_index() {
+    assert_aborted(&signing_abort(false), "EncProofOfK", &[2]);
+    assert_aborted(&signing_abort(true), "Round1aNotReliable", &[2]);
+  }
+
+  #[test]
+  fn key_refresh_needs_an_identity_and_keeps_the_public_key() {
+    let shares = dealt_shares::<Secp256k1>(3, 2);
+    let primes = crate::test_util::primes(3);
+    let mut execs = executors::<Secp256k1>(&shares, 2);
+    assert_eq!(error_message(execs[0].start_key_refresh_with_primes(primes[0].clone().into())), "key refresh requires setIdentity(): new shares are sent point-to-point");
+
+    set_identities(&mut execs);
+    for (ex, p) in execs.iter_mut().zip(&primes) {
+      ex.start_key_refresh_with_primes(p.clone().into()).unwrap();
+    }
+    let mut refs: Vec<&mut Executor<Secp256k1>> = execs.iter_mut().map(|ex| &mut **ex).collect();
+    assert!(run(&mut refs, Vec::new()).iter().all(|report| report.abort.is_none()));
+    for (ex, old) in execs.iter_mut().zip(&shares) {
+      let old = parse_key_export::<KeyShareWithLevel<Secp256k1>>(old, Content::KeyShare, None).unwrap();
+      assert_eq!(ex.status, "key_refresh_finished");
+      // 커밋 전에는 기존 쉐어를 그대로 사용
+      assert_eq!(ex.export_keyshare().unwrap().to_vec(), serde_json::to_vec(&old).unwrap());
+      ex.commit_key_refresh().unwrap();
+      assert_eq!(error_message(ex.commit_key_refresh()), "no refreshed key share to commit");
+      let new = ex.keyshare.as_ref().unwrap();
+      assert_eq!(new.core.shared_public_key, old.core.shared_public_key);
+      assert_ne!(AsRef::<Scalar<Secp256k1>>::as_ref(&new.core.x), AsRef::<Scalar<Secp256k1>>::as_ref(&old.core.x));
+    }
+  }
+
+  #[test]
+  fn uncommitted_refresh_is_dropped_by_a_retry() {
+    let shares = dealt_shares::<Secp256k1>(3, 2);
+    let primes = crate::test_util::primes(3);
+    let mut execs = executors::<Secp256k1>(&shares, 2);
+    set_identities(&mut execs);
+    for (ex, p) in execs.iter_mut().zip(&primes) {
+      ex.start_key_refresh_with_primes(p.clone().into()).unwrap();
+    }
+    let mut refs: Vec<&mut Executor<Secp256k1>> = execs.iter_mut().map(|ex| &mut **ex).collect();
+    run(&mut refs, Vec::new());
+    assert!(serde_json::from_str::<serde_json::Value>(&execs[0].snapshot().unwrap()).unwrap()["hasPendingKeyshare"].as_bool().unwrap());
+
+    // 한 파티라도 끝나지 않았으면 커밋하지 않고 기존 쉐어로 다시 갱신 (재시도에는 새 ExecutionId가 필요)
+    execs[0].set_execution_id(&[7; 32]).unwrap();
+    execs[0].start_key_refresh_with_primes(primes[0].clone().into()).unwrap();
+    assert!(execs[0].pending_keyshare.is_none());
+    assert_eq!(error_message(execs[0].commit_key_refresh()), "cannot commit the refreshed key share while a protocol is running");
+    assert_eq!(execs[0].export_keyshare().unwrap().to_vec(), serde_json::to_vec(&parse_key_export::<KeyShareWithLevel<Secp256k1>>(&shares[0], Content::KeyShare, None).unwrap()).unwrap());
+  }
+
+  /// 실행 인덱스 순서의 실행기들이 2-of-3 키(`old`, 기존 위원회는 실행 인덱스 0..3)를 `new_indices`의 새 위원회로 재분배
+  #[allow(clippy::vec_box)]
+  fn reshare(execs: &mut [Box<Executor<Secp256k1>>], old: &[Vec<u8>], new_indices: &[Option<u16>]) {
+    let key_info = serde_json::to_vec(&parse_key_export::<KeyShareWithLevel<Secp256k1>>(&old[0], Content::KeyShare, None).unwrap().core.key_info).unwrap();
+    let old_indices: Vec<Option<u16>> = (0..execs.len() as u16).map(|i| (i < 3).then_some(i)).collect();
+    let primes = crate::test_util::primes(4);
+    let request = |i: usize| ReshareRequest {
+      old_indices: old_indices.clone(), new_indices: new_indices.to_vec(),
+      key_share: old.get(i).cloned(), key_info: (i >= old.len()).then(|| key_info.clone()),
+      primes: new_indices[i].map(|j| primes[usize::from(j)].clone()),
+    };
+    assert_eq!(error_message(execs[0].start_reshare(request(0))), "reshare requires setIdentity(): new shares are sent point-to-point");
+    set_identities(execs);
+    for (i, ex) in execs.iter_mut().enumerate() {
+      ex.start_reshare(request(i)).unwrap();
+    }
+    let mut refs: Vec<&mut Executor<Secp256k1>> = execs.iter_mut().map(|ex| &mut **ex).collect();
+    assert!(run(&mut refs, Vec::new()).iter().all(|report| report.abort.is_none()));
+    assert!(execs.iter().all(|ex| ex.status == "reshare_finished"));
+  }
+
+  #[test]
+  fn reshare_to_a_larger_committee_keeps_the_public_key_and_signs() {
+    // 파티 2가 빠지고 두 파티가 합류해 2-of-3에서 3-of-4로
+    let old = dealt_shares::<Secp256k1>(3, 2);
+    let public_key = *parse_key_export::<KeyShareWithLevel<Secp256k1>>(&old[0], Content::KeyShare, None).unwrap().core.shared_public_key;
+    let mut execs: Vec<Box<Executor<Secp256k1>>> = (0..5).map(|i| Box::new(Executor::new("session".to_string(), "execution".to_string(), i, 3, 5).unwrap())).collect();
+    reshare(&mut execs, &old, &[Some(0), Some(1), None, Some(2), Some(3)]);
+
+    let shares: Vec<Vec<u8>> = [0, 1, 3, 4].iter().map(|&i| {
+      execs[i].commit_key_refresh().unwrap();
+      execs[i].export_keyshare().unwrap().to_vec()
+    }).collect();
+    for (i, ks) in (0..).zip(&shares) {
+      let ks: KeyShareWithLevel<Secp256k1> = serde_json::from_slice(ks).unwrap();
+      assert_eq!((ks.core.i, ks.min_signers(), ks.n(), *ks.core.shared_public_key), (i, 3, 4, public_key));
+    }
+    let sig = sim::signing::<Secp256k1>(&shares, &[3, 0, 2], b"reshared", &SigningOptions::default(), &mut sim::SimRng::new(None)).unwrap();
+    sig.verify(&public_key, &DataToSign::<Secp256k1>::digest::<Sha256>(b"reshared")).unwrap();
+    assert_eq!(error_message(sim::signing::<Secp256k1>(&shares, &[0, 1], b"reshared", &SigningOptions::default(), &mut sim::SimRng::new(None))), "expected 3 signers, got 2");
+  }
+
+  #[test]
+  fn departing_member_keeps_its_share_until_discarded() {
+    // 파티 2가 빠져 2-of-2로. 실행기 파라미터가 기존 키와 같아 기존 쉐어를 가져온 상태로 재분배
+    let old = dealt_shares::<Secp256k1>(3, 2);
+    let mut execs = executors::<Secp256k1>(&old, 2);
+    reshare(&mut execs, &old, &[Some(0), Some(1), None]);
+    // 남는 파티도 새 위원회가 모두 끝났는지 모르므로 커밋 전까지 기존 쉐어를 사용
+    for ex in &mut execs[..2] {
+      assert_eq!((ex.keyshare.as_ref().unwrap().n(), ex.pending_keyshare.as_ref().unwrap().n()), (3, 2));
+      ex.commit_key_refresh().unwrap();
+      assert_eq!(ex.keyshare.as_ref().unwrap().n(), 2);
+    }
+    assert_eq!(error_message(execs[2].commit_key_refresh()), "no refreshed key share to commit");
+    assert_eq!(execs[2].export_keyshare().unwrap().to_vec(), serde_json::to_vec(&parse_key_export::<KeyShareWithLevel<Secp256k1>>(&old[2], Content::KeyShare, None).unwrap()).unwrap());
+
+    execs[2].discard_keyshare().unwrap();
+    assert_eq!(error_message(execs[2].export_keyshare()), "keyshare not ready");
+    assert_eq!(error_message(execs[2].export_aux_info()), "aux info not ready");
+    assert_eq!(execs[2].status, "init");
+
+    let mut running = executors::<Secp256k1>(&old, 2).swap_remove(0);
+    running.start_presigning().unwrap();
+    assert_eq!(error_message(running.discard_keyshare()), "cannot discard the key share while a protocol is running");
+  }
+
+  #[test]
+  fn stark_message_hash_takes_felts_below_2_251() {
+    let mut below = [0xff; 32];
+    below[0] = 0x07;
+    assert_eq!(stark_message_hash(&below).unwrap(), Scalar::<Stark>::from_be_bytes_mod_order(below));
+    let mut at = [0; 32];
+    at[0] = 0x08;
+    assert_eq!(error_message(stark_message_hash(&at)), "stark message hash must be less than 2^251");
+    // 32바이트보다 짧은 메시지는 앞을 0으로 채운 felt
+    assert_eq!(stark_message_hash(&[1, 2]).unwrap(), Scalar::<Stark>::from(0x0102u64));
+  }
+
+  #[test]
+  fn long_stark_message_is_hashed_with_sn_keccak() {
+    // sn_keccak: Keccak256(msg) = 3e3115..의 하위 250비트
+    let expected = hex::decode("02311529755371b8bf04fa1e6bf0b3cc1037fe4e3832dfb3be53ba8e40c8472d").unwrap();
+    assert_eq!(stark_message_hash(b"a starknet message longer than thirty-two bytes").unwrap(), Scalar::<Stark>::from_be_bytes_mod_order(expected));
+  }
+
+  /// 딜러가 나눈 2-of-3 키로 파티 0, 2가 서명한 서명이 `verifySignature()`로 검증되는지
+  fn assert_signs_and_verifies<E: SupportedCurve>(msg: &str, hash_mode: Option<&str>)
+  where
+    Point<E>: HasAffineX<E>,
+    NonZero<Point<E>>: AlwaysHasAffineX<E>,
+  {
+    let shares = dealt_shares::<E>(3, 2);
+    let public_key = hex::encode(parse_key_export::<KeyShareWithLevel<E>>(&shares[0], Content::KeyShare, None).unwrap().core.shared_public_key.to_bytes(true));
+    let options = SigningOptions { hash_mode: hash_mode.map(str::to_string), ..Default::default() };
+    let sig = signature::compact(&sim::signing::<E>(&shares, &[0, 2], &hex::decode(msg).unwrap(), &options, &mut sim::SimRng::new(None)).unwrap());
+    let hash_mode = HashMode::parse(hash_mode).unwrap();
+    assert!(verify_signature_on::<E>(&public_key, msg, &sig, hash_mode, None).unwrap());
+    assert!(!verify_signature_on::<E>(&public_key, "00", &sig, hash_mode, None).unwrap());
+  }
+
+  #[test]
+  fn stark_shares_sign_a_felt() {
+    assert_signs_and_verifies::<Stark>(&format!("07{}", "ab".repeat(31)), None);
+  }
+
+  #[test]
+  fn p256_shares_sign_and_verify() {
+    let msg = hex::encode(b"webauthn challenge");
+    assert_signs_and_verifies::<Secp256r1>(&msg, Some("sha256"));
+
+    // secp256r1 실행기끼리 서명한 결과도 P-256 서명으로 검증
+    let mut execs = executors::<Secp256r1>(&dealt_shares::<Secp256r1>(3, 2), 2);
+    execs.truncate(2);
+    for ex in &mut execs {
+      ex.start_signing(msg.clone(), Some(SigningOptions { hash_mode: Some("sha256".to_string()), ..Default::default() })).unwrap();
+    }
+    let [a, b] = &mut execs[..] else { unreachable!() };
+    run(&mut [&mut **a, &mut **b], Vec::new());
+    assert_eq!(execs[0].status, "signing_finished");
+    let signed = execs[0].last_signature.as_ref().unwrap();
+    let public_key = hex::encode(signed.public_key.to_bytes(true));
+    assert!(verify_signature_on::<Secp256r1>(&public_key, &msg, &signature::compact(&signed.signature), HashMode::Sha256, None).unwrap());
+  }
+
+  /// 고정 시드로 실행기 3개가 키 생성한 Envelope와 공개키, 고정 시드로 시뮬레이션한 키 생성 결과의 해시
+  #[cfg(feature = "deterministic-rng")]
+  fn golden_keygen() -> serde_json::Value {
+    let mut execs: Vec<Box<Executor<Secp256k1>>> = (0..3u8).map(|i| {
+      let mut ex = Box::new(Executor::new("golden".to_string(), "keygen".to_string(), i.into(), 2, 3).unwrap());
+      ex.seed_rng(&[i + 1; 32]).unwrap();
+      ex.start_keygen().unwrap();
+      ex
+    }).collect();
+    let mut refs: Vec<&mut Executor<Secp256k1>> = execs.iter_mut().map(|ex| &mut **ex).collect();
+    let envelopes: Vec<String> = run(&mut refs, Vec::new()).iter().flat_map(|report| report.outgoing.iter().map(|env| hex::encode(&env[..]))).collect();
+    assert!(execs.iter().all(|ex| ex.status == "keygen_finished"));
+    let public_key = execs[0].core_keyshare.as_ref().unwrap().shared_public_key.to_bytes(true);
+    let simulated = sim::keygen::<Secp256k1>(3, 2, None, &mut sim::SimRng::new(Some([9; 32]))).unwrap();
+    serde_json::json!({
+      "envelopes": envelopes,
+      "publicKey": hex::encode(&public_key[..]),
+      "simulatedKeygenSha256": simulated.iter().map(|ks| hex::encode(Sha256::digest(ks))).collect::<Vec<_>>(),
+    })
+  }
+
+  /// 골든 픽스처를 바꾸는 변경(프로토콜 메시지, 실행 ID 유도, 시드 유도 등)이면
+  /// `UPDATE_GOLDEN=1 cargo test --features deterministic-rng golden`으로 다시 생성해 함께 커밋
+  #[cfg(feature = "deterministic-rng")]
+  #[test]
+  fn seeded_keygen_matches_the_golden_fixture() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/testdata/golden_keygen.json");
+    let actual = golden_keygen();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+      std::fs::write(path, serde_json::to_string_pretty(&actual).unwrap() + "\n").unwrap();
+    }
+    let expected: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert!(actual == expected, "seeded keygen no longer matches {path}");
+  }
+}
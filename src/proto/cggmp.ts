@@ -62,6 +62,7 @@ export enum Round {
   KEYGEN = 2,
   SIGNING = 3,
   PRESIGNATURE = 4,
+  KEY_REFRESH = 5,
   ERROR = 9,
   UNRECOGNIZED = -1,
 }
@@ -83,6 +84,9 @@ export function roundFromJSON(object: any): Round {
     case 4:
     case "PRESIGNATURE":
       return Round.PRESIGNATURE;
+    case 5:
+    case "KEY_REFRESH":
+      return Round.KEY_REFRESH;
     case 9:
     case "ERROR":
       return Round.ERROR;
@@ -105,6 +109,8 @@ export function roundToJSON(object: Round): string {
       return "SIGNING";
     case Round.PRESIGNATURE:
       return "PRESIGNATURE";
+    case Round.KEY_REFRESH:
+      return "KEY_REFRESH";
     case Round.ERROR:
       return "ERROR";
     case Round.UNRECOGNIZED:
@@ -120,13 +126,18 @@ export interface Envelope {
   round: Round;
   fromParty: number;
   toParties: number[];
-  meta: Meta | undefined;
+  meta:
+    | Meta
+    | undefined;
+  /** 발신 파티 식별 키의 Ed25519 서명 (도메인 구분자 + signature를 비운 Envelope 인코딩), 인증을 쓰지 않으면 비어 있음 */
+  signature: Uint8Array;
   auxInfo?: AuxInfo | undefined;
   keygen?: Keygen | undefined;
   signing?: Signing | undefined;
   presignature?: Presignature | undefined;
   error?: Error | undefined;
   status?: Status | undefined;
+  keyRefresh?: KeyRefresh | undefined;
 }
 
 export interface Meta {
@@ -163,10 +174,20 @@ export interface Presignature {
   payload: Uint8Array;
 }
 
+export interface KeyRefresh {
+  /** 쉐어 재분배 또는 aux 재생성 메시지 */
+  payload: Uint8Array;
+}
+
 export interface Error {
+  /** 1: 악의적 파티에 의한 중단, 2: I/O 오류, 3: 내부 오류 */
   code: number;
   message: string;
   retriable: boolean;
+  /** 중단 사유 (예: "EncProofOfK", "InvalidDecommitment") */
+  reason: string;
+  /** 책임 파티 (전역 인덱스) */
+  blamedParties: number[];
 }
 
 export interface Status {
@@ -187,12 +208,14 @@ function createBaseEnvelope(): Envelope {
     fromParty: 0,
     toParties: [],
     meta: undefined,
+    signature: new Uint8Array(0),
     auxInfo: undefined,
     keygen: undefined,
     signing: undefined,
     presignature: undefined,
     error: undefined,
     status: undefined,
+    keyRefresh: undefined,
   };
 }
 
@@ -221,6 +244,9 @@ export const Envelope = {
     if (message.meta !== undefined) {
       Meta.encode(message.meta, writer.uint32(58).fork()).ldelim();
     }
+    if (message.signature.length !== 0) {
+      writer.uint32(66).bytes(message.signature);
+    }
     if (message.auxInfo !== undefined) {
       AuxInfo.encode(message.auxInfo, writer.uint32(82).fork()).ldelim();
     }
@@ -239,6 +265,9 @@ export const Envelope = {
     if (message.status !== undefined) {
       Status.encode(message.status, writer.uint32(122).fork()).ldelim();
     }
+    if (message.keyRefresh !== undefined) {
+      KeyRefresh.encode(message.keyRefresh, writer.uint32(130).fork()).ldelim();
+    }
     return writer;
   },
 
@@ -308,6 +337,13 @@ export const Envelope = {
 
           message.meta = Meta.decode(reader, reader.uint32());
           continue;
+        case 8:
+          if (tag !== 66) {
+            break;
+          }
+
+          message.signature = reader.bytes();
+          continue;
         case 10:
           if (tag !== 82) {
             break;
@@ -350,6 +386,13 @@ export const Envelope = {
 
           message.status = Status.decode(reader, reader.uint32());
           continue;
+        case 16:
+          if (tag !== 130) {
+            break;
+          }
+
+          message.keyRefresh = KeyRefresh.decode(reader, reader.uint32());
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -370,12 +413,14 @@ export const Envelope = {
         ? object.toParties.map((e: any) => globalThis.Number(e))
         : [],
       meta: isSet(object.meta) ? Meta.fromJSON(object.meta) : undefined,
+      signature: isSet(object.signature) ? bytesFromBase64(object.signature) : new Uint8Array(0),
       auxInfo: isSet(object.auxInfo) ? AuxInfo.fromJSON(object.auxInfo) : undefined,
       keygen: isSet(object.keygen) ? Keygen.fromJSON(object.keygen) : undefined,
       signing: isSet(object.signing) ? Signing.fromJSON(object.signing) : undefined,
       presignature: isSet(object.presignature) ? Presignature.fromJSON(object.presignature) : undefined,
       error: isSet(object.error) ? Error.fromJSON(object.error) : undefined,
       status: isSet(object.status) ? Status.fromJSON(object.status) : undefined,
+      keyRefresh: isSet(object.keyRefresh) ? KeyRefresh.fromJSON(object.keyRefresh) : undefined,
     };
   },
 
@@ -402,6 +447,9 @@ export const Envelope = {
     if (message.meta !== undefined) {
       obj.meta = Meta.toJSON(message.meta);
     }
+    if (message.signature.length !== 0) {
+      obj.signature = base64FromBytes(message.signature);
+    }
     if (message.auxInfo !== undefined) {
       obj.auxInfo = AuxInfo.toJSON(message.auxInfo);
     }
@@ -420,6 +468,9 @@ export const Envelope = {
     if (message.status !== undefined) {
       obj.status = Status.toJSON(message.status);
     }
+    if (message.keyRefresh !== undefined) {
+      obj.keyRefresh = KeyRefresh.toJSON(message.keyRefresh);
+    }
     return obj;
   },
 
@@ -435,6 +486,7 @@ export const Envelope = {
     message.fromParty = object.fromParty ?? 0;
     message.toParties = object.toParties?.map((e) => e) || [];
     message.meta = (object.meta !== undefined && object.meta !== null) ? Meta.fromPartial(object.meta) : undefined;
+    message.signature = object.signature ?? new Uint8Array(0);
     message.auxInfo = (object.auxInfo !== undefined && object.auxInfo !== null)
       ? AuxInfo.fromPartial(object.auxInfo)
       : undefined;
@@ -451,6 +503,9 @@ export const Envelope = {
     message.status = (object.status !== undefined && object.status !== null)
       ? Status.fromPartial(object.status)
       : undefined;
+    message.keyRefresh = (object.keyRefresh !== undefined && object.keyRefresh !== null)
+      ? KeyRefresh.fromPartial(object.keyRefresh)
+      : undefined;
     return message;
   },
 };
@@ -873,8 +928,65 @@ export const Presignature = {
   },
 };
 
+function createBaseKeyRefresh(): KeyRefresh {
+  return { payload: new Uint8Array(0) };
+}
+
+export const KeyRefresh = {
+  encode(message: KeyRefresh, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.payload.length !== 0) {
+      writer.uint32(10).bytes(message.payload);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): KeyRefresh {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseKeyRefresh();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.payload = reader.bytes();
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  fromJSON(object: any): KeyRefresh {
+    return { payload: isSet(object.payload) ? bytesFromBase64(object.payload) : new Uint8Array(0) };
+  },
+
+  toJSON(message: KeyRefresh): unknown {
+    const obj: any = {};
+    if (message.payload.length !== 0) {
+      obj.payload = base64FromBytes(message.payload);
+    }
+    return obj;
+  },
+
+  create<I extends Exact<DeepPartial<KeyRefresh>, I>>(base?: I): KeyRefresh {
+    return KeyRefresh.fromPartial(base ?? ({} as any));
+  },
+  fromPartial<I extends Exact<DeepPartial<KeyRefresh>, I>>(object: I): KeyRefresh {
+    const message = createBaseKeyRefresh();
+    message.payload = object.payload ?? new Uint8Array(0);
+    return message;
+  },
+};
+
 function createBaseError(): Error {
-  return { code: 0, message: "", retriable: false };
+  return { code: 0, message: "", retriable: false, reason: "", blamedParties: [] };
 }
 
 export const Error = {
@@ -888,6 +1000,14 @@ export const Error = {
     if (message.retriable !== false) {
       writer.uint32(24).bool(message.retriable);
     }
+    if (message.reason !== "") {
+      writer.uint32(34).string(message.reason);
+    }
+    writer.uint32(42).fork();
+    for (const v of message.blamedParties) {
+      writer.uint32(v);
+    }
+    writer.ldelim();
     return writer;
   },
 
@@ -919,6 +1039,30 @@ export const Error = {
 
           message.retriable = reader.bool();
           continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.reason = reader.string();
+          continue;
+        case 5:
+          if (tag === 40) {
+            message.blamedParties.push(reader.uint32());
+
+            continue;
+          }
+
+          if (tag === 42) {
+            const end2 = reader.uint32() + reader.pos;
+            while (reader.pos < end2) {
+              message.blamedParties.push(reader.uint32());
+            }
+
+            continue;
+          }
+
+          break;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -933,6 +1077,10 @@ export const Error = {
       code: isSet(object.code) ? globalThis.Number(object.code) : 0,
       message: isSet(object.message) ? globalThis.String(object.message) : "",
       retriable: isSet(object.retriable) ? globalThis.Boolean(object.retriable) : false,
+      reason: isSet(object.reason) ? globalThis.String(object.reason) : "",
+      blamedParties: globalThis.Array.isArray(object?.blamedParties)
+        ? object.blamedParties.map((e: any) => globalThis.Number(e))
+        : [],
     };
   },
 
@@ -947,6 +1095,12 @@ export const Error = {
     if (message.retriable !== false) {
       obj.retriable = message.retriable;
     }
+    if (message.reason !== "") {
+      obj.reason = message.reason;
+    }
+    if (message.blamedParties?.length) {
+      obj.blamedParties = message.blamedParties.map((e) => Math.round(e));
+    }
     return obj;
   },
 
@@ -958,6 +1112,8 @@ export const Error = {
     message.code = object.code ?? 0;
     message.message = object.message ?? "";
     message.retriable = object.retriable ?? false;
+    message.reason = object.reason ?? "";
+    message.blamedParties = object.blamedParties?.map((e) => e) || [];
     return message;
   },
 };
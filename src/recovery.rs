@@ -0,0 +1,65 @@
+//! 재해 복구용 비밀키 복원 (`key-export` 기능)
+//!
+//! 임계값 이상의 키쉐어에서 전체 비밀키를 복원합니다. MPC의 전제를 깨는 작업이므로
+//! 오프라인 복구 절차 전용 빌드에서만 활성화합니다.
+
+use std::error::Error as _;
+
+use napi::{Error, Result, Status};
+use zeroize::Zeroizing;
+
+use cggmp24::key_share::AnyKeyShare;
+use generic_ec::Point;
+
+use crate::sealed::Content;
+use crate::{parse_key_export, KeyShareWithLevel, SupportedCurve};
+
+/// 키쉐어(JSON 또는 바이너리)에서 비밀키(32바이트 big-endian)를 복원하고 공유 공개키와 비교
+pub fn reconstruct<E: SupportedCurve>(shares: &[&[u8]]) -> Result<Zeroizing<Vec<u8>>> {
+  let shares: Vec<KeyShareWithLevel<E>> = shares.iter().map(|s| parse_key_export(s, Content::KeyShare, Some(E::PROTO))).collect::<Result<_>>()?;
+  let first = shares.first().ok_or_else(|| Error::new(Status::InvalidArg, "no key shares"))?;
+  let (min, public_key) = (first.min_signers(), first.core.shared_public_key);
+  if shares.len() < usize::from(min) {
+    return Err(Error::new(Status::InvalidArg, format!("at least {min} key shares are required, got {}", shares.len())));
+  }
+  if let Some(dup) = shares.iter().enumerate().find_map(|(k, ks)| shares[..k].iter().any(|prev| prev.core.i == ks.core.i).then_some(ks.core.i)) {
+    return Err(Error::new(Status::InvalidArg, format!("duplicate key share of party {dup}")));
+  }
+  let sk = cggmp24::key_share::reconstruct_secret_key(&shares).map_err(|e| {
+    let reason = e.source().map(|s| s.to_string()).unwrap_or_default();
+    Error::new(Status::InvalidArg, format!("key reconstruction failed: {reason}"))
+  })?;
+  if Point::generator() * &sk != *public_key {
+    return Err(Error::new(Status::GenericFailure, "reconstructed key does not match the shared public key"));
+  }
+  Ok(Zeroizing::new(sk.as_ref().to_be_bytes().to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+  use cggmp24::supported_curves::Secp256k1;
+
+  use super::*;
+  use crate::test_util::{self, SECRET_KEY};
+
+  fn reconstruct_from(shares: &[Vec<u8>], parties: &[usize]) -> Result<Zeroizing<Vec<u8>>> {
+    let shares: Vec<&[u8]> = parties.iter().map(|&p| shares[p].as_slice()).collect();
+    reconstruct::<Secp256k1>(&shares)
+  }
+
+  #[test]
+  fn any_threshold_subset_recovers_the_key() {
+    let shares = test_util::dealt_shares::<Secp256k1>(3, 2);
+    for parties in [&[0, 1][..], &[2, 0], &[0, 1, 2]] {
+      assert_eq!(&reconstruct_from(&shares, parties).unwrap()[..], SECRET_KEY);
+    }
+  }
+
+  #[test]
+  fn too_few_or_duplicate_shares_are_rejected() {
+    let shares = test_util::dealt_shares::<Secp256k1>(3, 2);
+    assert_eq!(reconstruct_from(&shares, &[1]).err().unwrap().reason, "at least 2 key shares are required, got 1");
+    assert_eq!(reconstruct_from(&shares, &[1, 1]).err().unwrap().reason, "duplicate key share of party 1");
+    assert_eq!(reconstruct_from(&shares, &[]).err().unwrap().reason, "no key shares");
+  }
+}
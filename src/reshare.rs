@@ -0,0 +1,496 @@
+//! 키 재공유(resharing) 프로토콜
+//!
+//! 기존 쉐어를 가진 딜러는 자신의 Lagrange 가중 쉐어 `λ_i·x_i`를 상수항으로 하는 새 다항식을 만들어
+//! Feldman 커밋과 함께 수신자들에게 다시 분배합니다. 수신자는 받은 값을 합쳐 새 쉐어를 얻고,
+//! 공개키는 그대로 유지됩니다. 딜러와 수신자가 같은 위원회면 주기적인 쉐어 갱신(proactive refresh)이 됩니다.
+//!
+//! cggmp24(0.7)의 `key_refresh` 모듈은 aux info 생성(`aux_info_gen`)만 제공하고 쉐어 갱신 프로토콜은 없으므로
+//! ("does not (currently) support key refresh") 쉐어 재분배는 여기서 직접 구현합니다.
+//!
+//! 라운드 구성:
+//! 1. 딜러가 커밋을 브로드캐스트하고 수신자마다 쉐어를 P2P로 전송 (P2P 암호화는 실행기의 식별 키가 담당)
+//! 2. (신뢰 브로드캐스트 사용 시) 받은 커밋 전체의 해시를 교환
+//! 3. 수신자가 커밋과 맞지 않는 쉐어를 보낸 딜러를 고발
+//! 4. 고발당한 딜러가 해당 쉐어를 공개. 커밋과 맞으면 고발자는 공개된 쉐어를 대신 쓰고(Feldman/GJKR 고발 처리),
+//!    공개하지 않았거나 맞지 않으면 딜러를 지목. 고발자는 받은 쉐어를 증명할 수 없으므로 고발만으로는 지목하지 않음
+//! 5. 전체 기록의 해시를 교환해, 받은 해시가 모두 내 기록과 같을 때만 새 쉐어를 내놓음. 확인 해시도 일반 브로드캐스트이므로
+//!    다른 파티가 모두 끝났다는 보장은 아니며, 실행기는 새 쉐어를 애플리케이션이 커밋할 때까지 기존 쉐어와 따로 보관
+
+use cggmp24::key_share::{DirtyIncompleteKeyShare, DirtyKeyInfo, IncompleteKeyShare, Validate, VssSetup};
+use cggmp24::ExecutionId;
+use generic_ec::{Curve, NonZero, Point, Scalar, SecretScalar};
+use generic_ec_zkp::polynomial::{lagrange_coefficient_at_zero, Polynomial};
+use rand_core::{CryptoRng, RngCore};
+use round_based::rounds_router::{simple_store::RoundInput, RoundsRouter};
+use round_based::{Delivery, Mpc, MpcParty, Outgoing, ProtocolMessage, SinkExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 재공유 프로토콜 메시지
+#[derive(ProtocolMessage, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum Msg<E: Curve> {
+  Round1(MsgRound1<E>),
+  Round2(MsgRound2<E>),
+  ReliabilityCheck(MsgReliabilityCheck),
+  Complaints(MsgComplaints),
+  Openings(MsgOpenings<E>),
+  Confirm(MsgConfirm),
+}
+
+/// 딜러 다항식의 Feldman 커밋 (딜러가 아니면 `None`)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MsgRound1<E: Curve> {
+  pub commitments: Option<Vec<Point<E>>>,
+}
+
+/// 수신자에게 보내는 쉐어 `f_i(I_j)` (딜러가 아니거나 수신자가 아니면 `None`)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MsgRound2<E: Curve> {
+  pub share: Option<Scalar<E>>,
+}
+
+/// 수신한 Round 1 커밋 전체의 해시 (브로드캐스트 일관성 확인)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MsgReliabilityCheck(pub [u8; 32]);
+
+/// 커밋과 맞지 않는 쉐어를 보낸 딜러 (실행 인덱스, 수신자가 아니면 비어 있음)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MsgComplaints(pub Vec<u16>);
+
+/// 나를 고발한 수신자에게 보낸 쉐어 `(수신자, f_i(I_k))`
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MsgOpenings<E: Curve>(pub Vec<(u16, Scalar<E>)>);
+
+/// 커밋, 고발, 공개한 쉐어 전체의 해시
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MsgConfirm(pub [u8; 32]);
+
+/// 재공유 참가자 구성. 모든 인덱스 벡터는 이번 실행의 파티 인덱스 순서입니다.
+pub struct Setup<E: Curve> {
+  /// 이번 실행에서의 내 인덱스
+  pub i: u16,
+  /// 기존 키의 공개 정보 (공개키, 공개 쉐어, VSS 설정)
+  pub old_key_info: DirtyKeyInfo<E>,
+  /// 각 파티의 기존 키쉐어 인덱스 (딜러가 아니면 `None`)
+  pub old_indices: Vec<Option<u16>>,
+  /// 내 기존 시크릿 쉐어 (딜러인 경우)
+  pub old_share: Option<NonZero<SecretScalar<E>>>,
+  /// 각 파티의 새 키쉐어 인덱스 (수신자가 아니면 `None`)
+  pub new_indices: Vec<Option<u16>>,
+  /// 새 threshold
+  pub new_t: u16,
+  /// Round 1 커밋을 해시로 교환해 확인할지 여부
+  pub reliable_broadcast: bool,
+}
+
+#[derive(Debug)]
+pub enum Error {
+  /// 참가자 구성이 잘못됨
+  InvalidSetup(&'static str),
+  /// 메시지 송수신 실패
+  Io(String),
+  /// 커밋이 기존 공개 쉐어와 맞지 않는 딜러
+  InvalidCommitment(Vec<u16>),
+  /// 고발당하고 커밋과 맞는 쉐어를 공개하지 않은 딜러
+  InvalidShare(Vec<u16>),
+  /// 성립할 수 없는 고발을 한 파티 (자기 자신이나 딜러가 아닌 파티를 고발, 수신자가 아닌 파티의 고발)
+  FalseComplaint(Vec<u16>),
+  /// 다른 커밋 집합을 받은 파티
+  NotReliable(Vec<u16>),
+  /// 내 기록과 다른 확인 해시를 보낸 파티 (어느 쪽이 잘못했는지는 알 수 없음)
+  NotConfirmed(Vec<u16>),
+  /// 결과 키쉐어 검증 실패
+  InvalidKeyShare(String),
+}
+
+impl std::fmt::Display for Error {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Error::InvalidSetup(reason) => write!(f, "invalid reshare setup: {reason}"),
+      Error::Io(e) => write!(f, "reshare io: {e}"),
+      Error::InvalidCommitment(parties) => write!(f, "invalid commitment from parties {parties:?}"),
+      Error::InvalidShare(parties) => write!(f, "invalid share from parties {parties:?}"),
+      Error::FalseComplaint(parties) => write!(f, "false complaint from parties {parties:?}"),
+      Error::NotReliable(parties) => write!(f, "round 1 not reliable, mismatch from parties {parties:?}"),
+      Error::NotConfirmed(parties) => write!(f, "reshare not confirmed, mismatch from parties {parties:?}"),
+      Error::InvalidKeyShare(e) => write!(f, "resulting key share is invalid: {e}"),
+    }
+  }
+}
+
+/// 새 키쉐어의 평가점 `I_k = k + 1`
+fn new_preimage<E: Curve>(k: u16) -> NonZero<Scalar<E>> {
+  NonZero::from_scalar(Scalar::from(k) + Scalar::one()).expect("k + 1 is nonzero")
+}
+
+fn hash_commitments<E: Curve>(hasher: &mut Sha256, commitments: &[MsgRound1<E>]) {
+  for msg in commitments {
+    match &msg.commitments {
+      Some(cs) => { hasher.update([1u8]); for c in cs { hasher.update(c.to_bytes(true)); } }
+      None => hasher.update([0u8]),
+    }
+  }
+}
+
+/// 재공유를 실행합니다. 수신자가 아니면 `None`을 반환합니다.
+pub async fn run<E, R, M>(setup: Setup<E>, eid: ExecutionId<'_>, rng: &mut R, party: M) -> Result<Option<IncompleteKeyShare<E>>, Error>
+where
+  E: Curve,
+  R: RngCore + CryptoRng,
+  M: Mpc<ProtocolMessage = Msg<E>>,
+{
+  let Setup { i, old_key_info, old_indices, old_share, new_indices, new_t, reliable_broadcast } = setup;
+  let n = u16::try_from(old_indices.len()).map_err(|_| Error::InvalidSetup("too many parties"))?;
+  if new_indices.len() != old_indices.len() || i >= n { return Err(Error::InvalidSetup("party count mismatch")); }
+  let new_n = new_indices.iter().flatten().count() as u16;
+  if new_t < 1 || new_t > new_n { return Err(Error::InvalidSetup("threshold out of range")); }
+  let mut sorted = new_indices.iter().flatten().copied().collect::<Vec<_>>(); sorted.sort_unstable();
+  if sorted.iter().enumerate().any(|(k, &idx)| usize::from(idx) != k) { return Err(Error::InvalidSetup("new indices must be 0..n")); }
+  if old_share.is_some() != old_indices[usize::from(i)].is_some() { return Err(Error::InvalidSetup("old share does not match role")); }
+
+  // 딜러 (실행 인덱스, 기존 평가점, 기존 공개 쉐어)
+  let dealers = old_indices.iter().enumerate().filter_map(|(j, o)| o.map(|o| (j as u16, o)))
+    .map(|(j, o)| Some((j, old_key_info.share_preimage(o)?, *old_key_info.public_shares.get(usize::from(o))?)))
+    .collect::<Option<Vec<_>>>().ok_or(Error::InvalidSetup("unknown old share index"))?;
+  let enough_dealers = match &old_key_info.vss_setup {
+    Some(vss) => dealers.len() >= usize::from(vss.min_signers),
+    None => dealers.len() == old_key_info.public_shares.len(),
+  };
+  if !enough_dealers { return Err(Error::InvalidSetup("not enough dealers")); }
+  let xs = dealers.iter().map(|(_, x, _)| *x).collect::<Vec<_>>();
+  // 추가(additive) 쉐어는 λ = 1
+  let lambdas = (0..dealers.len()).map(|k| match &old_key_info.vss_setup {
+    Some(_) => lagrange_coefficient_at_zero(k, &xs).map(|l| *l),
+    None => Some(Scalar::one()),
+  }).collect::<Option<Vec<Scalar<E>>>>().ok_or(Error::InvalidSetup("duplicate share index"))?;
+
+  let MpcParty { delivery, .. } = party.into_party();
+  let (incomings, mut outgoings) = delivery.split();
+  let mut rounds = RoundsRouter::<Msg<E>>::builder();
+  let round1 = rounds.add_round(RoundInput::<MsgRound1<E>>::broadcast(i, n));
+  let round2 = rounds.add_round(RoundInput::<MsgRound2<E>>::p2p(i, n));
+  let round1_sync = rounds.add_round(RoundInput::<MsgReliabilityCheck>::broadcast(i, n));
+  let round3 = rounds.add_round(RoundInput::<MsgComplaints>::broadcast(i, n));
+  let round4 = rounds.add_round(RoundInput::<MsgOpenings<E>>::broadcast(i, n));
+  let round5 = rounds.add_round(RoundInput::<MsgConfirm>::broadcast(i, n));
+  let mut rounds = rounds.listen(incomings);
+
+  // Round 1: 다항식 샘플링, 커밋 브로드캐스트 및 쉐어 전송
+  let f: Option<Polynomial<Scalar<E>>> = match &old_share {
+    Some(x) => {
+      let pos = dealers.iter().position(|(j, _, _)| *j == i).ok_or(Error::InvalidSetup("dealer missing"))?;
+      let const_term = lambdas[pos] * AsRef::<Scalar<E>>::as_ref(x);
+      Some(Polynomial::sample_with_const_term(rng, usize::from(new_t) - 1, const_term))
+    }
+    None => None,
+  };
+  let share_for = |k: u16| -> Option<Scalar<E>> { Some(f.as_ref()?.value::<_, Scalar<E>>(&*new_preimage::<E>(new_indices[usize::from(k)]?))) };
+  let my_commitment = MsgRound1 { commitments: f.as_ref().map(|f| (f * &Point::generator()).into_coefs()) };
+  outgoings.feed(Outgoing::broadcast(Msg::Round1(my_commitment.clone()))).await.map_err(|e| Error::Io(e.to_string()))?;
+  for j in (0..n).filter(|j| *j != i) {
+    outgoings.feed(Outgoing::p2p(j, Msg::Round2(MsgRound2 { share: share_for(j) }))).await.map_err(|e| Error::Io(e.to_string()))?;
+  }
+  outgoings.flush().await.map_err(|e| Error::Io(e.to_string()))?;
+
+  // Reliability Check: 모두가 같은 커밋을 받았는지 해시로 확인
+  let commitments = rounds.complete(round1).await.map_err(|e| Error::Io(e.to_string()))?.into_vec_including_me(my_commitment);
+  if reliable_broadcast {
+    let mut hasher = Sha256::new();
+    hasher.update(b"cggmp-node-binding.reshare.echo"); hasher.update(eid.as_bytes());
+    hash_commitments(&mut hasher, &commitments);
+    let echo: [u8; 32] = hasher.finalize().into();
+    outgoings.send(Outgoing::broadcast(Msg::ReliabilityCheck(MsgReliabilityCheck(echo)))).await.map_err(|e| Error::Io(e.to_string()))?;
+    let echoes = rounds.complete(round1_sync).await.map_err(|e| Error::Io(e.to_string()))?;
+    let blame = echoes.iter_indexed().filter(|(_, _, h)| h.0 != echo).map(|(j, _, _)| j).collect::<Vec<_>>();
+    if !blame.is_empty() { return Err(Error::NotReliable(blame)); }
+  }
+
+  // 커밋 검증: 상수항은 λ_i·X_i 와 같아야 함
+  let blame = (0..n).filter(|&j| {
+    let dealer = dealers.iter().position(|(d, _, _)| *d == j);
+    match (dealer, &commitments[usize::from(j)].commitments) {
+      (Some(pos), Some(cs)) => cs.len() != usize::from(new_t) || cs[0] != dealers[pos].2 * lambdas[pos],
+      (None, None) => false,
+      _ => true,
+    }
+  }).collect::<Vec<_>>();
+  if !blame.is_empty() { return Err(Error::InvalidCommitment(blame)); }
+  let polys = dealers.iter().map(|(j, _, _)| {
+    Polynomial::from_coefs(commitments[usize::from(*j)].commitments.clone().unwrap_or_default())
+  }).collect::<Vec<Polynomial<Point<E>>>>();
+  // 딜러 `pos`가 실행 인덱스 `k`의 수신자에게 보낸 쉐어가 커밋과 맞는지
+  let share_is_valid = |pos: usize, k: u16, share: &Scalar<E>| match new_indices[usize::from(k)] {
+    Some(new_k) => Point::generator() * share == polys[pos].value::<_, Point<E>>(&*new_preimage::<E>(new_k)),
+    None => false,
+  };
+
+  // Round 3: 커밋과 맞지 않는 쉐어를 보낸 딜러를 고발
+  let mut shares = rounds.complete(round2).await.map_err(|e| Error::Io(e.to_string()))?.into_vec_including_me(MsgRound2 { share: share_for(i) });
+  let my_complaints = match new_indices[usize::from(i)] {
+    Some(_) => dealers.iter().enumerate().filter(|(pos, (j, _, _))| match &shares[usize::from(*j)].share {
+      Some(s) => !share_is_valid(*pos, i, s),
+      None => true,
+    }).map(|(_, (j, _, _))| *j).collect(),
+    None => Vec::new(),
+  };
+  outgoings.send(Outgoing::broadcast(Msg::Complaints(MsgComplaints(my_complaints.clone())))).await.map_err(|e| Error::Io(e.to_string()))?;
+  let complaints = rounds.complete(round3).await.map_err(|e| Error::Io(e.to_string()))?.into_vec_including_me(MsgComplaints(my_complaints));
+
+  // Round 4: 나를 고발한 수신자의 쉐어를 공개해 모두가 커밋으로 확인할 수 있게 함
+  let my_openings: Vec<_> = (0..n).filter(|k| complaints[usize::from(*k)].0.contains(&i)).filter_map(|k| Some((k, share_for(k)?))).collect();
+  outgoings.send(Outgoing::broadcast(Msg::Openings(MsgOpenings(my_openings.clone())))).await.map_err(|e| Error::Io(e.to_string()))?;
+  let openings = rounds.complete(round4).await.map_err(|e| Error::Io(e.to_string()))?.into_vec_including_me(MsgOpenings(my_openings));
+
+  // 고발 판정: 공개한 쉐어가 커밋과 맞으면 고발자가 그 쉐어를 쓰고, 아니면 딜러의 잘못
+  let (mut bad_dealers, mut false_complaints) = (Vec::new(), Vec::new());
+  for (k, MsgComplaints(accused)) in (0..n).zip(&complaints) {
+    for &d in accused {
+      let pos = dealers.iter().position(|(j, _, _)| *j == d).filter(|_| d != k && new_indices[usize::from(k)].is_some());
+      let Some(pos) = pos else { false_complaints.push(k); continue };
+      match openings[usize::from(d)].0.iter().find(|(to, _)| *to == k) {
+        Some((_, s)) if share_is_valid(pos, k, s) => if k == i { shares[usize::from(d)].share = Some(*s) },
+        _ => bad_dealers.push(d),
+      }
+    }
+  }
+  bad_dealers.sort_unstable(); bad_dealers.dedup();
+  false_complaints.sort_unstable(); false_complaints.dedup();
+  if !bad_dealers.is_empty() { return Err(Error::InvalidShare(bad_dealers)); }
+  if !false_complaints.is_empty() { return Err(Error::FalseComplaint(false_complaints)); }
+
+  let new_share = match new_indices[usize::from(i)] {
+    Some(my_new) => {
+      let mut x = dealers.iter().filter_map(|(j, _, _)| shares[usize::from(*j)].share).sum::<Scalar<E>>();
+      let x = NonZero::from_secret_scalar(SecretScalar::new(&mut x)).ok_or(Error::InvalidKeyShare("zero share".into()))?;
+      let preimages = (0..new_n).map(new_preimage::<E>).collect::<Vec<_>>();
+      let public_shares = preimages.iter()
+        .map(|p| NonZero::from_point(polys.iter().map(|poly| poly.value::<_, Point<E>>(&**p)).sum::<Point<E>>()))
+        .collect::<Option<Vec<_>>>().ok_or(Error::InvalidKeyShare("zero public share".into()))?;
+      let key_info = DirtyKeyInfo {
+        public_shares,
+        vss_setup: Some(VssSetup { min_signers: new_t, I: preimages }),
+        ..old_key_info
+      };
+      Some(DirtyIncompleteKeyShare { i: my_new, key_info, x }.validate().map_err(|e| Error::InvalidKeyShare(format!("{e}")))?)
+    }
+    None => None,
+  };
+
+  // Round 5: 받은 확인 해시가 모두 내 기록과 같은지 확인. 다른 파티에는 다른 해시가 갈 수 있으므로 다른 파티의 완료는 보장하지 않음
+  let mut hasher = Sha256::new();
+  hasher.update(b"cggmp-node-binding.reshare.confirm"); hasher.update(eid.as_bytes());
+  hash_commitments(&mut hasher, &commitments);
+  for (MsgComplaints(accused), MsgOpenings(opened)) in complaints.iter().zip(&openings) {
+    hasher.update((accused.len() as u64).to_be_bytes());
+    for d in accused { hasher.update(d.to_be_bytes()); }
+    hasher.update((opened.len() as u64).to_be_bytes());
+    for (k, s) in opened { hasher.update(k.to_be_bytes()); hasher.update(s.to_be_bytes()); }
+  }
+  let confirm: [u8; 32] = hasher.finalize().into();
+  outgoings.send(Outgoing::broadcast(Msg::Confirm(MsgConfirm(confirm)))).await.map_err(|e| Error::Io(e.to_string()))?;
+  let confirms = rounds.complete(round5).await.map_err(|e| Error::Io(e.to_string()))?;
+  let blame = confirms.iter_indexed().filter(|(_, _, h)| h.0 != confirm).map(|(j, _, _)| j).collect::<Vec<_>>();
+  if !blame.is_empty() { return Err(Error::NotConfirmed(blame)); }
+  Ok(new_share)
+}
+
+#[cfg(test)]
+mod tests {
+  use cggmp24::key_share::KeyShare;
+  use cggmp24::supported_curves::Secp256k1;
+  use rand::{rngs::StdRng, SeedableRng};
+  use round_based::sim::Simulation;
+
+  use super::*;
+  use crate::{sim, test_util, SecLevel};
+
+  type E = Secp256k1;
+  type Output = Option<Result<Option<IncompleteKeyShare<E>>, Error>>;
+
+  fn secret_key() -> NonZero<SecretScalar<E>> {
+    let mut sk = Scalar::<E>::from_be_bytes(test_util::SECRET_KEY).unwrap();
+    NonZero::from_secret_scalar(SecretScalar::new(&mut sk)).unwrap()
+  }
+
+  /// 2-of-3 키쉐어 (aux info 포함)
+  fn dealt() -> Vec<KeyShare<E, SecLevel>> {
+    let primes = sim::party_primes(3, Some(test_util::primes(3)), &mut sim::SimRng::new(None)).unwrap();
+    cggmp24::trusted_dealer::builder::<E, SecLevel>(3)
+      .set_threshold(Some(2))
+      .set_shared_secret_key(secret_key())
+      .set_pregenerated_primes(primes)
+      .generate_shares(&mut StdRng::seed_from_u64(0))
+      .unwrap()
+  }
+
+  /// 모든 파티가 딜러이자 수신자인 재공유(쉐어 갱신)를 메모리에서 실행 (다른 파티의 중단으로 멈춘 파티는 `None`).
+  /// `tamper(from, to, msg)`로 보내는 메시지를 변조
+  fn refresh(shares: &[IncompleteKeyShare<E>], reliable_broadcast: bool, tamper: impl Fn(u16, u16, &mut Msg<E>)) -> Vec<Output> {
+    let n = shares.len() as u16;
+    let mut rngs: Vec<StdRng> = (0..n).map(|i| StdRng::seed_from_u64(i.into())).collect();
+    let sms = shares.iter().zip(&mut rngs).map(|(ks, rng)| {
+      let everyone: Vec<Option<u16>> = (0..n).map(Some).collect();
+      let setup = Setup {
+        i: ks.i, old_key_info: ks.key_info.clone(), old_indices: everyone.clone(),
+        old_share: Some(ks.x.clone()), new_indices: everyone, new_t: ks.min_signers(), reliable_broadcast,
+      };
+      round_based::state_machine::wrap_protocol(move |party| run(setup, ExecutionId::new(&[1; 32]), rng, party))
+    }).collect::<Vec<_>>();
+
+    test_util::simulate(sms, tamper)
+  }
+
+  fn refreshed(shares: &[IncompleteKeyShare<E>]) -> Vec<IncompleteKeyShare<E>> {
+    refresh(shares, true, |_, _, _| {}).into_iter().map(|out| out.expect("party did not finish").unwrap().unwrap()).collect()
+  }
+
+  fn cores(shares: &[KeyShare<E, SecLevel>]) -> Vec<IncompleteKeyShare<E>> {
+    shares.iter().map(|ks| ks.core.clone().validate().unwrap()).collect()
+  }
+
+  /// 2-of-3 쉐어 `a`, `b`를 Lagrange 보간해 복원한 공개키
+  fn interpolated_public_key(a: &IncompleteKeyShare<E>, b: &IncompleteKeyShare<E>) -> Point<E> {
+    let vss = a.vss_setup.as_ref().unwrap();
+    let xs = [vss.I[usize::from(a.i)], vss.I[usize::from(b.i)]];
+    let secret = [a, b].iter().enumerate()
+      .map(|(k, ks)| *lagrange_coefficient_at_zero(k, &xs).unwrap() * AsRef::<Scalar<E>>::as_ref(&ks.x))
+      .sum::<Scalar<E>>();
+    Point::generator() * secret
+  }
+
+  #[test]
+  fn refresh_keeps_public_key_and_replaces_shares() {
+    let old = cores(&dealt());
+    let new = refreshed(&old);
+    for (old, new) in old.iter().zip(&new) {
+      assert_eq!(new.shared_public_key, old.shared_public_key);
+      assert_eq!(new.i, old.i);
+      assert_ne!(AsRef::<Scalar<E>>::as_ref(&new.x), AsRef::<Scalar<E>>::as_ref(&old.x));
+    }
+    assert_eq!(interpolated_public_key(&new[0], &new[2]), *old[0].shared_public_key);
+  }
+
+  #[test]
+  fn refreshed_shares_can_sign() {
+    let dealt = dealt();
+    let new = refreshed(&cores(&dealt));
+    let shares: Vec<KeyShare<E, SecLevel>> = new.into_iter().zip(&dealt)
+      .map(|(core, ks)| KeyShare::from_parts((core, ks.aux.clone().validate().unwrap())).unwrap())
+      .collect();
+
+    let signers = [0u16, 2];
+    let data = cggmp24::DataToSign::<E>::digest::<Sha256>(b"refreshed");
+    let eid = [2u8; 32];
+    let mut rngs: Vec<StdRng> = (0..2).map(StdRng::seed_from_u64).collect();
+    let mut sim = Simulation::with_capacity(2);
+    for ((i, &p), rng) in (0..).zip(&signers).zip(&mut rngs) {
+      sim.add_party(cggmp24::signing(ExecutionId::new(&eid), i, &signers, &shares[usize::from(p)]).sign_sync(rng, &data));
+    }
+    let sig = sim.run().unwrap().into_vec().swap_remove(0).unwrap();
+    sig.verify(&shares[0].core.shared_public_key, &data).unwrap();
+  }
+
+  #[test]
+  fn old_and_new_shares_do_not_combine() {
+    let old = cores(&dealt());
+    let new = refreshed(&old);
+    assert_eq!(interpolated_public_key(&old[0], &old[1]), *old[0].shared_public_key);
+    assert_eq!(interpolated_public_key(&new[0], &new[1]), *old[0].shared_public_key);
+    assert_ne!(interpolated_public_key(&old[0], &new[1]), *old[0].shared_public_key);
+    assert_ne!(interpolated_public_key(&new[0], &old[1]), *old[0].shared_public_key);
+  }
+
+  /// 정직한 파티 `honest`가 보고한 중단 사유
+  fn blamed(outputs: &[Output], honest: &[usize]) -> Vec<String> {
+    honest.iter().map(|&i| outputs[i].as_ref().expect("party did not finish").as_ref().err().expect("party did not abort").to_string()).collect()
+  }
+
+  #[test]
+  fn invalid_share_blames_its_dealer_after_opening() {
+    // 딜러 0이 파티 1에게 틀린 쉐어를 보내고 고발당하자 틀린 쉐어를 공개
+    let old = cores(&dealt());
+    let outputs = refresh(&old, true, |from, to, msg| match (from, to, msg) {
+      (0, 1, Msg::Round2(MsgRound2 { share: Some(share) })) => *share += Scalar::one(),
+      (0, _, Msg::Openings(MsgOpenings(opened))) => opened.iter_mut().for_each(|(_, share)| *share += Scalar::one()),
+      _ => {}
+    });
+    assert_eq!(blamed(&outputs, &[1, 2]), ["invalid share from parties [0]"; 2]);
+  }
+
+  /// 모든 파티가 새 쉐어를 받았고 그 쉐어로 공개키를 복원할 수 있는지
+  fn assert_completed(old: &[IncompleteKeyShare<E>], outputs: Vec<Output>) {
+    let new: Vec<_> = outputs.into_iter().map(|out| out.expect("party did not finish").unwrap().unwrap()).collect();
+    assert_eq!(interpolated_public_key(&new[0], &new[1]), *old[0].shared_public_key);
+    assert_eq!(interpolated_public_key(&new[1], &new[2]), *old[0].shared_public_key);
+  }
+
+  #[test]
+  fn complaint_is_settled_by_a_valid_opening() {
+    // 파티 1이 딜러 0의 쉐어를 받지 못해 고발해도, 공개된 쉐어가 커밋과 맞으므로 아무도 지목하지 않고 계속 진행
+    let old = cores(&dealt());
+    let outputs = refresh(&old, true, |from, to, msg| {
+      if let (0, 1, Msg::Round2(MsgRound2 { share })) = (from, to, msg) {
+        *share = None;
+      }
+    });
+    assert_completed(&old, outputs);
+  }
+
+  #[test]
+  fn dealer_opening_a_good_share_after_a_bad_one_does_not_get_the_receiver_blamed() {
+    // 딜러 0이 파티 1에게 틀린 쉐어를 보낸 뒤 올바른 쉐어를 공개하면 파티 1은 공개된 쉐어를 씀
+    let old = cores(&dealt());
+    let outputs = refresh(&old, true, |from, to, msg| {
+      if let (0, 1, Msg::Round2(MsgRound2 { share: Some(share) })) = (from, to, msg) {
+        *share += Scalar::one();
+      }
+    });
+    assert_completed(&old, outputs);
+  }
+
+  #[test]
+  fn impossible_complaint_blames_the_complainer() {
+    // 자기 자신을 고발하는 것은 성립할 수 없음
+    let old = cores(&dealt());
+    let outputs = refresh(&old, true, |from, _, msg| {
+      if let (1, Msg::Complaints(MsgComplaints(accused))) = (from, msg) {
+        accused.push(1);
+      }
+    });
+    assert_eq!(blamed(&outputs, &[0, 2]), ["false complaint from parties [1]"; 2]);
+  }
+
+  #[test]
+  fn diverging_transcripts_are_not_confirmed() {
+    // 신뢰 브로드캐스트 없이 딜러 0이 파티 2에게만 다른 공개 메시지를 보내면 아무도 새 쉐어를 받지 않음.
+    // 불일치 상대만 알 수 있고 딜러 0을 지목할 수는 없으므로 실행기는 이 목록으로 책임 파티를 정하지 않음
+    let old = cores(&dealt());
+    let outputs = refresh(&old, false, |from, to, msg| {
+      if let (0, 2, Msg::Openings(MsgOpenings(opened))) = (from, to, msg) {
+        opened.push((0, Scalar::one()));
+      }
+    });
+    assert_eq!(blamed(&outputs, &[0, 1, 2]), [
+      "reshare not confirmed, mismatch from parties [2]",
+      "reshare not confirmed, mismatch from parties [2]",
+      "reshare not confirmed, mismatch from parties [0, 1]",
+    ]);
+  }
+
+  #[test]
+  fn one_wrong_confirm_splits_the_outcome() {
+    // 확인 해시도 일반 브로드캐스트이므로 파티 0이 파티 1에게만 틀린 해시를 보내면 파티 1만 중단하고 나머지는 새 쉐어를 받음
+    let old = cores(&dealt());
+    let outputs = refresh(&old, true, |from, to, msg| {
+      if let (0, 1, Msg::Confirm(MsgConfirm(hash))) = (from, to, msg) {
+        hash[0] ^= 1;
+      }
+    });
+    assert_eq!(blamed(&outputs, &[1]), ["reshare not confirmed, mismatch from parties [0]"]);
+    assert!([0, 2].iter().all(|&i| matches!(outputs[i], Some(Ok(Some(_))))));
+  }
+}
@@ -0,0 +1,301 @@
+//! 암호화된 키쉐어/aux info 컨테이너
+//!
+//! ```text
+//! magic "CGMK" | version u8 | content u8 | curve u8 | kdf u8
+//! [argon2id: m_cost u32 LE | t_cost u32 LE | p_cost u32 LE | salt 16B]
+//! nonce 24B | public key len u8 | public key | key id len u8 | key id | XChaCha20-Poly1305 암호문
+//! ```
+//!
+//! 암호문 앞의 헤더 전체를 AAD로 사용하므로 공개키, 키 ID, KDF 파라미터를 바꾸면 복호화에 실패합니다.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use napi::bindgen_prelude::{Buffer, Either};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 4] = b"CGMK";
+pub const VERSION: u8 = 1;
+
+const KDF_ARGON2ID: u8 = 1;
+const KDF_KEK: u8 = 2;
+
+// OWASP 권장값 (19 MiB, 2회). 가져오기 시 헤더의 KDF 파라미터 상한이기도 함 (조작된 파일로 메모리/CPU 고갈 방지)
+const ARGON2_M_COST: u32 = 19 * 1024;
+const ARGON2_T_COST: u32 = 2;
+const ARGON2_P_COST: u32 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+pub const KEK_LEN: usize = 32;
+
+/// 암호화 키: 문자열은 패스프레이즈(Argon2id), Buffer는 32바이트 KEK. 사용 후 메모리에서 지움
+pub enum Secret {
+  Passphrase(Zeroizing<String>),
+  Kek(Zeroizing<Vec<u8>>),
+}
+
+impl From<Either<String, Buffer>> for Secret {
+  fn from(secret: Either<String, Buffer>) -> Self {
+    match secret {
+      Either::A(passphrase) => Secret::Passphrase(Zeroizing::new(passphrase)),
+      Either::B(kek) => Secret::Kek(Zeroizing::new(kek.to_vec())),
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Content {
+  KeyShare = 1,
+  AuxInfo = 2,
+}
+
+impl Content {
+  pub fn from_u8(v: u8) -> Option<Self> {
+    match v {
+      1 => Some(Content::KeyShare),
+      2 => Some(Content::AuxInfo),
+      _ => None,
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Content::KeyShare => "keyshare",
+      Content::AuxInfo => "aux_info",
+    }
+  }
+}
+
+pub enum Kdf {
+  Argon2id { m_cost: u32, t_cost: u32, p_cost: u32, salt: [u8; SALT_LEN] },
+  Kek,
+}
+
+impl Kdf {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Kdf::Argon2id { .. } => "argon2id",
+      Kdf::Kek => "kek",
+    }
+  }
+}
+
+/// 복호화 없이 읽을 수 있는 헤더
+pub struct Header {
+  pub version: u8,
+  pub content: Content,
+  /// `proto::Curve` 값 (aux info는 0)
+  pub curve: u8,
+  pub kdf: Kdf,
+  pub nonce: [u8; NONCE_LEN],
+  pub public_key: Vec<u8>,
+  pub key_id: Vec<u8>,
+}
+
+/// 키 ID: 압축 공개키의 SHA-256
+pub fn key_id(public_key: &[u8]) -> Vec<u8> {
+  if public_key.is_empty() { Vec::new() } else { Sha256::digest(public_key).to_vec() }
+}
+
+pub fn seal(content: Content, curve: u8, public_key: &[u8], secret: &Secret, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+  let mut rng = rand::rngs::OsRng;
+  let kdf = match secret {
+    Secret::Passphrase(_) => {
+      let mut salt = [0u8; SALT_LEN];
+      rng.fill_bytes(&mut salt);
+      Kdf::Argon2id { m_cost: ARGON2_M_COST, t_cost: ARGON2_T_COST, p_cost: ARGON2_P_COST, salt }
+    }
+    Secret::Kek(_) => Kdf::Kek,
+  };
+  let mut nonce = [0u8; NONCE_LEN];
+  rng.fill_bytes(&mut nonce);
+  let header = Header { version: VERSION, content, curve, kdf, nonce, public_key: public_key.to_vec(), key_id: key_id(public_key) };
+
+  let mut out = header.encode()?;
+  let key = derive_key(&header.kdf, secret)?;
+  let cipher = XChaCha20Poly1305::new((&*key).into());
+  let ciphertext = cipher.encrypt(&XNonce::from(nonce), Payload { msg: plaintext, aad: &out }).map_err(|_| "encryption failed".to_string())?;
+  out.extend_from_slice(&ciphertext);
+  Ok(out)
+}
+
+pub fn open(data: &[u8], secret: &Secret) -> Result<(Header, Zeroizing<Vec<u8>>), String> {
+  let (header, header_len) = Header::decode(data)?;
+  let key = derive_key(&header.kdf, secret)?;
+  let cipher = XChaCha20Poly1305::new((&*key).into());
+  let plaintext = cipher
+    .decrypt(&XNonce::from(header.nonce), Payload { msg: &data[header_len..], aad: &data[..header_len] })
+    .map_err(|_| "decryption failed: wrong passphrase or KEK, or the data was modified".to_string())?;
+  Ok((header, Zeroizing::new(plaintext)))
+}
+
+fn derive_key(kdf: &Kdf, secret: &Secret) -> Result<Zeroizing<[u8; 32]>, String> {
+  let mut key = Zeroizing::new([0u8; 32]);
+  match (kdf, secret) {
+    (Kdf::Argon2id { m_cost, t_cost, p_cost, salt }, Secret::Passphrase(passphrase)) => {
+      if passphrase.is_empty() {
+        return Err("passphrase must not be empty".to_string());
+      }
+      let params = Params::new(*m_cost, *t_cost, *p_cost, Some(key.len())).map_err(|e| format!("invalid argon2 params: {e}"))?;
+      Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut *key)
+        .map_err(|e| format!("argon2: {e}"))?;
+    }
+    (Kdf::Kek, Secret::Kek(kek)) => {
+      if kek.len() != KEK_LEN {
+        return Err(format!("KEK must be {KEK_LEN} bytes, got {}", kek.len()));
+      }
+      key.copy_from_slice(kek);
+    }
+    (Kdf::Argon2id { .. }, Secret::Kek(_)) => return Err("data is encrypted with a passphrase, not a KEK".to_string()),
+    (Kdf::Kek, Secret::Passphrase(_)) => return Err("data is encrypted with a KEK, not a passphrase".to_string()),
+  }
+  Ok(key)
+}
+
+impl Header {
+  fn encode(&self) -> Result<Vec<u8>, String> {
+    let public_key_len = u8::try_from(self.public_key.len()).map_err(|_| "public key too long".to_string())?;
+    let key_id_len = u8::try_from(self.key_id.len()).map_err(|_| "key id too long".to_string())?;
+    let mut out = Vec::with_capacity(128);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[self.version, self.content as u8, self.curve]);
+    match &self.kdf {
+      Kdf::Argon2id { m_cost, t_cost, p_cost, salt } => {
+        out.push(KDF_ARGON2ID);
+        out.extend_from_slice(&m_cost.to_le_bytes());
+        out.extend_from_slice(&t_cost.to_le_bytes());
+        out.extend_from_slice(&p_cost.to_le_bytes());
+        out.extend_from_slice(salt);
+      }
+      Kdf::Kek => out.push(KDF_KEK),
+    }
+    out.extend_from_slice(&self.nonce);
+    out.push(public_key_len);
+    out.extend_from_slice(&self.public_key);
+    out.push(key_id_len);
+    out.extend_from_slice(&self.key_id);
+    Ok(out)
+  }
+
+  /// 헤더와 헤더 길이 (암호문 시작 위치)
+  pub fn decode(data: &[u8]) -> Result<(Self, usize), String> {
+    let mut r = Reader { data, pos: 0 };
+    if r.take(MAGIC.len())? != MAGIC {
+      return Err("not an encrypted key container".to_string());
+    }
+    let version = r.u8()?;
+    if version != VERSION {
+      return Err(format!("unsupported container version: {version}"));
+    }
+    let content = r.u8()?;
+    let content = Content::from_u8(content).ok_or_else(|| format!("unknown container content: {content}"))?;
+    let curve = r.u8()?;
+    let kdf = match r.u8()? {
+      KDF_ARGON2ID => {
+        let (m_cost, t_cost, p_cost) = (r.u32()?, r.u32()?, r.u32()?);
+        if m_cost > ARGON2_M_COST || t_cost > ARGON2_T_COST || p_cost > ARGON2_P_COST {
+          return Err(format!("argon2 params exceed the maximum of {ARGON2_M_COST} KiB, {ARGON2_T_COST} passes and {ARGON2_P_COST} lane"));
+        }
+        let mut salt = [0u8; SALT_LEN];
+        salt.copy_from_slice(r.take(SALT_LEN)?);
+        Kdf::Argon2id { m_cost, t_cost, p_cost, salt }
+      }
+      KDF_KEK => Kdf::Kek,
+      other => return Err(format!("unknown kdf: {other}")),
+    };
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(r.take(NONCE_LEN)?);
+    let len = usize::from(r.u8()?);
+    let public_key = r.take(len)?.to_vec();
+    let len = usize::from(r.u8()?);
+    let key_id = r.take(len)?.to_vec();
+    Ok((Header { version, content, curve, kdf, nonce, public_key, key_id }, r.pos))
+  }
+}
+
+struct Reader<'a> {
+  data: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+    let end = self.pos.checked_add(n).filter(|&end| end <= self.data.len()).ok_or("truncated container")?;
+    let out = &self.data[self.pos..end];
+    self.pos = end;
+    Ok(out)
+  }
+
+  fn u8(&mut self) -> Result<u8, String> {
+    Ok(self.take(1)?[0])
+  }
+
+  fn u32(&mut self) -> Result<u32, String> {
+    let b = self.take(4)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PLAINTEXT: &[u8] = b"{\"core\":\"secret\"}";
+  const PUBLIC_KEY: &[u8] = &[2; 33];
+
+  fn passphrase(p: &str) -> Secret {
+    Secret::Passphrase(Zeroizing::new(p.to_string()))
+  }
+
+  fn kek(byte: u8) -> Secret {
+    Secret::Kek(Zeroizing::new(vec![byte; KEK_LEN]))
+  }
+
+  #[test]
+  fn seal_then_open_round_trips() {
+    for secret in [passphrase("correct horse"), kek(1)] {
+      let sealed = seal(Content::KeyShare, 1, PUBLIC_KEY, &secret, PLAINTEXT).unwrap();
+      let (header, plaintext) = open(&sealed, &secret).unwrap();
+      assert_eq!(&plaintext[..], PLAINTEXT);
+      assert!(header.content == Content::KeyShare);
+      assert_eq!((header.curve, header.public_key.as_slice()), (1, PUBLIC_KEY));
+      assert_eq!(header.key_id, key_id(PUBLIC_KEY));
+    }
+  }
+
+  #[test]
+  fn wrong_passphrase_or_kek_fails() {
+    let sealed = seal(Content::KeyShare, 1, PUBLIC_KEY, &passphrase("correct horse"), PLAINTEXT).unwrap();
+    let err = open(&sealed, &passphrase("wrong horse")).err().unwrap();
+    assert!(err.starts_with("decryption failed"), "{err}");
+    assert_eq!(open(&sealed, &kek(1)).err().unwrap(), "data is encrypted with a passphrase, not a KEK");
+
+    let sealed = seal(Content::AuxInfo, 0, &[], &kek(1), PLAINTEXT).unwrap();
+    assert!(open(&sealed, &kek(2)).err().unwrap().starts_with("decryption failed"));
+  }
+
+  #[test]
+  fn argon2_params_above_the_written_ones_are_rejected() {
+    let secret = passphrase("correct horse");
+    let sealed = seal(Content::KeyShare, 1, PUBLIC_KEY, &secret, PLAINTEXT).unwrap();
+    // KDF 파라미터 (magic 4 | version, content, curve, kdf 4 | m_cost, t_cost, p_cost)
+    for (offset, value) in [(8, ARGON2_M_COST + 1), (12, ARGON2_T_COST + 1), (16, ARGON2_P_COST + 1)] {
+      let mut raised = sealed.clone();
+      raised[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+      assert_eq!(open(&raised, &secret).err().unwrap(), "argon2 params exceed the maximum of 19456 KiB, 2 passes and 1 lane");
+    }
+  }
+
+  #[test]
+  fn modified_header_fails() {
+    let secret = kek(1);
+    let mut sealed = seal(Content::KeyShare, 1, PUBLIC_KEY, &secret, PLAINTEXT).unwrap();
+    // 공개키의 마지막 바이트 (magic 4 | version, content, curve, kdf 4 | nonce 24 | len 1 | 공개키 33)
+    sealed[4 + 4 + NONCE_LEN + 1 + 32] ^= 1;
+    assert!(open(&sealed, &secret).err().unwrap().starts_with("decryption failed"));
+  }
+}
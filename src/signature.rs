@@ -0,0 +1,236 @@
+//! 체인별 서명 인코딩
+//!
+//! | 형식 | 내용 |
+//! |------|------|
+//! | `der` | ASN.1 DER `SEQUENCE { r INTEGER, s INTEGER }` |
+//! | `compact` | r ‖ s (각 32바이트 big-endian) |
+//! | `ethereum` | low-S r ‖ s ‖ v, v = 27 + 복구 ID (secp256k1) |
+//! | `bitcoin` | low-S로 정규화한 DER (sighash 타입 바이트 제외, secp256k1) |
+
+use cggmp24::Signature;
+use generic_ec::{Curve, Point, Scalar};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SignatureFormat {
+  Der,
+  Compact,
+  Ethereum,
+  Bitcoin,
+}
+
+impl SignatureFormat {
+  pub fn parse(name: &str) -> Result<Self, String> {
+    match name {
+      "der" => Ok(SignatureFormat::Der),
+      "compact" => Ok(SignatureFormat::Compact),
+      "ethereum" => Ok(SignatureFormat::Ethereum),
+      "bitcoin" => Ok(SignatureFormat::Bitcoin),
+      other => Err(format!("unsupported signature format: {other}")),
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      SignatureFormat::Der => "der",
+      SignatureFormat::Compact => "compact",
+      SignatureFormat::Ethereum => "ethereum",
+      SignatureFormat::Bitcoin => "bitcoin",
+    }
+  }
+
+  /// secp256k1에서만 의미가 있는 형식
+  pub fn secp256k1_only(self) -> bool {
+    matches!(self, SignatureFormat::Ethereum | SignatureFormat::Bitcoin)
+  }
+}
+
+/// 서명을 `format`으로 인코딩. 서명은 이미 `public_key`와 `message`로 검증된 것이어야 함
+pub fn encode<E: Curve>(format: SignatureFormat, sig: &Signature<E>, public_key: &Point<E>, message: &Scalar<E>) -> Result<Vec<u8>, String> {
+  Ok(match format {
+    SignatureFormat::Compact => compact(sig),
+    SignatureFormat::Der => der(sig),
+    SignatureFormat::Bitcoin => der(&sig.normalize_s()),
+    SignatureFormat::Ethereum => {
+      let sig = sig.normalize_s();
+      let v = recovery_id(&sig, public_key, message).ok_or("could not compute recovery id")?;
+      let mut out = compact(&sig);
+      out.push(27 + v);
+      out
+    }
+  })
+}
+
+/// `format`의 서명을 읽음. 형식을 주지 않으면 길이로 고름: 곡선의 r ‖ s 길이면 compact, 1바이트 더 길면
+/// Ethereum, 그 외는 DER. 검증에 필요 없는 Ethereum v는 값의 범위만 확인
+pub fn decode<E: Curve>(bytes: &[u8], format: Option<SignatureFormat>) -> Result<Signature<E>, String> {
+  let len = Signature::<E>::serialized_len();
+  let format = format.unwrap_or(match bytes.len() {
+    n if n == len => SignatureFormat::Compact,
+    n if n == len + 1 => SignatureFormat::Ethereum,
+    _ => SignatureFormat::Der,
+  });
+  let compact = match format {
+    SignatureFormat::Compact if bytes.len() == len => bytes.to_vec(),
+    SignatureFormat::Compact => return Err(format!("invalid signature encoding: compact signature must be {len} bytes, got {}", bytes.len())),
+    SignatureFormat::Ethereum if bytes.len() == len + 1 && matches!(bytes[len], 0 | 1 | 27 | 28) => bytes[..len].to_vec(),
+    SignatureFormat::Ethereum => return Err(format!("invalid signature encoding: ethereum signature must be {} bytes ending in v = 0, 1, 27 or 28", len + 1)),
+    SignatureFormat::Der | SignatureFormat::Bitcoin => der_to_compact(bytes, len / 2).ok_or_else(|| format!("invalid signature encoding: not DER ({} bytes)", bytes.len()))?,
+  };
+  let sig = Signature::read_from_slice(&compact).ok_or("invalid signature: r or s is zero or not less than the curve order")?;
+  if format == SignatureFormat::Bitcoin && sig.normalize_s().s != sig.s {
+    return Err("invalid signature encoding: bitcoin signature must have a low s".to_string());
+  }
+  Ok(sig)
+}
+
+pub fn compact<E: Curve>(sig: &Signature<E>) -> Vec<u8> {
+  let mut out = vec![0u8; Signature::<E>::serialized_len()];
+  sig.write_to_slice(&mut out);
+  out
+}
+
+fn der<E: Curve>(sig: &Signature<E>) -> Vec<u8> {
+  let r = der_integer(&sig.r.to_be_bytes());
+  let s = der_integer(&sig.s.to_be_bytes());
+  // 32바이트 스칼라 두 개는 항상 128바이트 미만이므로 짧은 길이 형식
+  let mut out = vec![0x30, (r.len() + s.len()) as u8];
+  out.extend_from_slice(&r);
+  out.extend_from_slice(&s);
+  out
+}
+
+/// 양의 정수 INTEGER: 앞의 0을 제거하고 최상위 비트가 1이면 0x00을 붙임
+fn der_integer(bytes: &[u8]) -> Vec<u8> {
+  let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1);
+  let value = &bytes[start..];
+  let pad = value[0] & 0x80 != 0;
+  let mut out = vec![0x02, (value.len() + usize::from(pad)) as u8];
+  if pad {
+    out.push(0);
+  }
+  out.extend_from_slice(value);
+  out
+}
+
+/// DER `SEQUENCE { r, s }`를 `size`바이트씩 이은 r ‖ s로 변환. DER가 아니면 `None`
+fn der_to_compact(bytes: &[u8], size: usize) -> Option<Vec<u8>> {
+  let (&[0x30, len], body) = bytes.split_first_chunk::<2>()? else { return None };
+  if body.len() != usize::from(len) {
+    return None;
+  }
+  let (r, rest) = der_read_integer(body)?;
+  let (s, rest) = der_read_integer(rest)?;
+  if !rest.is_empty() || r.len() > size || s.len() > size {
+    return None;
+  }
+  let mut out = vec![0u8; 2 * size];
+  out[size - r.len()..size].copy_from_slice(r);
+  out[2 * size - s.len()..].copy_from_slice(s);
+  Some(out)
+}
+
+/// 양의 INTEGER 값(앞의 0x00 제거)과 나머지 바이트
+fn der_read_integer(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+  let (&[0x02, len], rest) = bytes.split_first_chunk::<2>()? else { return None };
+  let len = usize::from(len);
+  if len == 0 || rest.len() < len || rest[0] & 0x80 != 0 {
+    return None;
+  }
+  let (value, rest) = rest.split_at(len);
+  let value = if value.len() > 1 && value[0] == 0 { &value[1..] } else { value };
+  Some((value, rest))
+}
+
+/// R의 y 패리티: 각 후보 R로 공개키를 복구해 서명한 키와 같은 것을 선택
+/// (R.x >= n인 경우는 확률이 무시할 만큼 작아 고려하지 않음)
+fn recovery_id<E: Curve>(sig: &Signature<E>, public_key: &Point<E>, message: &Scalar<E>) -> Option<u8> {
+  let r_inv = sig.r.invert();
+  (0..2u8).find(|&v| {
+    let mut encoded = vec![0x02 + v];
+    encoded.extend_from_slice(&sig.r.to_be_bytes());
+    Point::<E>::from_bytes(&encoded).is_ok_and(|r| (r * sig.s - Point::generator() * message) * r_inv == *public_key)
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use generic_ec::{curves::Secp256k1, SecretScalar};
+
+  use super::*;
+
+  // EIP-155 예제 트랜잭션: 개인키 0x4646..46, 서명 해시, v = 37 (chain id 1, 복구 ID 0)
+  const SECRET_KEY: [u8; 32] = [0x46; 32];
+  const HASH: &str = "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53";
+  const R: &str = "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276";
+  const S: &str = "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+  const HIGH_S: &str = "98341627668089e51348fccfb4c7ff31c55912f2d2e47ef09652acf665fad3be";
+
+  fn vector(s: &str) -> (Signature<Secp256k1>, Point<Secp256k1>, Scalar<Secp256k1>) {
+    let sig = decode::<Secp256k1>(&hex::decode(format!("{R}{s}")).unwrap(), None).unwrap();
+    let mut sk = Scalar::<Secp256k1>::from_be_bytes(SECRET_KEY).unwrap();
+    let public_key = Point::generator() * SecretScalar::new(&mut sk);
+    let message = Scalar::from_be_bytes(hex::decode(HASH).unwrap()).unwrap();
+    (sig, public_key, message)
+  }
+
+  #[test]
+  fn ethereum_v_recovers_the_signing_key() {
+    for s in [S, HIGH_S] {
+      let (sig, public_key, message) = vector(s);
+      let out = encode(SignatureFormat::Ethereum, &sig, &public_key, &message).unwrap();
+      assert_eq!(hex::encode(&out), format!("{R}{S}1b"));
+    }
+    let (sig, public_key, message) = vector(HIGH_S);
+    assert_eq!(recovery_id(&sig, &public_key, &message), Some(1));
+    assert_eq!(recovery_id(&sig, &(public_key + Point::generator()), &message), None);
+  }
+
+  #[test]
+  fn der_and_compact_round_trip() {
+    let (sig, public_key, message) = vector(HIGH_S);
+    let der = encode(SignatureFormat::Der, &sig, &public_key, &message).unwrap();
+    assert_eq!(hex::encode(&der), format!("3045022028ef{}022100{HIGH_S}", &R[4..]));
+    let bitcoin = encode(SignatureFormat::Bitcoin, &sig, &public_key, &message).unwrap();
+    assert_eq!(hex::encode(&bitcoin), format!("3044022028ef{}0220{S}", &R[4..]));
+
+    let ethereum = encode(SignatureFormat::Ethereum, &sig, &public_key, &message).unwrap();
+    for (format, bytes) in [(SignatureFormat::Der, &der), (SignatureFormat::Compact, &compact(&sig)), (SignatureFormat::Ethereum, &ethereum)] {
+      for decoded in [decode::<Secp256k1>(bytes, None).unwrap(), decode::<Secp256k1>(bytes, Some(format)).unwrap()] {
+        assert_eq!(decoded.r, sig.r);
+        assert!(decoded.s == sig.s || decoded.s == -sig.s);
+      }
+    }
+    assert!(decode::<Secp256k1>(&bitcoin, Some(SignatureFormat::Bitcoin)).is_ok());
+    assert!(decode::<Secp256k1>(&der, Some(SignatureFormat::Bitcoin)).unwrap_err().contains("low s"));
+  }
+
+  #[test]
+  fn an_explicit_format_is_not_guessed() {
+    let (sig, public_key, message) = vector(S);
+    let der = encode(SignatureFormat::Der, &sig, &public_key, &message).unwrap();
+    let ethereum = encode(SignatureFormat::Ethereum, &sig, &public_key, &message).unwrap();
+    assert!(decode::<Secp256k1>(&der, Some(SignatureFormat::Compact)).unwrap_err().contains("must be 64 bytes"));
+    assert!(decode::<Secp256k1>(&ethereum, Some(SignatureFormat::Der)).unwrap_err().contains("not DER"));
+    assert!(decode::<Secp256k1>(&ethereum, Some(SignatureFormat::Compact)).is_err());
+    let mut bad_v = ethereum.clone();
+    bad_v[64] = 37;
+    assert!(decode::<Secp256k1>(&bad_v, None).unwrap_err().contains("v = 0, 1, 27 or 28"));
+    // 64바이트는 DER 모양이어도 compact로 읽음
+    let mut der_shaped = vec![0x30, 62, 0x02, 30];
+    der_shaped.extend_from_slice(&[1; 30]);
+    der_shaped.extend_from_slice(&[0x02, 28]);
+    der_shaped.extend_from_slice(&[1; 28]);
+    assert_eq!(der_shaped.len(), 64);
+    assert_eq!(decode::<Secp256k1>(&der_shaped, None).unwrap().r.to_be_bytes()[..4], [0x30, 62, 0x02, 30]);
+    assert!(decode::<Secp256k1>(&der_shaped, Some(SignatureFormat::Der)).is_ok());
+  }
+
+  #[test]
+  fn der_integers_are_minimal() {
+    assert_eq!(der_integer(&[0, 0, 0x7f]), [0x02, 1, 0x7f]);
+    assert_eq!(der_integer(&[0, 0x80]), [0x02, 2, 0, 0x80]);
+    assert_eq!(der_read_integer(&[0x02, 2, 0, 0x80]), Some((&[0x80][..], &[][..])));
+    assert_eq!(der_read_integer(&[0x02, 1, 0x80]), None);
+    assert!(decode::<Secp256k1>(&[0x30, 0], None).is_err());
+  }
+}
@@ -0,0 +1,198 @@
+//! 한 프로세스에서 모든 파티를 실행하는 시뮬레이션 (`round_based::sim`)
+//!
+//! 실행기와 같은 설정으로 상태 머신을 만들어 메시지를 메모리에서 전달합니다.
+//! 네트워크 없이 종단 간 테스트, 픽스처 생성, 벤치마크에 사용합니다.
+//! 시드(`deterministic-rng`)를 주면 실행 ID, 파티별 RNG와 소수 생성을 모두 시드에서 유도하므로 결과가 바이트 단위로 같습니다.
+
+use napi::{Error, Result, Status};
+use rand::{rngs::StdRng, SeedableRng};
+use rayon::prelude::*;
+use round_based::sim::Simulation;
+
+use cggmp24::key_share::{AnyKeyShare, KeyShare};
+use cggmp24::{ExecutionId, Signature};
+use cggmp24_keygen::KeygenBuilder;
+use generic_ec::coords::{AlwaysHasAffineX, HasAffineX};
+use generic_ec::{NonZero, Point};
+
+use crate::hashing::HashMode;
+use crate::sealed::Content;
+use crate::{fresh_seed, hd_error, parse_derivation_path, parse_key_export, AuxInfoMsg, KeyShareWithLevel, SecLevel, SeedSource, SigningOptions, SupportedCurve};
+
+/// 시뮬레이션 난수원. 시드가 없으면 OS 엔트로피, 있으면 실행기(`withSeed()`)와 같은 방식으로 순서대로 유도
+pub struct SimRng(Option<SeedSource>);
+
+impl SimRng {
+  pub fn new(seed: Option<[u8; 32]>) -> Self {
+    Self(seed.map(|seed| SeedSource { seed, counter: 0 }))
+  }
+
+  fn next_seed(&mut self) -> [u8; 32] {
+    match &mut self.0 {
+      Some(source) => source.next(),
+      None => fresh_seed(),
+    }
+  }
+
+  /// 시뮬레이션마다 새 실행 ID
+  fn eid(&mut self) -> [u8; 32] {
+    self.next_seed()
+  }
+
+  fn party_rngs(&mut self, n: usize) -> Vec<StdRng> {
+    (0..n).map(|_| StdRng::from_seed(self.next_seed())).collect()
+  }
+}
+
+pub fn check_parties(n: u16, t: u16) -> Result<()> {
+  if n < 2 || t < 2 || t > n {
+    return Err(Error::new(Status::InvalidArg, format!("invalid parameters: threshold {t} of {n} parties")));
+  }
+  Ok(())
+}
+
+/// 분산 키 생성. `aux_infos`가 있으면 결합한 키쉐어를, 없으면 aux 없는 core 키쉐어를 반환
+pub fn keygen<E: SupportedCurve>(n: u16, t: u16, aux_infos: Option<Vec<AuxInfoMsg>>, rng: &mut SimRng) -> Result<Vec<Vec<u8>>> {
+  check_parties(n, t)?;
+  if aux_infos.as_ref().is_some_and(|aux| aux.len() != usize::from(n)) {
+    return Err(Error::new(Status::InvalidArg, format!("expected {n} aux infos")));
+  }
+  let eid = rng.eid();
+  let mut rngs = rng.party_rngs(n.into());
+  let mut sim = Simulation::with_capacity(n);
+  for (i, rng) in (0..n).zip(&mut rngs) {
+    sim.add_party(KeygenBuilder::<E>::new(ExecutionId::new(&eid), i, n).set_threshold(t).enforce_reliable_broadcast(false).into_state_machine(rng));
+  }
+  let cores = sim_outputs(sim.run(), "keygen")?;
+  match aux_infos {
+    None => cores.iter().map(to_json).collect(),
+    Some(aux_infos) => cores.into_iter().zip(aux_infos).map(|(core, aux)| {
+      let ks = KeyShare::from_parts((core, aux)).map_err(|e| Error::new(Status::InvalidArg, format!("aux info does not match key share: {}", e.error())))?;
+      to_json(&ks)
+    }).collect(),
+  }
+}
+
+/// aux info 생성
+pub fn aux_gen(n: u16, primes: Option<Vec<Vec<u8>>>, rng: &mut SimRng) -> Result<Vec<Vec<u8>>> {
+  if n < 2 {
+    return Err(Error::new(Status::InvalidArg, format!("invalid parameters: {n} parties")));
+  }
+  let primes = party_primes(n, primes, rng)?;
+  let eid = rng.eid();
+  let mut rngs = rng.party_rngs(n.into());
+  let mut sim = Simulation::with_capacity(n);
+  for ((i, rng), primes) in (0..n).zip(&mut rngs).zip(primes) {
+    sim.add_party(cggmp24::aux_info_gen(ExecutionId::new(&eid), i, n, primes).enforce_reliable_broadcast(false).into_state_machine(rng));
+  }
+  sim_outputs(sim.run(), "aux gen")?.iter().map(to_json).collect()
+}
+
+/// 파티별 안전 소수 (`generatePrimes()` 결과). 없으면 병렬로 생성 (시드는 순서대로 미리 뽑아 병렬 실행과 무관하게 결정)
+pub fn party_primes(n: u16, primes: Option<Vec<Vec<u8>>>, rng: &mut SimRng) -> Result<Vec<cggmp24::PregeneratedPrimes<SecLevel>>> {
+  match primes {
+    Some(primes) if primes.len() != usize::from(n) => Err(Error::new(Status::InvalidArg, format!("expected {n} primes"))),
+    Some(primes) => primes.iter().map(|p| bincode::deserialize(p).map_err(|e| Error::new(Status::InvalidArg, format!("invalid primes: {e}")))).collect(),
+    None => {
+      let seeds: Vec<[u8; 32]> = (0..n).map(|_| rng.next_seed()).collect();
+      Ok(seeds.into_par_iter().map(|seed| cggmp24::PregeneratedPrimes::generate(&mut StdRng::from_seed(seed))).collect())
+    }
+  }
+}
+
+/// `shares` 중 `signers`(키 생성 시 파티 인덱스)의 키쉐어로 서명하고, 서명 공개키로 검증한 서명을 반환
+pub fn signing<E: SupportedCurve>(shares: &[Vec<u8>], signers: &[u16], msg: &[u8], options: &SigningOptions, rng: &mut SimRng) -> Result<Signature<E>>
+where
+  Point<E>: HasAffineX<E>,
+  NonZero<Point<E>>: AlwaysHasAffineX<E>,
+{
+  let path = parse_derivation_path(options.derivation_path.as_deref().unwrap_or(""))?;
+  let hash_mode = HashMode::parse(options.hash_mode.as_deref()).map_err(|e| Error::new(Status::InvalidArg, e))?;
+  let data = E::data_to_sign(msg, hash_mode)?;
+  let shares: Vec<KeyShareWithLevel<E>> = shares.iter().map(|s| parse_key_export(s, Content::KeyShare, Some(E::PROTO))).collect::<Result<_>>()?;
+  let selected: Vec<&KeyShareWithLevel<E>> = signers.iter().map(|&p| {
+    shares.iter().find(|ks| ks.core.i == p).ok_or_else(|| Error::new(Status::InvalidArg, format!("key share of signer {p} missing")))
+  }).collect::<Result<_>>()?;
+  let first = selected.first().ok_or_else(|| Error::new(Status::InvalidArg, "no signers"))?;
+  let min = first.min_signers();
+  if signers.len() != usize::from(min) {
+    return Err(Error::new(Status::InvalidArg, format!("expected {min} signers, got {}", signers.len())));
+  }
+  let public_key = if path.is_empty() { *first.core.shared_public_key } else { first.core.derive_child_public_key::<E::Hd, _>(path.iter().copied()).map_err(hd_error)?.public_key };
+
+  let eid = rng.eid();
+  let mut rngs = rng.party_rngs(signers.len());
+  let mut sim = Simulation::with_capacity(min);
+  for ((i, ks), rng) in (0..).zip(&selected).zip(&mut rngs) {
+    let mut signing = cggmp24::signing(ExecutionId::new(&eid), i, signers, ks);
+    if !path.is_empty() {
+      signing = signing.set_derivation_path_with_algo::<E::Hd, _>(path.iter().copied()).map_err(hd_error)?;
+    }
+    sim.add_party(signing.sign_sync(rng, &*data));
+  }
+  let sig = sim_outputs(sim.run(), "signing")?.swap_remove(0);
+  sig.verify(&public_key, &*data).map_err(|_| Error::new(Status::GenericFailure, "signature does not verify against the public key"))?;
+  Ok(sig)
+}
+
+fn sim_outputs<T, E: std::fmt::Display>(result: std::result::Result<round_based::sim::SimResult<std::result::Result<T, E>>, round_based::sim::SimError>, protocol: &str) -> Result<Vec<T>> {
+  let outputs = result.map_err(|e| Error::new(Status::GenericFailure, format!("{protocol} simulation failed: {e}")))?;
+  outputs.into_vec().into_iter().map(|out| out.map_err(|e| Error::new(Status::GenericFailure, format!("{protocol} failed: {e}")))).collect()
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<Vec<u8>> {
+  serde_json::to_vec(value).map_err(|e| Error::new(Status::GenericFailure, format!("export: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+  use cggmp24::supported_curves::Secp256k1;
+  use cggmp24::DataToSign;
+  use sha2::Sha256;
+
+  use super::*;
+  use crate::test_util;
+
+  fn parse(ks: &[u8]) -> KeyShareWithLevel<Secp256k1> {
+    parse_key_export(ks, Content::KeyShare, Some(Secp256k1::PROTO)).unwrap()
+  }
+
+  #[test]
+  fn simulated_keygen_shares_sign() {
+    // aux info 생성은 수십 초가 걸리므로 딜러가 만든 aux info를 사용
+    let aux_infos: Vec<AuxInfoMsg> = test_util::dealt_shares::<Secp256k1>(3, 2).iter()
+      .map(|ks| serde_json::from_value(serde_json::to_value(&parse(ks).aux).unwrap()).unwrap())
+      .collect();
+    let shares = keygen::<Secp256k1>(3, 2, Some(aux_infos), &mut SimRng::new(None)).unwrap();
+    let public_key = *parse(&shares[0]).core.shared_public_key;
+    assert!(shares.iter().all(|ks| *parse(ks).core.shared_public_key == public_key));
+
+    let sig = signing::<Secp256k1>(&shares, &[2, 0], b"simulated", &SigningOptions::default(), &mut SimRng::new(None)).unwrap();
+    sig.verify(&public_key, &DataToSign::<Secp256k1>::digest::<Sha256>(b"simulated")).unwrap();
+  }
+
+  #[test]
+  fn signing_checks_the_signer_set() {
+    let shares = test_util::dealt_shares::<Secp256k1>(3, 2);
+    let err = |signers: &[u16]| signing::<Secp256k1>(&shares, signers, b"m", &SigningOptions::default(), &mut SimRng::new(None)).err().unwrap().reason;
+    assert_eq!(err(&[0, 1, 2]), "expected 2 signers, got 3");
+    assert_eq!(err(&[0, 5]), "key share of signer 5 missing");
+    assert!(keygen::<Secp256k1>(3, 4, None, &mut SimRng::new(None)).is_err());
+  }
+
+  #[test]
+  fn same_seed_gives_identical_bytes() {
+    let dealt = test_util::dealt_shares::<Secp256k1>(3, 2);
+    let run = |seed: [u8; 32]| {
+      let mut rng = SimRng::new(Some(seed));
+      let shares = keygen::<Secp256k1>(3, 2, None, &mut rng).unwrap();
+      let sig = signing::<Secp256k1>(&dealt, &[0, 2], b"seeded", &SigningOptions::default(), &mut rng).unwrap();
+      (shares, crate::signature::compact(&sig))
+    };
+    let (shares, sig) = run([1; 32]);
+    assert_eq!(run([1; 32]), (shares.clone(), sig.clone()));
+    let (other_shares, other_sig) = run([2; 32]);
+    assert_ne!(other_shares, shares);
+    assert_ne!(other_sig, sig);
+  }
+}
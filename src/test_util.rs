@@ -0,0 +1,144 @@
+//! 테스트 공용 도우미: 미리 생성한 안전 소수로 만든 키쉐어와 메모리 안의 Envelope 전달
+
+use std::collections::VecDeque;
+
+use generic_ec::coords::{AlwaysHasAffineX, HasAffineX};
+use generic_ec::{NonZero, Point};
+use napi::bindgen_prelude::Buffer;
+use prost::Message;
+use round_based::state_machine::{ProceedResult, StateMachine};
+use round_based::{Incoming, MessageDestination, MessageType};
+
+use crate::proto::Envelope;
+use crate::{dealer, identity, Executor, StepReport, SupportedCurve};
+
+/// `generatePrimes()`로 만든 파티별 안전 소수 (소수 생성은 테스트마다 하기에 너무 느림)
+const PRIMES: [&[u8]; 4] = [
+  include_bytes!("testdata/primes0.bin"),
+  include_bytes!("testdata/primes1.bin"),
+  include_bytes!("testdata/primes2.bin"),
+  include_bytes!("testdata/primes3.bin"),
+];
+
+pub const SECRET_KEY: [u8; 32] = [7; 32];
+
+pub fn primes(n: u16) -> Vec<Vec<u8>> {
+  PRIMES[..usize::from(n)].iter().map(|p| p.to_vec()).collect()
+}
+
+/// `SECRET_KEY`를 신뢰 딜러로 나눈 키쉐어 (JSON, 파티 순서)
+pub fn dealt_shares<E: SupportedCurve>(n: u16, t: u16) -> Vec<Vec<u8>> {
+  dealer::deal::<E>(&SECRET_KEY, n, t, None, Some(primes(n))).unwrap().into_iter().map(|ks| ks.to_vec()).collect()
+}
+
+/// 키쉐어를 가져온 실행기 (상태 머신이 실행기 필드를 참조하므로 Box에 고정)
+#[allow(clippy::vec_box)]
+pub fn executors<E: SupportedCurve>(shares: &[Vec<u8>], t: u16) -> Vec<Box<Executor<E>>>
+where
+  Point<E>: HasAffineX<E>,
+  NonZero<Point<E>>: AlwaysHasAffineX<E>,
+{
+  let n = shares.len() as u16;
+  (0..n).zip(shares).map(|(i, ks)| {
+    let mut ex = Box::new(Executor::<E>::new("session".to_string(), "execution".to_string(), i, t, n).unwrap());
+    ex.import_keyshare(Buffer::from(ks.clone())).unwrap();
+    ex
+  }).collect()
+}
+
+/// 파티 `i`의 식별 키를 `[i + 1; 32]`로 정해 모든 실행기에 같은 식별 명단을 설정
+pub fn set_identities<E: SupportedCurve>(execs: &mut [Box<Executor<E>>])
+where
+  Point<E>: HasAffineX<E>,
+  NonZero<Point<E>>: AlwaysHasAffineX<E>,
+{
+  let keys: Vec<[u8; 32]> = (1..=execs.len() as u8).map(|i| [i; 32]).collect();
+  let roster: Vec<Vec<u8>> = keys.iter().map(|k| identity::public_key(k).to_vec()).collect();
+  for (ex, key) in execs.iter_mut().zip(&keys) {
+    ex.set_identity(key, &roster).unwrap();
+  }
+}
+
+pub fn decode(env: &[u8]) -> Envelope {
+  Envelope::decode(env).unwrap()
+}
+
+/// Envelope가 `to`에게 전달되는지 (`to_parties`가 비어 있으면 발신자를 제외한 모두)
+pub fn addressed_to(env: &[u8], to: u16) -> bool {
+  let env = decode(env);
+  env.from_party != u32::from(to) && (env.to_parties.is_empty() || env.to_parties.contains(&u32::from(to)))
+}
+
+/// 모든 실행기를 입력 없이 한 번 구동한 뒤 보낼 Envelope가 없을 때까지 `queue`와 이후 메시지를 주고받고,
+/// 각 `step()` 결과를 모아 반환
+pub fn run<E: SupportedCurve>(execs: &mut [&mut Executor<E>], mut queue: Vec<Vec<u8>>) -> Vec<StepReport>
+where
+  Point<E>: HasAffineX<E>,
+  NonZero<Point<E>>: AlwaysHasAffineX<E>,
+{
+  let mut reports = Vec::new();
+  let mut first = true;
+  while first || !queue.is_empty() {
+    let batch = std::mem::take(&mut queue);
+    for ex in execs.iter_mut() {
+      let inputs: Vec<&Vec<u8>> = batch.iter().filter(|env| addressed_to(env, ex.party_index)).collect();
+      if inputs.is_empty() && !first {
+        continue;
+      }
+      let report = ex.step(&inputs).unwrap();
+      queue.extend(report.outgoing.iter().map(|b| b.to_vec()));
+      reports.push(report);
+    }
+    first = false;
+  }
+  reports
+}
+
+/// 상태 머신들을 메모리 안에서 더 진행할 수 없을 때까지 구동하고 파티 순서대로 결과를 반환
+/// (다른 파티의 중단으로 메시지를 기다리다 멈춘 파티는 `None`). `tamper(from, to, msg)`로 전달 중인 메시지를 변조
+pub fn simulate<SM>(mut sms: Vec<SM>, tamper: impl Fn(u16, u16, &mut SM::Msg)) -> Vec<Option<SM::Output>>
+where
+  SM: StateMachine,
+  SM::Msg: Clone,
+{
+  let n = sms.len() as u16;
+  let mut inboxes: Vec<VecDeque<Incoming<SM::Msg>>> = (0..n).map(|_| VecDeque::new()).collect();
+  let mut outputs: Vec<Option<SM::Output>> = (0..n).map(|_| None).collect();
+  let mut id = 0;
+  let mut progressed = true;
+  while progressed && outputs.iter().any(Option::is_none) {
+    progressed = false;
+    for (i, sm) in (0..n).zip(&mut sms) {
+      while outputs[usize::from(i)].is_none() {
+        match sm.proceed() {
+          ProceedResult::SendMsg(out) => {
+            let (recipients, msg_type) = match out.recipient {
+              MessageDestination::AllParties => ((0..n).filter(|&j| j != i).collect(), MessageType::Broadcast),
+              MessageDestination::OneParty(j) => (vec![j], MessageType::P2P),
+            };
+            for j in recipients {
+              let mut msg = out.msg.clone();
+              tamper(i, j, &mut msg);
+              inboxes[usize::from(j)].push_back(Incoming { id, sender: i, msg_type, msg });
+              id += 1;
+            }
+          }
+          ProceedResult::NeedsOneMoreMessage => match inboxes[usize::from(i)].pop_front() {
+            Some(msg) => {
+              progressed = true;
+              sm.received_msg(msg).unwrap_or_else(|_| panic!("party {i} refused a message"))
+            }
+            None => break,
+          },
+          ProceedResult::Output(out) => {
+            progressed = true;
+            outputs[usize::from(i)] = Some(out)
+          }
+          ProceedResult::Yielded => {}
+          ProceedResult::Error(e) => panic!("party {i}: {e}"),
+        }
+      }
+    }
+  }
+  outputs
+}